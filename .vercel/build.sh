@@ -4,4 +4,6 @@ curl https://sh.rustup.rs -sSf | sh -s -- -y
 source "$HOME/.cargo/env"
 rustup target add wasm32-unknown-unknown
 cargo install dioxus-cli
-/vercel/.cargo/bin/dx build --release
\ No newline at end of file
+/vercel/.cargo/bin/dx build --release
+# Put the menu and credits into the built pages, so they show before the wasm loads.
+cargo run --release --bin rummy-nights-prerender -- dist
@@ -0,0 +1,141 @@
+# German
+
+start_game = Neues Spiel
+resume_game = Spiel fortsetzen
+tile_bonus = Steinbonus
+restart = App neu starten
+clear_data = Daten löschen
+language = Sprache
+score_editing = Punkte bearbeiten erlauben
+dealer_tracking = Geber anzeigen
+max_score = Höchstpunktzahl:
+end_at_max_score = Spiel bei Höchstpunktzahl beenden
+tile_bonus_value = Wert des Steinbonus:
+programmer = Programmierung:
+design = UI/UX-Design:
+icons = Symbole:
+tech = Technik:
+love = Mit ❤️ in Rumänien gemacht.
+start_game_button = Spiel starten
+insert_player = Spielername eingeben
+add_players = Bis zu { $max } Spieler hinzufügen
+winners = { $count ->
+    [one] { $names } hat gewonnen!
+   *[other] { $names } teilen sich den Sieg!
+}
+winners_label = DIE GEWINNER SIND
+tie_rule = Bei Gleichstand
+tie_another_round = Weiterspielen
+tie_share_win = Sieg teilen
+tie_fewest_rounds = Wenigste Runden über dem Limit
+end_after_rounds = Nach einer Anzahl von Runden beenden
+round_limit = Runden
+end_at_time = Zu einer festen Uhrzeit beenden
+end_time = Uhrzeit
+round_counter = Runde { $round } von { $limit }
+time_left = noch { $hours }:{ $minutes }
+time_up = Die Zeit ist um - letzte Runde!
+banner_bonus = Wer bekommt den Bonus?
+banner_play = Viel Glück und viel Spaß!
+banner_double = Die Punkte eines Spielers verdoppeln oder die aller?
+winner_label = DER GEWINNER IST
+no_templates_yet = Noch keine Vorlagen gespeichert - leg welche an!
+template_add = Aktuelle Spieler speichern
+name_template = Vorlage benennen
+template_not_enough = Füge zuerst Spieler hinzu!
+template_too_many = Es sind nur 5 Vorlagen erlaubt!
+template_prompt = Bis zu 5 Vorlagen hinzufügen
+score_checking = Punkte prüfen
+banner_wrong_score = Punkte sollten Vielfache von { $step } sein!
+choose_variant = Regeln
+variant_romanian = Rumänisches Rommé
+variant_rummikub = Rummikub
+variant_gin = Gin Rommé
+variant_500 = Rommé 500
+gin_bonus = Gin
+undercut_bonus = Unterbieten
+scoring_highest = Höchste Punktzahl gewinnt
+scoring_lowest = Niedrigste Punktzahl gewinnt
+scoring_penalty = Minuspunkte
+double_game = Doppeltes Spiel
+max_players = Höchstens Spieler:
+everyone = Alle
+history = Vergangene Spiele
+history_prompt = Beendete Spiele
+no_history_yet = Noch keine beendeten Spiele - los, spielt eine Runde!
+share_game = Dieses Spiel teilen
+show_code = QR-Code
+hide_code = Ausblenden
+share_hint = Scanne den Code oder schicke den Link, um dieses Spiel auf einem anderen Gerät zu öffnen.
+share_too_long = Dieses Spiel ist zu lang für einen QR-Code - schicke stattdessen den Link.
+copy_link = Link kopieren
+link_copied = Link kopiert!
+share_problem = Das geteilte Spiel konnte nicht geöffnet werden:
+play_together = Zusammen spielen
+sync_prompt = Auf mehreren Geräten spielen
+sync_hint = Ein Gerät richtet das Spiel aus, die anderen treten mit seinem Code bei, sehen die Punkte live und tragen ihre eigenen ein. Alle Geräte müssen denselben Relay-Server erreichen.
+relay_address = Relay-Server
+host_game = Dieses Spiel ausrichten
+host_needs_game = Starte zuerst ein Spiel, um es auszurichten.
+session_code = Sitzungscode
+join_game = Beitreten
+hosting = Du richtest aus - andere treten mit diesem Code bei:
+joined = Dem Spiel beigetreten
+waiting_for_host = Warte auf das Spiel des Gastgebers...
+sync_connected = Verbunden
+sync_disconnected = Nicht verbunden - prüfe den Relay-Server.
+open_game = Spiel öffnen
+leave_session = Verlassen
+update_available = Eine neue Version der App ist bereit.
+reload = Neu laden
+data_prompt = Export und Import
+current_game = Aktuelles Spiel
+export_json = JSON
+export_csv = CSV
+import_games = Spiele importieren
+import_hint = Importierte beendete Spiele werden zum Verlauf hinzugefügt. Ein importiertes laufendes Spiel ersetzt deines.
+import_done = { $count ->
+    [one] Ein Spiel importiert.
+   *[other] { $count } Spiele importiert.
+}
+import_failed = Import fehlgeschlagen: { $reason }
+storage_problem = Einige gespeicherte Daten konnten nicht geladen werden. Sie wurden beiseitegelegt, damit sie nicht verloren gehen:
+dismiss = OK
+backup = Sicherung
+backup_hint = Einstellungen, Vorlagen, Spieler, das laufende Spiel und der Verlauf in einer Datei.
+download_backup = Herunterladen
+restore_backup = Wiederherstellen
+backup_preview = Sicherung vom { $date }: { $games ->
+        [one] ein beendetes Spiel
+       *[other] { $games } beendete Spiele
+    }, { $players ->
+        [one] ein Spieler
+       *[other] { $players } Spieler
+    }, { $templates ->
+        [one] eine Vorlage
+       *[other] { $templates } Vorlagen
+    }.
+backup_has_game = Sie enthält auch ein laufendes Spiel.
+restore_modes = Zusammenführen ergänzt, was fehlt, und behält deine Einstellungen und dein laufendes Spiel. Ersetzen tauscht alle deine Daten gegen die Sicherung.
+restore_merge = Zusammenführen
+restore_replace = Ersetzen
+cancel = Abbrechen
+backup_and_restore = Sichern und wiederherstellen
+clear_data_confirm = Damit wird alles gelöscht. Zuerst eine Sicherung herunterladen?
+clear_anyway = Trotzdem löschen
+stats_prompt = Spielerstatistik
+stats_games = Gespielte Spiele
+stats_wins = Siege
+stats_win_rate = Siegquote
+stats_average_round = Durchschnitt pro Runde
+stats_best_round = Beste Runde
+stats_worst_round = Schlechteste Runde
+stats_tile_bonus = Steinboni
+stats_doubled = Verdoppelte Spiele
+rounds_view = Runde für Runde
+totals_view = Summen
+breakdown_hint = Tippe auf einen Namen, um zu sehen, wie die Summe zustande kommt.
+breakdown_scores = Punkte: { $scores } = { $total }
+breakdown_bonuses = Boni: { $points }
+breakdown_doubled = Verdoppelt: { $points }
+breakdown_sum = Summe: { $sum }
@@ -0,0 +1,142 @@
+# English - the reference translation. Keys missing from other languages
+# are shown in English.
+
+start_game = New game
+resume_game = Resume game
+tile_bonus = Tile bonus
+restart = Restart app
+clear_data = Clear data
+language = Language
+score_editing = Allow score editing
+dealer_tracking = Dealer tracking
+max_score = Maximum score:
+end_at_max_score = End game at maximum score
+tile_bonus_value = Tile bonus value:
+programmer = Programming:
+design = UI/UX Design:
+icons = Icons:
+tech = Tech:
+love = Made with ❤️ in Romania.
+start_game_button = Start game
+insert_player = Insert player name
+add_players = Add up to { $max } players
+winners = { $count ->
+    [one] { $names } won!
+   *[other] { $names } share the win!
+}
+winners_label = THE WINNERS ARE
+tie_rule = On a tie
+tie_another_round = Play on
+tie_share_win = Share win
+tie_fewest_rounds = Fewest rounds over
+end_after_rounds = End after a number of rounds
+round_limit = Rounds
+end_at_time = End at a set time
+end_time = Time
+round_counter = Round { $round } of { $limit }
+time_left = { $hours }:{ $minutes } left
+time_up = Time's up - last round!
+banner_bonus = Who gets the bonus?
+banner_play = Good luck and have fun!
+banner_double = Double a player's score, or everyone's?
+winner_label = THE WINNER IS
+no_templates_yet = No templates saved yet - add some!
+template_add = Save current players
+name_template = Name this template
+template_not_enough = Add some players first!
+template_too_many = Only 5 templates are allowed!
+template_prompt = Add up to 5 templates
+score_checking = Check scores
+banner_wrong_score = Scores should be multiples of { $step }!
+choose_variant = Rules
+variant_romanian = Romanian rummy
+variant_rummikub = Rummikub
+variant_gin = Gin rummy
+variant_500 = Rummy 500
+gin_bonus = Gin
+undercut_bonus = Undercut
+scoring_highest = Highest wins
+scoring_lowest = Lowest wins
+scoring_penalty = Negative penalties
+double_game = Double game
+max_players = Maximum players:
+everyone = Everyone
+history = Past games
+history_prompt = Finished games
+no_history_yet = No finished games yet - go play some!
+share_game = Share this game
+show_code = QR code
+hide_code = Hide
+share_hint = Scan the code or send the link to open this game on another device.
+share_too_long = This game is too long to fit in a QR code - send the link instead.
+copy_link = Copy link
+link_copied = Link copied!
+share_problem = The shared game could not be opened:
+play_together = Play together
+sync_prompt = Play on several devices
+sync_hint = One device hosts the game, the others join with its code to see the scores live and add their own. Every device needs to reach the same relay server.
+relay_address = Relay server
+host_game = Host this game
+host_needs_game = Start a game first to host it.
+session_code = Session code
+join_game = Join
+hosting = Hosting - others can join with this code:
+joined = Joined the game
+waiting_for_host = Waiting for the host's game...
+sync_connected = Connected
+sync_disconnected = Not connected - check the relay server.
+open_game = Open game
+leave_session = Leave
+update_available = A new version of the app is ready.
+reload = Reload
+data_prompt = Export and import
+current_game = Current game
+export_json = JSON
+export_csv = CSV
+import_games = Import games
+import_hint = Imported finished games are added to the history. An imported current game replaces yours.
+import_done = { $count ->
+    [one] Imported one game.
+   *[other] Imported { $count } games.
+}
+import_failed = Import failed: { $reason }
+storage_problem = Some saved data could not be loaded. It was kept aside, so it isn't lost:
+dismiss = OK
+backup = Backup
+backup_hint = Settings, templates, players, the current game and the history in one file.
+download_backup = Download
+restore_backup = Restore
+backup_preview = Backup from { $date }: { $games ->
+        [one] one finished game
+       *[other] { $games } finished games
+    }, { $players ->
+        [one] one player
+       *[other] { $players } players
+    }, { $templates ->
+        [one] one template
+       *[other] { $templates } templates
+    }.
+backup_has_game = It also has a game in progress.
+restore_modes = Merge adds what's missing and keeps your settings and game in progress. Replace swaps all your data for the backup.
+restore_merge = Merge
+restore_replace = Replace
+cancel = Cancel
+backup_and_restore = Backup and restore
+clear_data_confirm = This deletes everything. Download a backup first?
+clear_anyway = Clear anyway
+stats_prompt = Player statistics
+stats_games = Games played
+stats_wins = Wins
+stats_win_rate = Win rate
+stats_average_round = Average round
+stats_best_round = Best round
+stats_worst_round = Worst round
+stats_tile_bonus = Tile bonuses
+stats_doubled = Doubled games
+rounds_view = Round by round
+totals_view = Totals
+breakdown_hint = Tap a name to see how the total adds up.
+breakdown_scores = Scores: { $scores } = { $total }
+breakdown_bonuses = Bonuses: { $points }
+breakdown_doubled = Doubled: { $points }
+breakdown_sum = Total: { $sum }
@@ -0,0 +1,141 @@
+# Spanish
+
+start_game = Nueva partida
+resume_game = Continuar partida
+tile_bonus = Bonus de fichas
+restart = Reiniciar la app
+clear_data = Borrar datos
+language = Idioma
+score_editing = Permitir editar puntuaciones
+dealer_tracking = Seguimiento del repartidor
+max_score = Puntuación máxima:
+end_at_max_score = Terminar la partida en la puntuación máxima
+tile_bonus_value = Valor del bonus de fichas:
+programmer = Programación:
+design = Diseño UI/UX:
+icons = Iconos:
+tech = Tecnología:
+love = Hecho con ❤️ en Rumanía.
+start_game_button = Empezar partida
+insert_player = Nombre del jugador
+add_players = Añade hasta { $max } jugadores
+winners = { $count ->
+    [one] ¡{ $names } ha ganado!
+   *[other] ¡{ $names } comparten la victoria!
+}
+winners_label = LOS GANADORES SON
+tie_rule = En caso de empate
+tie_another_round = Seguir jugando
+tie_share_win = Compartir victoria
+tie_fewest_rounds = Menos rondas por encima
+end_after_rounds = Terminar tras un número de rondas
+round_limit = Rondas
+end_at_time = Terminar a una hora fija
+end_time = Hora
+round_counter = Ronda { $round } de { $limit }
+time_left = Quedan { $hours }:{ $minutes }
+time_up = Se acabó el tiempo - ¡última ronda!
+banner_bonus = ¿Quién se lleva el bonus?
+banner_play = ¡Buena suerte y a divertirse!
+banner_double = ¿Doblar la puntuación de un jugador, o la de todos?
+winner_label = EL GANADOR ES
+no_templates_yet = Aún no hay plantillas guardadas - ¡añade alguna!
+template_add = Guardar los jugadores actuales
+name_template = Nombra esta plantilla
+template_not_enough = ¡Añade jugadores primero!
+template_too_many = ¡Solo se permiten 5 plantillas!
+template_prompt = Añade hasta 5 plantillas
+score_checking = Comprobar puntuaciones
+banner_wrong_score = ¡Las puntuaciones deben ser múltiplos de { $step }!
+choose_variant = Reglas
+variant_romanian = Rummy rumano
+variant_rummikub = Rummikub
+variant_gin = Gin rummy
+variant_500 = Rummy 500
+gin_bonus = Gin
+undercut_bonus = Contraataque
+scoring_highest = Gana el más alto
+scoring_lowest = Gana el más bajo
+scoring_penalty = Penalizaciones negativas
+double_game = Partida doble
+max_players = Máximo de jugadores:
+everyone = Todos
+history = Partidas anteriores
+history_prompt = Partidas terminadas
+no_history_yet = Aún no hay partidas terminadas - ¡a jugar!
+share_game = Compartir esta partida
+show_code = Código QR
+hide_code = Ocultar
+share_hint = Escanea el código o envía el enlace para abrir esta partida en otro dispositivo.
+share_too_long = Esta partida es demasiado larga para un código QR - envía el enlace.
+copy_link = Copiar enlace
+link_copied = ¡Enlace copiado!
+share_problem = No se pudo abrir la partida compartida:
+play_together = Jugar juntos
+sync_prompt = Jugar en varios dispositivos
+sync_hint = Un dispositivo aloja la partida y los demás se unen con su código para ver las puntuaciones en directo y añadir las suyas. Todos los dispositivos deben poder llegar al mismo servidor de enlace.
+relay_address = Servidor de enlace
+host_game = Alojar esta partida
+host_needs_game = Empieza una partida primero para alojarla.
+session_code = Código de sesión
+join_game = Unirse
+hosting = Estás alojando - los demás pueden unirse con este código:
+joined = Te has unido a la partida
+waiting_for_host = Esperando la partida del anfitrión...
+sync_connected = Conectado
+sync_disconnected = Sin conexión - revisa el servidor de enlace.
+open_game = Abrir partida
+leave_session = Salir
+update_available = Hay una nueva versión de la app lista.
+reload = Recargar
+data_prompt = Exportar e importar
+current_game = Partida actual
+export_json = JSON
+export_csv = CSV
+import_games = Importar partidas
+import_hint = Las partidas terminadas importadas se añaden al historial. Una partida actual importada sustituye a la tuya.
+import_done = { $count ->
+    [one] Se importó una partida.
+   *[other] Se importaron { $count } partidas.
+}
+import_failed = La importación falló: { $reason }
+storage_problem = No se pudieron cargar algunos datos guardados. Se han apartado para que no se pierdan:
+dismiss = OK
+backup = Copia de seguridad
+backup_hint = Ajustes, plantillas, jugadores, la partida actual y el historial en un solo archivo.
+download_backup = Descargar
+restore_backup = Restaurar
+backup_preview = Copia del { $date }: { $games ->
+        [one] una partida terminada
+       *[other] { $games } partidas terminadas
+    }, { $players ->
+        [one] un jugador
+       *[other] { $players } jugadores
+    }, { $templates ->
+        [one] una plantilla
+       *[other] { $templates } plantillas
+    }.
+backup_has_game = También tiene una partida en curso.
+restore_modes = Combinar añade lo que falta y conserva tus ajustes y tu partida en curso. Sustituir cambia todos tus datos por los de la copia.
+restore_merge = Combinar
+restore_replace = Sustituir
+cancel = Cancelar
+backup_and_restore = Copia de seguridad y restauración
+clear_data_confirm = Esto lo borra todo. ¿Descargar antes una copia de seguridad?
+clear_anyway = Borrar igualmente
+stats_prompt = Estadísticas de jugadores
+stats_games = Partidas jugadas
+stats_wins = Victorias
+stats_win_rate = Porcentaje de victorias
+stats_average_round = Media por ronda
+stats_best_round = Mejor ronda
+stats_worst_round = Peor ronda
+stats_tile_bonus = Bonus de fichas
+stats_doubled = Partidas dobladas
+rounds_view = Ronda a ronda
+totals_view = Totales
+breakdown_hint = Toca un nombre para ver cómo se suma el total.
+breakdown_scores = Puntos: { $scores } = { $total }
+breakdown_bonuses = Bonificaciones: { $points }
+breakdown_doubled = Doblados: { $points }
+breakdown_sum = Total: { $sum }
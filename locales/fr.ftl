@@ -0,0 +1,141 @@
+# French
+
+start_game = Nouvelle partie
+resume_game = Reprendre la partie
+tile_bonus = Bonus de tuiles
+restart = Redémarrer l'appli
+clear_data = Effacer les données
+language = Langue
+score_editing = Autoriser la modification des scores
+dealer_tracking = Suivi du donneur
+max_score = Score maximum :
+end_at_max_score = Finir la partie au score maximum
+tile_bonus_value = Valeur du bonus de tuiles :
+programmer = Programmation :
+design = Design UI/UX :
+icons = Icônes :
+tech = Technologies :
+love = Fait avec ❤️ en Roumanie.
+start_game_button = Commencer la partie
+insert_player = Nom du joueur
+add_players = Ajoutez jusqu'à { $max } joueurs
+winners = { $count ->
+    [one] { $names } a gagné !
+   *[other] { $names } se partagent la victoire !
+}
+winners_label = LES GAGNANTS SONT
+tie_rule = En cas d'égalité
+tie_another_round = Continuer
+tie_share_win = Partager la victoire
+tie_fewest_rounds = Le moins de manches au-delà
+end_after_rounds = Finir après un nombre de manches
+round_limit = Manches
+end_at_time = Finir à une heure fixée
+end_time = Heure
+round_counter = Manche { $round } sur { $limit }
+time_left = { $hours }:{ $minutes } restantes
+time_up = C'est l'heure - dernière manche !
+banner_bonus = Qui reçoit le bonus ?
+banner_play = Bonne chance et amusez-vous bien !
+banner_double = Doubler le score d'un joueur, ou de tout le monde ?
+winner_label = LE GAGNANT EST
+no_templates_yet = Aucun modèle enregistré - ajoutez-en !
+template_add = Enregistrer les joueurs actuels
+name_template = Nommez ce modèle
+template_not_enough = Ajoutez d'abord des joueurs !
+template_too_many = Seuls 5 modèles sont autorisés !
+template_prompt = Ajoutez jusqu'à 5 modèles
+score_checking = Vérifier les scores
+banner_wrong_score = Les scores doivent être des multiples de { $step } !
+choose_variant = Règles
+variant_romanian = Rami roumain
+variant_rummikub = Rummikub
+variant_gin = Gin rami
+variant_500 = Rami 500
+gin_bonus = Gin
+undercut_bonus = Contre
+scoring_highest = Le plus haut gagne
+scoring_lowest = Le plus bas gagne
+scoring_penalty = Pénalités négatives
+double_game = Partie doublée
+max_players = Joueurs maximum :
+everyone = Tout le monde
+history = Parties passées
+history_prompt = Parties terminées
+no_history_yet = Aucune partie terminée - allez jouer !
+share_game = Partager cette partie
+show_code = Code QR
+hide_code = Masquer
+share_hint = Scannez le code ou envoyez le lien pour ouvrir cette partie sur un autre appareil.
+share_too_long = Cette partie est trop longue pour un code QR - envoyez plutôt le lien.
+copy_link = Copier le lien
+link_copied = Lien copié !
+share_problem = La partie partagée n'a pas pu être ouverte :
+play_together = Jouer ensemble
+sync_prompt = Jouer sur plusieurs appareils
+sync_hint = Un appareil héberge la partie, les autres la rejoignent avec son code pour voir les scores en direct et ajouter les leurs. Tous les appareils doivent pouvoir joindre le même serveur relais.
+relay_address = Serveur relais
+host_game = Héberger cette partie
+host_needs_game = Commencez d'abord une partie pour l'héberger.
+session_code = Code de session
+join_game = Rejoindre
+hosting = Vous hébergez - les autres peuvent rejoindre avec ce code :
+joined = Partie rejointe
+waiting_for_host = En attente de la partie de l'hôte...
+sync_connected = Connecté
+sync_disconnected = Non connecté - vérifiez le serveur relais.
+open_game = Ouvrir la partie
+leave_session = Quitter
+update_available = Une nouvelle version de l'appli est prête.
+reload = Recharger
+data_prompt = Export et import
+current_game = Partie en cours
+export_json = JSON
+export_csv = CSV
+import_games = Importer des parties
+import_hint = Les parties terminées importées sont ajoutées à l'historique. Une partie en cours importée remplace la vôtre.
+import_done = { $count ->
+    [one] { $count } partie importée.
+   *[other] { $count } parties importées.
+}
+import_failed = L'import a échoué : { $reason }
+storage_problem = Certaines données enregistrées n'ont pas pu être chargées. Elles ont été mises de côté pour ne pas être perdues :
+dismiss = OK
+backup = Sauvegarde
+backup_hint = Les réglages, les modèles, les joueurs, la partie en cours et l'historique dans un seul fichier.
+download_backup = Télécharger
+restore_backup = Restaurer
+backup_preview = Sauvegarde du { $date } : { $games ->
+        [one] { $games } partie terminée
+       *[other] { $games } parties terminées
+    }, { $players ->
+        [one] { $players } joueur
+       *[other] { $players } joueurs
+    }, { $templates ->
+        [one] { $templates } modèle
+       *[other] { $templates } modèles
+    }.
+backup_has_game = Elle contient aussi une partie en cours.
+restore_modes = Fusionner ajoute ce qui manque et garde vos réglages et votre partie en cours. Remplacer échange toutes vos données contre la sauvegarde.
+restore_merge = Fusionner
+restore_replace = Remplacer
+cancel = Annuler
+backup_and_restore = Sauvegarde et restauration
+clear_data_confirm = Cela efface tout. Télécharger d'abord une sauvegarde ?
+clear_anyway = Effacer quand même
+stats_prompt = Statistiques des joueurs
+stats_games = Parties jouées
+stats_wins = Victoires
+stats_win_rate = Taux de victoire
+stats_average_round = Moyenne par manche
+stats_best_round = Meilleure manche
+stats_worst_round = Pire manche
+stats_tile_bonus = Bonus de tuiles
+stats_doubled = Parties doublées
+rounds_view = Manche par manche
+totals_view = Totaux
+breakdown_hint = Touchez un nom pour voir comment le total est calculé.
+breakdown_scores = Points : { $scores } = { $total }
+breakdown_bonuses = Bonus : { $points }
+breakdown_doubled = Doublés : { $points }
+breakdown_sum = Total : { $sum }
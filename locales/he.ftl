@@ -0,0 +1,141 @@
+# Hebrew - written right to left.
+
+start_game = משחק חדש
+resume_game = המשך משחק
+tile_bonus = בונוס אבנים
+restart = הפעלה מחדש
+clear_data = מחיקת נתונים
+language = שפה
+score_editing = אפשר עריכת ניקוד
+dealer_tracking = מעקב אחר המחלק
+max_score = ניקוד מרבי:
+end_at_max_score = סיום המשחק בניקוד המרבי
+tile_bonus_value = ערך בונוס האבנים:
+programmer = תכנות:
+design = עיצוב ממשק וחוויית משתמש:
+icons = סמלים:
+tech = טכנולוגיה:
+love = נעשה באהבה ❤️ ברומניה.
+start_game_button = התחלת משחק
+insert_player = שם השחקן
+add_players = אפשר להוסיף עד { $max } שחקנים
+winners = { $count ->
+    [one] { $names } ניצח!
+   *[other] { $names } חולקים את הניצחון!
+}
+winners_label = המנצחים הם
+tie_rule = במקרה של תיקו
+tie_another_round = ממשיכים לשחק
+tie_share_win = חולקים את הניצחון
+tie_fewest_rounds = מי שעבר את הגבול בפחות סיבובים
+end_after_rounds = סיום אחרי מספר סיבובים
+round_limit = סיבובים
+end_at_time = סיום בשעה קבועה
+end_time = שעה
+round_counter = סיבוב { $round } מתוך { $limit }
+time_left = נותרו { $hours }:{ $minutes }
+time_up = הזמן נגמר - סיבוב אחרון!
+banner_bonus = מי מקבל את הבונוס?
+banner_play = בהצלחה ושיהיה כיף!
+banner_double = להכפיל את הניקוד של שחקן אחד, או של כולם?
+winner_label = המנצח הוא
+no_templates_yet = אין עדיין תבניות שמורות - כדאי להוסיף!
+template_add = שמירת השחקנים הנוכחיים
+name_template = שם לתבנית
+template_not_enough = קודם צריך להוסיף שחקנים!
+template_too_many = אפשר לשמור רק 5 תבניות!
+template_prompt = אפשר להוסיף עד 5 תבניות
+score_checking = בדיקת ניקוד
+banner_wrong_score = הניקוד צריך להיות כפולה של { $step }!
+choose_variant = חוקים
+variant_romanian = רמי רומני
+variant_rummikub = רמיקוב
+variant_gin = ג'ין רמי
+variant_500 = רמי 500
+gin_bonus = ג'ין
+undercut_bonus = עקיפה
+scoring_highest = הגבוה מנצח
+scoring_lowest = הנמוך מנצח
+scoring_penalty = קנסות שליליים
+double_game = הכפלת משחק
+max_players = מספר שחקנים מרבי:
+everyone = כולם
+history = משחקים קודמים
+history_prompt = משחקים שהסתיימו
+no_history_yet = עדיין אין משחקים שהסתיימו - לכו לשחק!
+share_game = שיתוף המשחק
+show_code = קוד QR
+hide_code = הסתרה
+share_hint = אפשר לסרוק את הקוד או לשלוח את הקישור כדי לפתוח את המשחק במכשיר אחר.
+share_too_long = המשחק ארוך מדי לקוד QR - עדיף לשלוח את הקישור.
+copy_link = העתקת קישור
+link_copied = הקישור הועתק!
+share_problem = לא ניתן היה לפתוח את המשחק המשותף:
+play_together = משחק משותף
+sync_prompt = משחק בכמה מכשירים
+sync_hint = מכשיר אחד מארח את המשחק, והאחרים מצטרפים עם הקוד שלו כדי לראות את הניקוד בזמן אמת ולהוסיף ניקוד משלהם. כל המכשירים צריכים להגיע לאותו שרת ממסר.
+relay_address = שרת ממסר
+host_game = אירוח המשחק
+host_needs_game = קודם צריך להתחיל משחק כדי לארח אותו.
+session_code = קוד מפגש
+join_game = הצטרפות
+hosting = מארחים - אחרים יכולים להצטרף עם הקוד הזה:
+joined = הצטרפת למשחק
+waiting_for_host = מחכים למשחק של המארח...
+sync_connected = מחובר
+sync_disconnected = אין חיבור - כדאי לבדוק את שרת הממסר.
+open_game = פתיחת המשחק
+leave_session = יציאה
+update_available = גרסה חדשה של האפליקציה מוכנה.
+reload = טעינה מחדש
+data_prompt = ייצוא וייבוא
+current_game = המשחק הנוכחי
+export_json = JSON
+export_csv = CSV
+import_games = ייבוא משחקים
+import_hint = משחקים שהסתיימו ויובאו נוספים להיסטוריה. משחק נוכחי שמיובא מחליף את שלך.
+import_done = { $count ->
+    [one] יובא משחק אחד.
+   *[other] יובאו { $count } משחקים.
+}
+import_failed = הייבוא נכשל: { $reason }
+storage_problem = חלק מהנתונים השמורים לא נטענו. הם נשמרו בצד, כך שהם לא אבדו:
+dismiss = אישור
+backup = גיבוי
+backup_hint = ההגדרות, התבניות, השחקנים, המשחק הנוכחי וההיסטוריה בקובץ אחד.
+download_backup = הורדה
+restore_backup = שחזור
+backup_preview = גיבוי מ-{ $date }: { $games ->
+        [one] משחק אחד שהסתיים
+       *[other] { $games } משחקים שהסתיימו
+    }, { $players ->
+        [one] שחקן אחד
+       *[other] { $players } שחקנים
+    }, { $templates ->
+        [one] תבנית אחת
+       *[other] { $templates } תבניות
+    }.
+backup_has_game = יש בו גם משחק בתהליך.
+restore_modes = מיזוג מוסיף את מה שחסר ושומר על ההגדרות ועל המשחק בתהליך. החלפה מחליפה את כל הנתונים שלך בגיבוי.
+restore_merge = מיזוג
+restore_replace = החלפה
+cancel = ביטול
+backup_and_restore = גיבוי ושחזור
+clear_data_confirm = פעולה זו מוחקת הכול. להוריד קודם גיבוי?
+clear_anyway = למחוק בכל זאת
+stats_prompt = סטטיסטיקת שחקנים
+stats_games = משחקים
+stats_wins = ניצחונות
+stats_win_rate = אחוז ניצחונות
+stats_average_round = סיבוב ממוצע
+stats_best_round = הסיבוב הטוב ביותר
+stats_worst_round = הסיבוב הגרוע ביותר
+stats_tile_bonus = בונוסי אבנים
+stats_doubled = משחקים מוכפלים
+rounds_view = סיבוב אחר סיבוב
+totals_view = סיכומים
+breakdown_hint = אפשר להקיש על שם כדי לראות איך מחושב הסכום.
+breakdown_scores = ניקוד: { $scores } = { $total }
+breakdown_bonuses = בונוסים: { $points }
+breakdown_doubled = הכפלות: { $points }
+breakdown_sum = סך הכול: { $sum }
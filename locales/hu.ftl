@@ -0,0 +1,141 @@
+# Hungarian
+
+start_game = Új játék
+resume_game = Játék folytatása
+tile_bonus = Kőbónusz
+restart = Alkalmazás újraindítása
+clear_data = Adatok törlése
+language = Nyelv
+score_editing = Pontok szerkesztésének engedélyezése
+dealer_tracking = Osztó követése
+max_score = Maximális pontszám:
+end_at_max_score = Játék vége a maximális pontszámnál
+tile_bonus_value = Kőbónusz értéke:
+programmer = Programozás:
+design = UI/UX tervezés:
+icons = Ikonok:
+tech = Technológia:
+love = ❤️-vel készült Romániában.
+start_game_button = Játék indítása
+insert_player = Játékos neve
+add_players = Legfeljebb { $max } játékos hozzáadása
+winners = { $count ->
+    [one] { $names } nyert!
+   *[other] { $names } osztoznak a győzelmen!
+}
+winners_label = A GYŐZTESEK
+tie_rule = Döntetlen esetén
+tie_another_round = Tovább játszunk
+tie_share_win = Közös győzelem
+tie_fewest_rounds = Legkevesebb kör a határ felett
+end_after_rounds = Vége adott számú kör után
+round_limit = Körök
+end_at_time = Vége egy adott időpontban
+end_time = Időpont
+round_counter = { $round }. kör / { $limit }
+time_left = Még { $hours }:{ $minutes }
+time_up = Lejárt az idő - utolsó kör!
+banner_bonus = Ki kapja a bónuszt?
+banner_play = Sok sikert és jó szórakozást!
+banner_double = Egy játékos pontjait duplázzuk, vagy mindenkiét?
+winner_label = A GYŐZTES
+no_templates_yet = Még nincs mentett sablon - adj hozzá néhányat!
+template_add = Jelenlegi játékosok mentése
+name_template = Sablon elnevezése
+template_not_enough = Előbb adj hozzá játékosokat!
+template_too_many = Legfeljebb 5 sablon lehet!
+template_prompt = Legfeljebb 5 sablon hozzáadása
+score_checking = Pontok ellenőrzése
+banner_wrong_score = A pontoknak { $step } többszörösének kell lenniük!
+choose_variant = Szabályok
+variant_romanian = Román römi
+variant_rummikub = Rummikub
+variant_gin = Gin römi
+variant_500 = Römi 500
+gin_bonus = Gin
+undercut_bonus = Alávágás
+scoring_highest = A legtöbb pont nyer
+scoring_lowest = A legkevesebb pont nyer
+scoring_penalty = Negatív büntetőpontok
+double_game = Dupla játék
+max_players = Játékosok legfeljebb:
+everyone = Mindenki
+history = Korábbi játékok
+history_prompt = Befejezett játékok
+no_history_yet = Még nincs befejezett játék - irány játszani!
+share_game = Játék megosztása
+show_code = QR-kód
+hide_code = Elrejtés
+share_hint = Olvasd be a kódot, vagy küldd el a linket, hogy a játékot egy másik eszközön nyisd meg.
+share_too_long = Ez a játék túl hosszú egy QR-kódhoz - küldd el inkább a linket.
+copy_link = Link másolása
+link_copied = Link másolva!
+share_problem = A megosztott játékot nem sikerült megnyitni:
+play_together = Közös játék
+sync_prompt = Játék több eszközön
+sync_hint = Egy eszköz vezeti a játékot, a többiek a kódjával csatlakoznak, élőben látják a pontokat és a sajátjukat is beírhatják. Minden eszköznek el kell érnie ugyanazt a közvetítő szervert.
+relay_address = Közvetítő szerver
+host_game = Játék vezetése
+host_needs_game = A vezetéshez előbb indíts egy játékot.
+session_code = Munkamenet kódja
+join_game = Csatlakozás
+hosting = Te vezeted - a többiek ezzel a kóddal csatlakozhatnak:
+joined = Csatlakoztál a játékhoz
+waiting_for_host = Várakozás a vezető játékára...
+sync_connected = Kapcsolódva
+sync_disconnected = Nincs kapcsolat - ellenőrizd a közvetítő szervert.
+open_game = Játék megnyitása
+leave_session = Kilépés
+update_available = Elkészült az alkalmazás új verziója.
+reload = Újratöltés
+data_prompt = Exportálás és importálás
+current_game = Jelenlegi játék
+export_json = JSON
+export_csv = CSV
+import_games = Játékok importálása
+import_hint = Az importált befejezett játékok bekerülnek az előzmények közé. Egy importált folyamatban lévő játék lecseréli a tiédet.
+import_done = { $count ->
+    [one] Egy játék importálva.
+   *[other] { $count } játék importálva.
+}
+import_failed = Az importálás nem sikerült: { $reason }
+storage_problem = Néhány mentett adatot nem sikerült betölteni. Félretettük őket, hogy ne vesszenek el:
+dismiss = OK
+backup = Biztonsági mentés
+backup_hint = Beállítások, sablonok, játékosok, a jelenlegi játék és az előzmények egyetlen fájlban.
+download_backup = Letöltés
+restore_backup = Visszaállítás
+backup_preview = Mentés ekkor: { $date }: { $games ->
+        [one] egy befejezett játék
+       *[other] { $games } befejezett játék
+    }, { $players ->
+        [one] egy játékos
+       *[other] { $players } játékos
+    }, { $templates ->
+        [one] egy sablon
+       *[other] { $templates } sablon
+    }.
+backup_has_game = Egy folyamatban lévő játék is van benne.
+restore_modes = Az egyesítés hozzáadja, ami hiányzik, és megtartja a beállításaidat és a folyamatban lévő játékodat. A csere minden adatodat a mentésre cseréli.
+restore_merge = Egyesítés
+restore_replace = Csere
+cancel = Mégse
+backup_and_restore = Mentés és visszaállítás
+clear_data_confirm = Ez mindent töröl. Előbb letöltesz egy biztonsági mentést?
+clear_anyway = Törlés mindenképp
+stats_prompt = Játékosstatisztika
+stats_games = Lejátszott játékok
+stats_wins = Győzelmek
+stats_win_rate = Győzelmi arány
+stats_average_round = Körönkénti átlag
+stats_best_round = Legjobb kör
+stats_worst_round = Leggyengébb kör
+stats_tile_bonus = Kőbónuszok
+stats_doubled = Duplázott játékok
+rounds_view = Körönként
+totals_view = Összesítés
+breakdown_hint = Koppints egy névre, hogy lásd, hogyan jön ki az összeg.
+breakdown_scores = Pontok: { $scores } = { $total }
+breakdown_bonuses = Bónuszok: { $points }
+breakdown_doubled = Duplázva: { $points }
+breakdown_sum = Összesen: { $sum }
@@ -0,0 +1,145 @@
+# Romanian
+
+start_game = Joc nou
+resume_game = Reluați jocul
+tile_bonus = Atu
+restart = Reporniți
+clear_data = Ștergeți datele
+language = Limbă
+score_editing = Permiteți editarea scorurilor
+dealer_tracking = Urmărire dealer
+max_score = Scorul maxim:
+end_at_max_score = Limită de scor
+tile_bonus_value = Valoarea atuului:
+programmer = Programator:
+design = Design UI/UX:
+icons = Pictograme:
+tech = Tehnologii:
+love = Creat cu ❤️ în România.
+start_game_button = Începe jocul
+insert_player = Introdu un nume
+add_players = Adaugă până la { $max } jucători
+winners = { $count ->
+    [one] { $names } a câștigat!
+   *[other] { $names } au câștigat împreună!
+}
+winners_label = CÂȘTIGĂTORII SUNT
+tie_rule = La egalitate
+tie_another_round = Încă o rundă
+tie_share_win = Victorie comună
+tie_fewest_rounds = Mai puține runde peste
+end_after_rounds = Termină după un număr de runde
+round_limit = Runde
+end_at_time = Termină la o anumită oră
+end_time = Ora
+round_counter = Runda { $round } din { $limit }
+time_left = { $hours }:{ $minutes } rămase
+time_up = Timpul a expirat - ultima rundă!
+banner_bonus = Cine primește atuuul?
+banner_play = Cel mai bun să câștige!
+banner_double = Dublezi scorul unui singur jucător sau al tuturor?
+winner_label = CÂȘTIGĂTORUL ESTE
+no_templates_yet = Niciun șablon salvat - adaugă câteva!
+template_add = Salvați jucătorii actuali
+name_template = Numește acest șablon
+template_not_enough = Adaugă mai întâi niște jucători!
+template_too_many = Poți avea doar 5 șabloane!
+template_prompt = Adaugă până la 5 șabloane
+score_checking = Verifică scorurile adăugate
+banner_wrong_score = Scorurile trebuie să fie multipli de { $step }!
+choose_variant = Reguli
+variant_romanian = Remi pe tablă
+variant_rummikub = Rummikub
+variant_gin = Gin rummy
+variant_500 = Rummy 500
+gin_bonus = Gin
+undercut_bonus = Subcotare
+scoring_highest = Câștigă cel mai mare
+scoring_lowest = Câștigă cel mai mic
+scoring_penalty = Penalizări negative
+double_game = Joc dublu
+max_players = Număr maxim de jucători:
+everyone = Tuturor
+history = Jocuri trecute
+history_prompt = Jocuri terminate
+no_history_yet = Niciun joc terminat încă - hai să jucăm!
+share_game = Trimite jocul
+show_code = Cod QR
+hide_code = Ascunde
+share_hint = Scanează codul sau trimite linkul ca să deschizi jocul pe alt dispozitiv.
+share_too_long = Jocul e prea lung pentru un cod QR - trimite linkul în schimb.
+copy_link = Copiază linkul
+link_copied = Link copiat!
+share_problem = Jocul trimis nu a putut fi deschis:
+play_together = Joacă împreună
+sync_prompt = Joacă pe mai multe dispozitive
+sync_hint = Un dispozitiv găzduiește jocul, celelalte se alătură cu codul lui ca să vadă scorurile pe loc și să-și adauge propriile scoruri. Toate dispozitivele trebuie să ajungă la același server de legătură.
+relay_address = Server de legătură
+host_game = Găzduiește jocul
+host_needs_game = Începe întâi un joc ca să-l găzduiești.
+session_code = Codul sesiunii
+join_game = Alătură-te
+hosting = Găzduiești - ceilalți se pot alătura cu codul:
+joined = Te-ai alăturat jocului
+waiting_for_host = Se așteaptă jocul gazdei...
+sync_connected = Conectat
+sync_disconnected = Neconectat - verifică serverul de legătură.
+open_game = Deschide jocul
+leave_session = Părăsește
+update_available = O nouă versiune a aplicației este gata.
+reload = Reîncarcă
+data_prompt = Export și import
+current_game = Jocul curent
+export_json = JSON
+export_csv = CSV
+import_games = Importă jocuri
+import_hint = Jocurile terminate importate sunt adăugate în istoric. Un joc curent importat îl înlocuiește pe al tău.
+import_done = { $count ->
+    [one] Am importat un joc.
+    [few] Am importat { $count } jocuri.
+   *[other] Am importat { $count } de jocuri.
+}
+import_failed = Importul a eșuat: { $reason }
+storage_problem = Unele date salvate nu au putut fi încărcate. Au fost păstrate separat, ca să nu se piardă:
+dismiss = OK
+backup = Copie de rezervă
+backup_hint = Setările, șabloanele, jucătorii, jocul curent și istoricul într-un singur fișier.
+download_backup = Descarcă
+restore_backup = Restaurează
+backup_preview = Copie din { $date }: { $games ->
+        [one] un joc terminat
+        [few] { $games } jocuri terminate
+       *[other] { $games } de jocuri terminate
+    }, { $players ->
+        [one] un jucător
+        [few] { $players } jucători
+       *[other] { $players } de jucători
+    }, { $templates ->
+        [one] un șablon
+        [few] { $templates } șabloane
+       *[other] { $templates } de șabloane
+    }.
+backup_has_game = Are și un joc în desfășurare.
+restore_modes = Combinarea adaugă ce lipsește și păstrează setările și jocul tău. Înlocuirea îți schimbă toate datele cu cele din copie.
+restore_merge = Combină
+restore_replace = Înlocuiește
+cancel = Anulează
+backup_and_restore = Copie de rezervă
+clear_data_confirm = Asta șterge tot. Descarci întâi o copie de rezervă?
+clear_anyway = Șterge oricum
+stats_prompt = Statisticile jucătorilor
+stats_games = Jocuri jucate
+stats_wins = Victorii
+stats_win_rate = Rata de câștig
+stats_average_round = Media pe rundă
+stats_best_round = Cea mai bună rundă
+stats_worst_round = Cea mai slabă rundă
+stats_tile_bonus = Atuuri primite
+stats_doubled = Jocuri dublate
+rounds_view = Pe runde
+totals_view = Totaluri
+breakdown_hint = Apasă pe un nume ca să vezi cum se adună totalul.
+breakdown_scores = Puncte: { $scores } = { $total }
+breakdown_bonuses = Bonusuri: { $points }
+breakdown_doubled = Dublate: { $points }
+breakdown_sum = Total: { $sum }
@@ -1,68 +1,103 @@
-// This is the service worker with the combined offline experience (Offline page + Offline copy of pages)
+// The service worker that keeps the app working offline.
+//
+// Every build caches its files under its own name, taken from `version.js`:
+// the build version and a hash of the built files, written into `dist` by
+// `src/bin/prerender.rs`. Without it the worker doesn't install at all, rather
+// than serving one build's files under another's name. A new build installs
+// next to the running one and waits until the app asks it to take over - see
+// `src/frontend/update.rs` - so the wasm and the page it was built with are
+// always served together.
 
-const CACHE = "rummy-nights-v1.2.0";
+importScripts('version.js');
 
-importScripts('https://storage.googleapis.com/workbox-cdn/releases/5.1.2/workbox-sw.js');
+const CACHE = `rummy-nights-${BUILD_VERSION}`;
+const APP = 'assets/dioxus/rummy-nights.js';
+const OFFLINE_PAGE = 'index.html';
 
-// TODO: replace the following with the correct offline fallback page i.e.: const offlineFallbackPage = "offline.html";
-const offlineFallbackPage = "index.html";
-var offlineFundamentals = [
-  '/favicon.ico',
-  '/index.html',
-  '/manifest.json',
-  '/rummy-nights.js',
-  '/rummy-nights_bg.wasm',
-  '/style.css',
-  '/sw.js',
-  '/snippets/dioxus-interpreter-js-459fb15b86d869f7/src/interpreter.js',
-  '/intro_logo.gif'
+const offlineFundamentals = [
+  './',
+  'index.html',
+  'credits.html',
+  'manifest.json',
+  'style.css',
+  'favicon.ico',
+  'intro_logo.gif',
+  'logo_192.png',
+  'logo_512.png',
+  APP,
+  'assets/dioxus/rummy-nights_bg.wasm',
 ];
 
-self.addEventListener("message", (event) => {
-  if (event.data && event.data.type === "SKIP_WAITING") {
-    self.skipWaiting();
-  }
-});
+// The snippets wasm-bindgen puts next to the app have a hash in their path, so
+// they're found through the imports of the app's script.
+async function appSnippets() {
+  const script = await fetch(APP, { cache: 'no-cache' }).then((response) => response.text());
+  const imports = script.matchAll(/from ['"]\.\/(snippets\/[^'"]+)['"]/g);
+  return Array.from(imports, (found) => `assets/dioxus/${found[1]}`);
+}
 
-self.addEventListener('install', async (event) => {
-  event.waitUntil(
-    caches.open(CACHE)
-      .then((cache) => {
-        cache.add(offlineFallbackPage);
-        cache.addAll(offlineFundamentals);
-      })
-  );
-});
+async function precache() {
+  const cache = await caches.open(CACHE);
+  const files = offlineFundamentals.concat(await appSnippets());
 
-if (workbox.navigationPreload.isSupported()) {
-  workbox.navigationPreload.enable();
+  // Pages that aren't part of every build (like the pre-rendered credits) are
+  // left out rather than failing the install.
+  await Promise.all(files.map(async (file) => {
+    const response = await fetch(file, { cache: 'no-cache' });
+    if (response.ok) {
+      await cache.put(file, response);
+    } else if (file !== 'credits.html') {
+      throw new Error(`Could not cache ${file}: ${response.status}`);
+    }
+  }));
 }
 
-workbox.routing.registerRoute(
-  new RegExp('/*'),
-  new workbox.strategies.StaleWhileRevalidate({
-    cacheName: CACHE
-  })
-);
+async function removeOldCaches() {
+  const names = await caches.keys();
+  await Promise.all(names
+    .filter((name) => name.startsWith('rummy-nights-') && name !== CACHE)
+    .map((name) => caches.delete(name)));
+}
 
-self.addEventListener('fetch', (event) => {
-  if (event.request.mode === 'navigate') {
-    event.respondWith((async () => {
-      try {
-        const preloadResp = await event.preloadResponse;
+async function respond(request) {
+  const cache = await caches.open(CACHE);
+  const cached = await cache.match(request, { ignoreSearch: request.mode === 'navigate' });
+  if (cached) {
+    return cached;
+  }
 
-        if (preloadResp) {
-          return preloadResp;
-        }
+  try {
+    const response = await fetch(request);
+    if (response.ok) {
+      await cache.put(request, response.clone());
+    }
+    return response;
+  } catch (error) {
+    if (request.mode === 'navigate') {
+      return cache.match(OFFLINE_PAGE);
+    }
+    throw error;
+  }
+}
 
-        const networkResp = await fetch(event.request);
-        return networkResp;
-      } catch (error) {
+self.addEventListener('message', (event) => {
+  if (event.data && event.data.type === 'SKIP_WAITING') {
+    self.skipWaiting();
+  }
+});
+
+self.addEventListener('install', (event) => {
+  event.waitUntil(precache());
+});
 
-        const cache = await caches.open(CACHE);
-        const cachedResp = await cache.match(offlineFallbackPage);
-        return cachedResp;
-      }
-    })());
+self.addEventListener('activate', (event) => {
+  event.waitUntil(removeOldCaches().then(() => self.clients.claim()));
+});
+
+self.addEventListener('fetch', (event) => {
+  const url = new URL(event.request.url);
+  if (event.request.method !== 'GET' || url.origin !== self.location.origin) {
+    return;
   }
-});
\ No newline at end of file
+  event.respondWith(respond(event.request));
+});
@@ -0,0 +1,128 @@
+// Checks that a built web app launches without a network connection.
+//
+// Run it after `dx build --release` (and pre-rendering) with
+// `node scripts/check-offline.mjs dist`. It loads `dist/sw.js` the way a browser
+// would, installs and activates it against the files in `dist`, then cuts the
+// network and asks for everything the app needs to start.
+
+import { readFile } from 'node:fs/promises';
+import { join } from 'node:path';
+import vm from 'node:vm';
+
+const dist = process.argv[2] ?? 'dist';
+const origin = 'https://rummy-nights.test';
+let online = true;
+
+const resolve = (request) => new URL(typeof request === 'string' ? request : request.url, `${origin}/sw.js`);
+const key = (request, ignoreSearch) => {
+  const url = resolve(request);
+  if (ignoreSearch) {
+    url.search = '';
+  }
+  return url.pathname === '/' ? `${url.origin}/` : url.href;
+};
+
+async function serve(request) {
+  if (!online) {
+    throw new TypeError('Failed to fetch (offline)');
+  }
+  const path = resolve(request).pathname.replace(/^\/$/, '/index.html');
+  try {
+    return new Response(await readFile(join(dist, path)));
+  } catch {
+    return new Response('Not found', { status: 404 });
+  }
+}
+
+class Cache {
+  entries = new Map();
+  async put(request, response) {
+    this.entries.set(key(request), await response.arrayBuffer());
+  }
+  async match(request, options = {}) {
+    const wanted = key(request, options.ignoreSearch);
+    for (const [url, body] of this.entries) {
+      if (key(url, options.ignoreSearch) === wanted) {
+        return new Response(body.slice(0));
+      }
+    }
+    return undefined;
+  }
+}
+
+const caches = new Map();
+const cacheStorage = {
+  open: async (name) => caches.get(name) ?? caches.set(name, new Cache()).get(name),
+  keys: async () => [...caches.keys()],
+  delete: async (name) => caches.delete(name),
+};
+
+const listeners = {};
+const worker = {
+  location: new URL(`${origin}/sw.js`),
+  addEventListener: (type, listener) => { listeners[type] = listener; },
+  skipWaiting: async () => {},
+  clients: { claim: async () => {} },
+};
+const version = await readFile(join(dist, 'version.js'), 'utf8');
+const context = vm.createContext({
+  ...worker,
+  self: worker,
+  caches: cacheStorage,
+  fetch: serve,
+  URL,
+  Response,
+  console,
+  // The only script the worker imports is the build's version.
+  importScripts: () => vm.runInContext(version, context),
+});
+vm.runInContext(await readFile(join(dist, 'sw.js'), 'utf8'), context);
+
+async function dispatch(type, fields = {}) {
+  const pending = [];
+  let response;
+  listeners[type]?.({
+    ...fields,
+    waitUntil: (promise) => pending.push(promise),
+    respondWith: (promise) => { response = promise; },
+  });
+  await Promise.all(pending);
+  return response;
+}
+
+const cacheName = vm.runInContext('CACHE', context);
+await cacheStorage.open('rummy-nights-an-older-build');
+await dispatch('install');
+await dispatch('activate');
+
+const leftOver = [...caches.keys()].filter((name) => name !== cacheName);
+if (leftOver.length > 0) {
+  throw new Error(`Caches of older builds were kept: ${leftOver.join(', ')}`);
+}
+
+online = false;
+const script = await readFile(join(dist, 'assets/dioxus/rummy-nights.js'), 'utf8');
+const snippets = Array.from(
+  script.matchAll(/from ['"]\.\/(snippets\/[^'"]+)['"]/g),
+  (found) => `/assets/dioxus/${found[1]}`,
+);
+const launch = [
+  { url: '/', mode: 'navigate' },
+  { url: '/?join=ABC123', mode: 'navigate' },
+  { url: '/style.css', mode: 'no-cors' },
+  { url: '/manifest.json', mode: 'cors' },
+  { url: '/intro_logo.gif', mode: 'no-cors' },
+  { url: '/assets/dioxus/rummy-nights.js', mode: 'cors' },
+  { url: '/assets/dioxus/rummy-nights_bg.wasm', mode: 'cors' },
+  ...snippets.map((url) => ({ url, mode: 'cors' })),
+];
+
+for (const { url, mode } of launch) {
+  const request = { url: `${origin}${url}`, method: 'GET', mode };
+  const response = await dispatch('fetch', { request });
+  if (!response || !response.ok) {
+    throw new Error(`${url} is not available offline`);
+  }
+}
+
+console.log(`Launched offline from ${cacheName}: ${launch.length} requests served from the cache.`);
@@ -0,0 +1,185 @@
+//! A single file holding everything the app saves, to move it to another
+//! device or to get it back after clearing the data.
+
+use crate::backend::export::{parse_versioned, validate_game, ImportError};
+use crate::backend::history::ArchivedGame;
+use crate::backend::prelude::*;
+use crate::backend::schema::{self, SCHEMA_VERSION};
+use crate::backend::GameTemplate;
+use serde::{Deserialize, Serialize};
+
+pub const BACKUP_APP: &str = "rummy-nights-backup";
+pub const BACKUP_VERSION: u32 = 1;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub app: String,
+    pub version: u32,
+    /// The storage schema the data was saved in, see [`crate::backend::schema`].
+    pub schema: u32,
+    /// When the backup was made, as a Unix timestamp in seconds.
+    pub created_at: i64,
+    pub settings: Settings,
+    #[serde(default)]
+    pub templates: Vec<GameTemplate>,
+    #[serde(default)]
+    pub roster: Roster,
+    #[serde(default)]
+    pub game: Option<Game>,
+    #[serde(default)]
+    pub history: Vec<ArchivedGame>,
+}
+
+/// What a backup holds, shown before restoring it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackupPreview {
+    pub created_at: i64,
+    pub templates: usize,
+    pub players: usize,
+    pub has_game: bool,
+    pub history: usize,
+}
+
+/// How a restored backup is combined with the data already in the app.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestoreMode {
+    /// Add what's missing, keeping the current settings and game in progress.
+    Merge,
+    /// Throw away the current data and use the backup's.
+    Replace,
+}
+
+impl Backup {
+    pub fn new(
+        settings: Settings,
+        templates: Vec<GameTemplate>,
+        roster: Roster,
+        game: Option<Game>,
+        history: Vec<ArchivedGame>,
+    ) -> Self {
+        Self {
+            app: String::from(BACKUP_APP),
+            version: BACKUP_VERSION,
+            schema: SCHEMA_VERSION,
+            created_at: crate::backend::clock::now(),
+            settings,
+            templates,
+            roster,
+            game,
+            history,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Parse and validate a backup, migrating data saved by older versions.
+    pub fn from_json(text: &str) -> Result<Self, ImportError> {
+        let mut value = parse_versioned(text, BACKUP_APP, BACKUP_VERSION)?;
+
+        let schema = value
+            .get("schema")
+            .and_then(|schema| schema.as_u64())
+            .unwrap_or_default() as u32;
+        if schema > SCHEMA_VERSION {
+            return Err(ImportError::UnsupportedVersion(schema));
+        }
+
+        // Each section has the same layout as the storage key of the same name.
+        for key in ["settings", "templates", "roster", "game", "history"] {
+            if let Some(section) = value.get_mut(key).filter(|section| !section.is_null()) {
+                schema::migrate(key, schema, section);
+            }
+        }
+
+        let backup: Self =
+            serde_json::from_value(value).map_err(|err| ImportError::Malformed(err.to_string()))?;
+
+        if let Some(game) = &backup.game {
+            validate_game(game)?;
+        }
+        for entry in &backup.history {
+            validate_game(&entry.game)?;
+        }
+
+        Ok(backup)
+    }
+
+    /// Point everything that refers to a roster entry to another id.
+    pub fn rename_roster_id(&mut self, old: &str, new: &str) {
+        let players = self
+            .game
+            .iter_mut()
+            .chain(self.history.iter_mut().map(|entry| &mut entry.game))
+            .flat_map(|game| game.players.iter_mut());
+
+        for player in players {
+            if player.uid.as_deref() == Some(old) {
+                player.uid = Some(new.to_string());
+            }
+        }
+
+        for template in &mut self.templates {
+            for id in &mut template.player_ids {
+                if id == old {
+                    *id = new.to_string();
+                }
+            }
+        }
+    }
+
+    pub fn preview(&self) -> BackupPreview {
+        BackupPreview {
+            created_at: self.created_at,
+            templates: self.templates.len(),
+            players: self.roster.entries.len(),
+            has_game: self.game.is_some(),
+            history: self.history.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backup_round_trips_with_a_preview() {
+        let mut roster = Roster::new();
+        roster.register("Antonio", 0);
+        let mut game = Game::new();
+        game.add_player(String::from("Antonio"), 0);
+
+        let json =
+            Backup::new(Settings::new(), Vec::new(), roster, Some(game), Vec::new()).to_json();
+        let preview = Backup::from_json(&json).unwrap().preview();
+
+        assert_eq!(preview.players, 1);
+        assert!(preview.has_game);
+        assert_eq!(preview.history, 0);
+    }
+
+    #[test]
+    fn games_from_older_schemas_are_migrated() {
+        let backup = Backup::new(Settings::new(), Vec::new(), Roster::new(), None, Vec::new());
+        let mut value = serde_json::to_value(backup).unwrap();
+        let mut game = serde_json::to_value(Game::new()).unwrap();
+        game.as_object_mut().unwrap().remove("winners");
+        game["winner_name"] = "Vlad".into();
+        value["game"] = game;
+        value["schema"] = 0.into();
+
+        let backup = Backup::from_json(&value.to_string()).unwrap();
+        assert_eq!(backup.game.unwrap().winners, ["Vlad"]);
+    }
+
+    #[test]
+    fn game_exports_are_not_backups() {
+        let export = crate::backend::export::ExportDocument::new(None, Vec::new()).to_json();
+        assert_eq!(
+            Backup::from_json(&export).err(),
+            Some(ImportError::NotAnExport)
+        );
+    }
+}
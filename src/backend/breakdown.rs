@@ -0,0 +1,183 @@
+//! A game's scores round by round, and how each player's total adds up.
+//!
+//! A [`Player`] keeps what they got in three maps: `score` by round (0-based),
+//! `bonus` by round + 1, and `doubles` by round + 1 for a doubled score and by
+//! round + 1001 for a doubled bonus. `sum` is everything in the three added up.
+
+use crate::backend::prelude::*;
+
+/// What a player got in one round.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct RoundPoints {
+    /// The score entered for the round, if there is one yet.
+    pub score: Option<i32>,
+    pub bonus: i32,
+    /// The extra points from doubling the round - the score and the bonus again.
+    pub doubled: i32,
+    /// The player's total once this round is counted.
+    pub running_total: i32,
+}
+
+impl RoundPoints {
+    /// Everything the round added to the total.
+    pub fn points(&self) -> i32 {
+        self.score.unwrap_or(0) + self.bonus + self.doubled
+    }
+}
+
+/// One row of the breakdown: a round, with the points of every player in
+/// the game's order.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RoundRow {
+    /// 0-based, like the keys of `Player::score`.
+    pub round: usize,
+    pub points: Vec<RoundPoints>,
+}
+
+/// How a player's `sum` adds up.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SumBreakdown {
+    /// The score of every round that has one, in order.
+    pub scores: Vec<i32>,
+    pub bonuses: i32,
+    pub doubled: i32,
+    pub sum: i32,
+}
+
+impl SumBreakdown {
+    pub fn of(player: &Player) -> Self {
+        Self {
+            scores: player.score.values().copied().collect(),
+            bonuses: player.bonus.values().sum(),
+            doubled: player.doubles.values().sum(),
+            sum: player.sum,
+        }
+    }
+
+    pub fn score_total(&self) -> i32 {
+        self.scores.iter().sum()
+    }
+}
+
+/// The rounds anyone has points in, first to last.
+pub fn round_rows(game: &Game) -> Vec<RoundRow> {
+    let rounds = game
+        .players
+        .iter()
+        .filter_map(last_round)
+        .max()
+        .map_or(0, |last| last + 1);
+    let mut running_totals = vec![0; game.players.len()];
+
+    (0..rounds)
+        .map(|round| RoundRow {
+            round,
+            points: game
+                .players
+                .iter()
+                .zip(running_totals.iter_mut())
+                .map(|(player, running_total)| {
+                    let mut points = round_points(player, round);
+                    *running_total += points.points();
+                    points.running_total = *running_total;
+                    points
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn round_points(player: &Player, round: usize) -> RoundPoints {
+    let key = round + 1;
+    RoundPoints {
+        score: player.score.get(&round).copied(),
+        bonus: player.bonus.get(&key).copied().unwrap_or(0),
+        doubled: player.doubles.get(&key).copied().unwrap_or(0)
+            + player.doubles.get(&(key + 1000)).copied().unwrap_or(0),
+        running_total: 0,
+    }
+}
+
+/// The last round a player has anything in.
+fn last_round(player: &Player) -> Option<usize> {
+    let scored = player.score.keys().next_back().copied();
+    let bonus = player.bonus.keys().next_back().map(|key| key - 1);
+    let doubled = player
+        .doubles
+        .keys()
+        .map(|key| if *key > 1000 { key - 1001 } else { key - 1 })
+        .max();
+
+    [scored, bonus, doubled].into_iter().flatten().max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_with(players: &[&str]) -> Game {
+        let mut game = Game::new();
+        for (index, name) in players.iter().enumerate() {
+            game.add_player(name.to_string(), index);
+        }
+        game.max_score = 10_000;
+        game.start_game();
+        game
+    }
+
+    #[test]
+    fn rows_add_up_to_each_players_sum() {
+        let mut game = game_with(&["Antonio", "Vlad"]);
+        game.add_score(1, 100);
+        game.add_score(2, 20);
+
+        game.grant_bonus(2, 0);
+        game.double_game_for_player(2);
+        game.add_score(1, 50);
+        game.add_score(2, 60);
+
+        let rows = round_rows(&game);
+        assert_eq!(rows.len(), 2);
+
+        let vlad = rows[1].points[1];
+        assert_eq!(vlad.score, Some(60));
+        assert_eq!(vlad.bonus, 50);
+        assert_eq!(vlad.doubled, 110);
+        assert_eq!(vlad.running_total, 240);
+
+        for (index, player) in game.players.iter().enumerate() {
+            assert_eq!(rows[1].points[index].running_total, player.sum);
+        }
+    }
+
+    #[test]
+    fn a_bonus_before_the_score_opens_the_round() {
+        let mut game = game_with(&["Antonio", "Vlad"]);
+        game.grant_bonus(1, 0);
+
+        let rows = round_rows(&game);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].points[0].score, None);
+        assert_eq!(rows[0].points[0].running_total, 50);
+        assert_eq!(rows[0].points[1], RoundPoints::default());
+    }
+
+    #[test]
+    fn the_sum_is_explained_by_the_three_maps() {
+        let mut game = game_with(&["Antonio", "Vlad"]);
+        game.double_game_total();
+        game.add_score(1, 100);
+        game.add_score(2, 20);
+        game.add_score(1, 35);
+
+        let antonio = SumBreakdown::of(&game.players[0]);
+        assert_eq!(antonio.scores, [100, 35]);
+        assert_eq!(antonio.score_total(), 135);
+        assert_eq!(antonio.bonuses, 0);
+        assert_eq!(antonio.doubled, 100);
+        assert_eq!(
+            antonio.score_total() + antonio.bonuses + antonio.doubled,
+            antonio.sum
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Wall-clock helpers for time-limited games.
+
+use time::OffsetDateTime;
+
+/// The current time as a unix timestamp, in seconds.
+pub fn now() -> i64 {
+    OffsetDateTime::now_utc().unix_timestamp()
+}
+
+/// A unix timestamp as day.month.year.
+pub fn format_date(timestamp: i64) -> String {
+    match OffsetDateTime::from_unix_timestamp(timestamp) {
+        Ok(date) => format!(
+            "{:02}.{:02}.{}",
+            date.day(),
+            u8::from(date.month()),
+            date.year()
+        ),
+        Err(_) => String::new(),
+    }
+}
+
+/// How far ahead of UTC the browser's clock is, in seconds.
+///
+/// Outside the browser there is no reliable way to tell, so UTC is assumed.
+pub fn local_offset() -> i64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        -(js_sys::Date::new_0().get_timezone_offset() as i64) * 60
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        0
+    }
+}
+
+/// The next time the local clock shows `minutes` past midnight, as a unix timestamp.
+pub fn next_time_of_day(minutes: u16, now: i64, offset: i64) -> i64 {
+    const DAY: i64 = 24 * 60 * 60;
+
+    let local_now = now + offset;
+    let midnight = local_now - local_now.rem_euclid(DAY);
+    let mut target = midnight + minutes as i64 * 60;
+    if target <= local_now {
+        target += DAY;
+    }
+    target - offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadline_rolls_over_to_the_next_day() {
+        // 1 January 2024, 20:00 UTC.
+        let evening = 1_704_139_200;
+
+        assert_eq!(next_time_of_day(23 * 60, evening, 0), evening + 3 * 3600);
+        assert_eq!(next_time_of_day(18 * 60, evening, 0), evening + 22 * 3600);
+        // 23:00 in UTC+2 is 21:00 UTC.
+        assert_eq!(next_time_of_day(23 * 60, evening, 2 * 3600), evening + 3600);
+    }
+}
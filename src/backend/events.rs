@@ -0,0 +1,148 @@
+//! The actions that change a game's scores.
+//!
+//! Every action is recorded in order, and the scores, bonuses and doubles
+//! of each player are rebuilt from that log, which is what makes undo/redo possible.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The most rounds a game can have - the doubles of a round's bonus are kept
+/// 1000 keys after the round's own.
+pub const MAX_ROUNDS: usize = 1000;
+
+/// The most a single score or bonus can be worth, either way, so totals can't overflow.
+pub const MAX_POINTS: i32 = 100_000;
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub enum GameEvent {
+    /// A player's score for a round (0-based) - later entries for the same round replace it.
+    AddScore {
+        player_id: usize,
+        round: usize,
+        value: i32,
+    },
+    EditScore {
+        player_id: usize,
+        score_id: usize,
+        value: i32,
+    },
+    /// A bonus for a round (0-based) - a round has one bonus, so later grants for it are ignored.
+    GrantBonus {
+        player_id: usize,
+        round: usize,
+        /// Which of the variant's bonuses was granted.
+        #[serde(default)]
+        kind: usize,
+    },
+    /// Doubles a round (0-based) for one player - a round is doubled once, so later doubles are ignored.
+    DoubleGameForPlayer { player_id: usize, round: usize },
+    /// Doubles a round (0-based) for everyone.
+    DoubleGameTotal { round: usize },
+}
+
+impl GameEvent {
+    /// The player this event applies to, if it only applies to one.
+    pub fn player_id(&self) -> Option<usize> {
+        match self {
+            GameEvent::AddScore { player_id, .. }
+            | GameEvent::EditScore { player_id, .. }
+            | GameEvent::GrantBonus { player_id, .. }
+            | GameEvent::DoubleGameForPlayer { player_id, .. } => Some(*player_id),
+            GameEvent::DoubleGameTotal { .. } => None,
+        }
+    }
+
+    /// The round (0-based) this event applies to.
+    pub fn round(&self) -> usize {
+        match *self {
+            GameEvent::AddScore { round, .. }
+            | GameEvent::GrantBonus { round, .. }
+            | GameEvent::DoubleGameForPlayer { round, .. }
+            | GameEvent::DoubleGameTotal { round } => round,
+            GameEvent::EditScore { score_id, .. } => score_id.saturating_sub(1),
+        }
+    }
+
+    /// Point the event to another player, used when players get renumbered.
+    pub fn with_player_id(self, new_id: usize) -> Self {
+        match self {
+            GameEvent::AddScore { round, value, .. } => GameEvent::AddScore {
+                player_id: new_id,
+                round,
+                value,
+            },
+            GameEvent::EditScore {
+                score_id, value, ..
+            } => GameEvent::EditScore {
+                player_id: new_id,
+                score_id,
+                value,
+            },
+            GameEvent::GrantBonus { round, kind, .. } => GameEvent::GrantBonus {
+                player_id: new_id,
+                round,
+                kind,
+            },
+            GameEvent::DoubleGameForPlayer { round, .. } => GameEvent::DoubleGameForPlayer {
+                player_id: new_id,
+                round,
+            },
+            GameEvent::DoubleGameTotal { round } => GameEvent::DoubleGameTotal { round },
+        }
+    }
+}
+
+/// Works out the round of bonuses and doubles recorded before they said which
+/// round they were for. They went to the round in progress, which only moved on
+/// once every player had the same number of scores.
+pub(crate) struct LegacyRounds {
+    players: usize,
+    scored: BTreeMap<usize, BTreeSet<usize>>,
+    current: usize,
+}
+
+impl LegacyRounds {
+    pub fn new(players: usize) -> Self {
+        Self {
+            players,
+            scored: BTreeMap::new(),
+            current: 0,
+        }
+    }
+
+    /// The round in progress.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Follow a player getting a score for a round (0-based).
+    pub fn scored(&mut self, player_id: usize, round: usize) {
+        self.scored.entry(player_id).or_default().insert(round);
+
+        let counts: Vec<usize> = (1..=self.players)
+            .map(|id| self.scored.get(&id).map_or(0, BTreeSet::len))
+            .collect();
+        if let Some(first) = counts.first() {
+            if counts.iter().all(|count| count == first) {
+                self.current = *first;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_rounds_move_on_once_everyone_scored() {
+        let mut rounds = LegacyRounds::new(2);
+        rounds.scored(1, 0);
+        assert_eq!(rounds.current(), 0);
+        rounds.scored(2, 0);
+        assert_eq!(rounds.current(), 1);
+        rounds.scored(2, 1);
+        rounds.scored(1, 0);
+        assert_eq!(rounds.current(), 1);
+    }
+}
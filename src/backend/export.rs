@@ -0,0 +1,353 @@
+//! Getting games out of the app and back in.
+//!
+//! Games are exported as a versioned JSON document that can be imported again,
+//! or as a flat CSV with one row per player per round for spreadsheets.
+
+use crate::backend::events::{MAX_POINTS, MAX_ROUNDS};
+use crate::backend::history::ArchivedGame;
+use crate::backend::prelude::*;
+use crate::backend::schema::{self, SCHEMA_VERSION};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Tells our exports apart from any other JSON file.
+pub const EXPORT_APP: &str = "rummy-nights";
+/// Bumped whenever the document layout changes in a way older versions can't read.
+pub const EXPORT_VERSION: u32 = 1;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExportDocument {
+    pub app: String,
+    pub version: u32,
+    /// When the export was made, as a Unix timestamp in seconds.
+    pub exported_at: i64,
+    /// The storage schema the games are in - exports older than this field count as 0.
+    #[serde(default)]
+    pub schema: u32,
+    #[serde(default)]
+    pub current_game: Option<Game>,
+    #[serde(default)]
+    pub history: Vec<ArchivedGame>,
+}
+
+impl ExportDocument {
+    pub fn new(current_game: Option<Game>, history: Vec<ArchivedGame>) -> Self {
+        Self {
+            app: String::from(EXPORT_APP),
+            version: EXPORT_VERSION,
+            exported_at: crate::backend::clock::now(),
+            schema: SCHEMA_VERSION,
+            current_game,
+            history,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Parse and validate an exported document, migrating games saved by older versions.
+    pub fn from_json(text: &str) -> Result<Self, ImportError> {
+        let mut value = parse_versioned(text, EXPORT_APP, EXPORT_VERSION)?;
+
+        let schema = value
+            .get("schema")
+            .and_then(|schema| schema.as_u64())
+            .unwrap_or_default() as u32;
+        if schema > SCHEMA_VERSION {
+            return Err(ImportError::UnsupportedVersion(schema));
+        }
+        if let Some(game) = value.get_mut("current_game").filter(|game| !game.is_null()) {
+            schema::migrate("game", schema, game);
+        }
+        if let Some(history) = value.get_mut("history") {
+            schema::migrate("history", schema, history);
+        }
+
+        let document: Self =
+            serde_json::from_value(value).map_err(|err| ImportError::Malformed(err.to_string()))?;
+
+        if let Some(game) = &document.current_game {
+            validate_game(game)?;
+        }
+        for entry in &document.history {
+            validate_game(&entry.game)?;
+        }
+
+        Ok(document)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ImportError {
+    /// Not valid JSON, or missing required fields.
+    Malformed(String),
+    NotAnExport,
+    /// Made by a newer version of the app.
+    UnsupportedVersion(u32),
+    InvalidGame(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Malformed(reason) => write!(f, "the file could not be read ({reason})"),
+            ImportError::NotAnExport => write!(f, "the file is not a Rummy Nights export"),
+            ImportError::UnsupportedVersion(version) => {
+                write!(f, "export version {version} is not supported")
+            }
+            ImportError::InvalidGame(reason) => write!(f, "a game is invalid ({reason})"),
+        }
+    }
+}
+
+/// Parse a JSON file made by the app, checking it is the expected kind and not too new.
+pub(crate) fn parse_versioned(
+    text: &str,
+    app: &str,
+    max_version: u32,
+) -> Result<serde_json::Value, ImportError> {
+    let value: serde_json::Value =
+        serde_json::from_str(text).map_err(|err| ImportError::Malformed(err.to_string()))?;
+
+    if value.get("app").and_then(|app| app.as_str()) != Some(app) {
+        return Err(ImportError::NotAnExport);
+    }
+
+    let version = value
+        .get("version")
+        .and_then(|version| version.as_u64())
+        .unwrap_or_default() as u32;
+    if version == 0 || version > max_version {
+        return Err(ImportError::UnsupportedVersion(version));
+    }
+
+    Ok(value)
+}
+
+/// Check the things the rest of the app relies on, so a bad file can't crash it later.
+pub(crate) fn validate_game(game: &Game) -> Result<(), ImportError> {
+    let invalid = |reason: &str| Err(ImportError::InvalidGame(String::from(reason)));
+
+    if game.players.len() > PLAYER_COLORS {
+        return invalid("too many players");
+    }
+    if game.status != GameStatus::NotStarted && game.players.len() < MIN_PLAYERS {
+        return invalid("not enough players");
+    }
+
+    for (index, player) in game.players.iter().enumerate() {
+        if player.id != index + 1 && game.status != GameStatus::NotStarted {
+            return invalid("player ids are out of order");
+        }
+        if player.color_index >= PLAYER_COLORS {
+            return invalid("unknown player color");
+        }
+    }
+
+    let known_player = |id: usize| game.players.iter().any(|player| player.id == id);
+    if game
+        .events
+        .iter()
+        .chain(&game.undone_events)
+        .filter_map(|entry| entry.event.player_id())
+        .any(|id| !known_player(id))
+    {
+        return invalid("an action refers to a missing player");
+    }
+
+    if game.tile_bonus_value.abs() > MAX_POINTS {
+        return invalid("the tile bonus is too large");
+    }
+    let bonuses = game.rules().bonuses.len();
+    let in_range = |event: &GameEvent| match *event {
+        GameEvent::AddScore { round, value, .. } => round < MAX_ROUNDS && value.abs() <= MAX_POINTS,
+        GameEvent::EditScore {
+            score_id, value, ..
+        } => (1..=MAX_ROUNDS).contains(&score_id) && value.abs() <= MAX_POINTS,
+        GameEvent::GrantBonus { round, kind, .. } => round < MAX_ROUNDS && kind < bonuses,
+        GameEvent::DoubleGameForPlayer { round, .. } | GameEvent::DoubleGameTotal { round } => {
+            round < MAX_ROUNDS
+        }
+    };
+    if !game
+        .events
+        .iter()
+        .chain(&game.undone_events)
+        .all(|entry| in_range(&entry.event))
+    {
+        return invalid("an action is out of range");
+    }
+
+    Ok(())
+}
+
+/// One row per player per round, for the given games and their labels.
+pub fn games_to_csv<'a>(games: impl IntoIterator<Item = (String, &'a Game)>) -> String {
+    let mut csv = String::from(
+        "game,round,player,score,bonus,bonus_points,doubled,double_points,round_points,total\n",
+    );
+
+    for (label, game) in games {
+        for player in &game.players {
+            let mut total = 0;
+
+            for round in player.score.keys() {
+                let bonus = player.bonus.get(&(round + 1)).copied();
+                let doubled = player.list_of_doubled_games.contains_key(&(round + 1));
+                let double_points = player.doubles.get(&(round + 1)).copied().unwrap_or(0)
+                    + player.doubles.get(&(round + 1001)).copied().unwrap_or(0);
+                let points = player.round_points(*round);
+                total += points;
+
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{},{},{},{}\n",
+                    csv_field(&label),
+                    round + 1,
+                    csv_field(&player.name),
+                    player.score[round],
+                    bonus.is_some(),
+                    bonus.unwrap_or(0),
+                    doubled,
+                    double_points,
+                    points,
+                    total,
+                ));
+            }
+        }
+    }
+
+    csv
+}
+
+/// Quote a field if it would otherwise break the row.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ledger::Entry;
+
+    fn played_game() -> Game {
+        let mut game = Game::new();
+        game.add_player(String::from("Antonio"), 0);
+        game.add_player(String::from("Vlad, Jr."), 1);
+        game.start_game();
+        game.grant_bonus(1, 0);
+        game.double_game_for_player(2);
+        game.add_score(1, 100);
+        game.add_score(2, 40);
+        game.add_score(1, 10);
+        game.add_score(2, 5);
+        game
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let game = played_game();
+        let history = vec![ArchivedGame::new(3, game.clone())];
+        let json = ExportDocument::new(Some(game.clone()), history).to_json();
+
+        let document = ExportDocument::from_json(&json).unwrap();
+        assert!(document.current_game.unwrap() == game);
+        assert_eq!(document.history[0].id, 3);
+    }
+
+    #[test]
+    fn rejects_foreign_and_newer_files() {
+        assert_eq!(
+            ExportDocument::from_json("{\"app\": \"other\"}").err(),
+            Some(ImportError::NotAnExport)
+        );
+
+        let mut document = serde_json::to_value(ExportDocument::new(None, Vec::new())).unwrap();
+        document["version"] = (EXPORT_VERSION + 1).into();
+        assert_eq!(
+            ExportDocument::from_json(&document.to_string()).err(),
+            Some(ImportError::UnsupportedVersion(EXPORT_VERSION + 1))
+        );
+
+        assert!(matches!(
+            ExportDocument::from_json("not json"),
+            Err(ImportError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_actions_for_missing_players() {
+        let mut game = played_game();
+        game.events.push(Entry::new(
+            99,
+            GameEvent::AddScore {
+                player_id: 9,
+                round: 0,
+                value: 5,
+            },
+        ));
+        let json = ExportDocument::new(Some(game), Vec::new()).to_json();
+
+        assert!(matches!(
+            ExportDocument::from_json(&json),
+            Err(ImportError::InvalidGame(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_actions_out_of_range() {
+        let events = [
+            GameEvent::EditScore {
+                player_id: 1,
+                score_id: 0,
+                value: 5,
+            },
+            GameEvent::AddScore {
+                player_id: 1,
+                round: usize::MAX,
+                value: 5,
+            },
+            GameEvent::AddScore {
+                player_id: 1,
+                round: 2,
+                value: i32::MAX,
+            },
+            GameEvent::GrantBonus {
+                player_id: 1,
+                round: 0,
+                kind: 7,
+            },
+            GameEvent::DoubleGameTotal { round: MAX_ROUNDS },
+        ];
+
+        for event in events {
+            let mut game = played_game();
+            game.undone_events.push(Entry::new(99, event));
+            let json = ExportDocument::new(Some(game), Vec::new()).to_json();
+
+            assert!(
+                matches!(
+                    ExportDocument::from_json(&json),
+                    Err(ImportError::InvalidGame(_))
+                ),
+                "{event:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn csv_has_a_row_per_player_per_round() {
+        let game = played_game();
+        let csv = games_to_csv([(String::from("1"), &game)]);
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[1], "1,1,Antonio,100,true,50,false,0,150,150");
+        assert_eq!(rows[3], "1,1,\"Vlad, Jr.\",40,false,0,true,40,80,80");
+        assert_eq!(rows[4], "1,2,\"Vlad, Jr.\",5,false,0,false,0,5,85");
+    }
+}
@@ -1,8 +1,12 @@
+use crate::backend::events::GameEvent;
+use crate::backend::ledger::{Entry, Stamp};
 use crate::backend::prelude::*;
-use gloo_console::log;
-use gloo_storage::{LocalStorage, SessionStorage, Storage};
+use crate::backend::rules::{RuleSet, ScoringMode, TieRule, Variant};
+use crate::backend::settings::default_max_players;
+use crate::backend::uid::new_uid;
+use crate::log;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Clone, Serialize, Deserialize, PartialEq)]
 pub struct Game {
@@ -19,8 +23,51 @@ pub struct Game {
     pub is_sorted: bool,
     pub max_score: i32,
     pub tile_bonus_value: i32,
-    pub winner_name: String,
+    /// Names of the winners - more than one when the win is shared.
+    #[serde(default)]
+    pub winners: Vec<String>,
+    /// The winners' [`Player::identity`], which tells apart players with the same name
+    /// and follows a player who is later renamed.
+    #[serde(default)]
+    pub winner_identities: Vec<String>,
     pub warn_incorrect_score: bool,
+    #[serde(default = "default_end_game_at_score")]
+    pub end_game_at_score: bool,
+    #[serde(default = "default_max_players")]
+    pub max_players: usize,
+    #[serde(default)]
+    pub variant: Variant,
+    #[serde(default)]
+    pub scoring: ScoringMode,
+    #[serde(default)]
+    pub tie_rule: TieRule,
+    /// The game ends after this many rounds.
+    #[serde(default)]
+    pub round_limit: Option<usize>,
+    /// The game ends with the first round finished after this unix timestamp.
+    #[serde(default)]
+    pub deadline: Option<i64>,
+    /// Which of the variant's bonuses the bonus button currently grants.
+    #[serde(default)]
+    pub active_bonus: usize,
+    /// Every action taken this game, ordered by stamp - the player scores are built from these.
+    #[serde(default)]
+    pub events: Vec<Entry>,
+    /// Undone actions, most recent last, waiting to be redone.
+    #[serde(default)]
+    pub undone_events: Vec<Entry>,
+    /// Stamps of every undone action, so merging with a device that still has them
+    /// doesn't bring them back.
+    #[serde(default)]
+    pub retracted: BTreeSet<Stamp>,
+    /// Tells games apart when they are synced, so a new game isn't mixed into the last one.
+    /// Empty for games saved before it existed.
+    #[serde(default)]
+    pub uid: String,
+}
+
+fn default_end_game_at_score() -> bool {
+    true
 }
 
 impl Game {
@@ -40,15 +87,28 @@ impl Game {
             is_sorted: false,
             max_score: 1000,
             tile_bonus_value: 50,
-            winner_name: String::new(),
+            winners: Vec::new(),
+            winner_identities: Vec::new(),
             warn_incorrect_score: false,
+            end_game_at_score: true,
+            max_players: default_max_players(),
+            variant: Variant::default(),
+            scoring: ScoringMode::default(),
+            tie_rule: TieRule::default(),
+            round_limit: None,
+            deadline: None,
+            active_bonus: 0,
+            events: Vec::new(),
+            undone_events: Vec::new(),
+            retracted: BTreeSet::new(),
+            uid: new_uid(),
         }
     }
 
     pub fn add_player(&mut self, name: String, color_index: usize) {
         log!("Adding player.");
 
-        if self.players.len() < 4 && !name.is_empty() {
+        if self.players.len() < self.max_players && !name.is_empty() {
             let id = self.players.len() + 1;
 
             self.players.push(Player {
@@ -61,10 +121,62 @@ impl Game {
                 doubles: BTreeMap::new(),
                 color_index,
                 winner: false,
+                uid: None,
             });
         };
     }
 
+    pub fn rules(&self) -> RuleSet {
+        self.variant.rules()
+    }
+
+    /// Switch to another variant, taking its target score and scoring mode.
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+        self.max_score = variant.rules().target_score;
+        self.scoring = variant.rules().scoring;
+        self.active_bonus = 0;
+    }
+
+    /// The points of one of the variant's bonuses.
+    pub fn bonus_value(&self, kind: usize) -> i32 {
+        self.rules()
+            .bonuses
+            .get(kind)
+            .and_then(|bonus| bonus.value)
+            .unwrap_or(self.tile_bonus_value)
+    }
+
+    /// The first color nobody in the game has yet.
+    pub fn next_free_color(&self) -> usize {
+        (0..PLAYER_COLORS)
+            .find(|color| {
+                !self
+                    .players
+                    .iter()
+                    .any(|player| player.color_index == *color)
+            })
+            .unwrap_or(self.players.len() % PLAYER_COLORS)
+    }
+
+    /// The id of the player dealing this round - the deal moves to the next seat every round.
+    pub fn dealer_id(&self) -> usize {
+        if self.players.is_empty() {
+            return 0;
+        }
+
+        (self.round + self.total_rounds) % self.players.len() + 1
+    }
+
+    /// The player whose score input comes after this one's, wrapping around the table.
+    pub fn next_player_id(&self, player_id: usize) -> usize {
+        if player_id >= self.players.len() {
+            1
+        } else {
+            player_id + 1
+        }
+    }
+
     pub fn edit_player_name(&mut self, id: usize, name: String) {
         log!("Adding player.");
 
@@ -112,11 +224,30 @@ impl Game {
         log!("Removing player.");
 
         self.players.retain(|player| player.id != id);
+        self.events
+            .retain(|entry| entry.event.player_id() != Some(id));
+        self.undone_events
+            .retain(|entry| entry.event.player_id() != Some(id));
+        self.renumber_players();
+    }
+
+    /// Give players ids matching their position, keeping the event log pointed at the right players.
+    fn renumber_players(&mut self) {
+        let new_ids: BTreeMap<usize, usize> = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(index, player)| (player.id, index + 1))
+            .collect();
 
-        let mut counter = 1;
         for player in &mut self.players {
-            player.id = counter;
-            counter += 1;
+            player.id = new_ids[&player.id];
+        }
+
+        for entry in self.events.iter_mut().chain(self.undone_events.iter_mut()) {
+            if let Some(new_id) = entry.event.player_id().and_then(|id| new_ids.get(&id)) {
+                entry.event = entry.event.with_player_id(*new_id);
+            }
         }
     }
 
@@ -135,20 +266,243 @@ impl Game {
         if *max_games == *min_games && self.round != *max_games {
             self.round = *max_games;
             self.new_round_started = true;
-            self.tile_bonus_granted = false;
-            self.double_game_granted = false;
+            // Another device may have granted the new round's bonus or double already.
+            self.tile_bonus_granted = self.has_bonus(self.round);
+            self.double_game_granted = self.is_doubled(self.round);
         } else {
             self.new_round_started = false;
         }
+    }
+
+    /// Record an action and apply it to the players.
+    ///
+    /// Anything previously undone can't be redone after this.
+    pub fn record(&mut self, event: GameEvent) {
+        self.stamp(event);
+        self.undone_events.clear();
+        self.apply(event);
+    }
+
+    /// Add an action to the log with a stamp later than any seen so far.
+    fn stamp(&mut self, event: GameEvent) {
+        let clock = self
+            .events
+            .iter()
+            .chain(&self.undone_events)
+            .map(|entry| &entry.stamp)
+            .chain(&self.retracted)
+            .map(|stamp| stamp.clock)
+            .max()
+            .unwrap_or(0);
+
+        self.events.push(Entry::new(clock + 1, event));
+    }
+
+    /// Combine the actions recorded on another device with this game's.
+    ///
+    /// Both devices end up with the same game after merging each other's, whatever the order.
+    /// Actions undone on either device stay undone, and when both devices scored the same
+    /// round for a player, the score with the later stamp counts.
+    pub fn merge(&mut self, other: &Game) {
+        log!("Merging game from another device.");
+
+        self.retracted.extend(other.retracted.iter().cloned());
+
+        let mut entries: BTreeMap<Stamp, GameEvent> = BTreeMap::new();
+        for entry in self.events.iter().chain(&other.events) {
+            if !self.retracted.contains(&entry.stamp) {
+                entries.insert(entry.stamp.clone(), entry.event);
+            }
+        }
+        self.events = entries
+            .into_iter()
+            .map(|(stamp, event)| Entry { stamp, event })
+            .collect();
+
+        self.rebuild();
+    }
 
-        self.save_game()
+    fn apply(&mut self, event: GameEvent) {
+        match event {
+            GameEvent::AddScore {
+                player_id,
+                round,
+                value,
+            } => self.apply_add_score(player_id, round, value),
+            GameEvent::EditScore {
+                player_id,
+                score_id,
+                value,
+            } => self.apply_edit_score(player_id, score_id, value),
+            GameEvent::GrantBonus {
+                player_id,
+                round,
+                kind,
+            } => self.apply_grant_bonus(player_id, round, kind),
+            GameEvent::DoubleGameForPlayer { player_id, round } => {
+                self.double_game(&[player_id], round)
+            }
+            GameEvent::DoubleGameTotal { round } => {
+                let ids: Vec<usize> = self.players.iter().map(|player| player.id).collect();
+                self.double_game(&ids, round);
+            }
+        }
     }
 
-    pub fn add_score(&mut self, player_id: usize, value: i32, round: usize) {
+    /// Clear every score, bonus and double, then replay the event log from the start.
+    pub fn rebuild(&mut self) {
+        log!("Rebuilding game from the event log.");
+
+        for player in &mut self.players {
+            player.score.clear();
+            player.bonus.clear();
+            player.list_of_doubled_games.clear();
+            player.doubles.clear();
+            player.sum = 0;
+            player.winner = false;
+        }
+
+        self.round = 0;
+        self.new_round_started = true;
+        self.tile_bonus_button_active = false;
+        self.tile_bonus_granted = false;
+        self.double_game_button_active = false;
+        self.double_game_granted = false;
+        self.sorted_players.clear();
+        self.is_sorted = false;
+        self.winners.clear();
+        self.winner_identities.clear();
+        self.warn_incorrect_score = false;
+        if self.status == GameStatus::Finished {
+            self.status = GameStatus::Ongoing;
+        }
+
+        for entry in self.events.clone() {
+            self.apply(entry.event);
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.events.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone_events.is_empty()
+    }
+
+    /// Take back the last action. Returns false if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.events.pop() {
+            Some(entry) => {
+                log!("Undoing last action.");
+                self.retracted.insert(entry.stamp.clone());
+                self.undone_events.push(entry);
+                self.rebuild();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Apply the last undone action again, as a new action. Returns false if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.undone_events.pop() {
+            Some(entry) => {
+                log!("Redoing action.");
+                self.stamp(entry.event);
+                self.rebuild();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Games saved before the event log existed only have the player maps,
+    /// so work out the events that would have produced them.
+    pub fn restore_legacy_events(&mut self) {
+        let has_data = self.players.iter().any(|player| {
+            !player.score.is_empty()
+                || !player.bonus.is_empty()
+                || !player.list_of_doubled_games.is_empty()
+        });
+
+        if !self.events.is_empty() || !has_data {
+            return;
+        }
+        log!("Restoring event log of an older game.");
+
+        let rounds = self
+            .players
+            .iter()
+            .map(|player| player.score.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut events = Vec::new();
+        for round in 0..=rounds {
+            for player in &self.players {
+                if player.bonus.contains_key(&(round + 1)) {
+                    events.push(GameEvent::GrantBonus {
+                        player_id: player.id,
+                        round,
+                        kind: 0,
+                    });
+                }
+            }
+
+            let doubled: Vec<usize> = self
+                .players
+                .iter()
+                .filter(|player| player.list_of_doubled_games.contains_key(&(round + 1)))
+                .map(|player| player.id)
+                .collect();
+
+            if doubled.len() > 1 && doubled.len() == self.players.len() {
+                events.push(GameEvent::DoubleGameTotal { round });
+            } else if let Some(player_id) = doubled.first() {
+                events.push(GameEvent::DoubleGameForPlayer {
+                    player_id: *player_id,
+                    round,
+                });
+            }
+
+            for player in &self.players {
+                if let Some(value) = player.score.get(&round) {
+                    events.push(GameEvent::AddScore {
+                        player_id: player.id,
+                        round,
+                        value: *value,
+                    });
+                }
+            }
+        }
+
+        for event in events {
+            self.stamp(event);
+        }
+    }
+
+    /// Add a player's score for the next round they haven't played yet.
+    pub fn add_score(&mut self, player_id: usize, value: i32) {
+        let round = self
+            .players
+            .iter()
+            .find(|player| player.id == player_id)
+            .map(|player| player.score.len())
+            .unwrap_or(self.round);
+
+        self.record(GameEvent::AddScore {
+            player_id,
+            round,
+            value,
+        });
+    }
+
+    fn apply_add_score(&mut self, player_id: usize, round: usize, value: i32) {
         log!("Adding score.");
         for player in &mut self.players {
             if player_id == player.id {
-                player.score.insert(player.score.len(), value);
+                player.score.insert(round, value);
                 if player.list_of_doubled_games.contains_key(&(round + 1)) {
                     player.doubles.insert(round + 1, value);
                 }
@@ -158,7 +512,133 @@ impl Game {
             }
         }
         self.check_round();
-        self.save_game();
+        self.check_status();
+    }
+
+    /// Scores that don't fit the variant, e.g. not a multiple of 5 in Romanian rummy, are most likely typos.
+    pub fn is_score_valid(&self, score: i32) -> bool {
+        self.rules().is_valid_score(score)
+    }
+
+    /// Replace the score of an already-played round (1-based `score_id`).
+    pub fn edit_score(&mut self, player_id: usize, score_id: usize, score: i32) {
+        self.record(GameEvent::EditScore {
+            player_id,
+            score_id,
+            value: score,
+        });
+    }
+
+    fn apply_edit_score(&mut self, player_id: usize, score_id: usize, score: i32) {
+        log!("Editing score.");
+
+        for player in &mut self.players {
+            if player_id == player.id {
+                log!(format!(
+                    "Player {} has score: {:?}, doubles: {:?}",
+                    player.name, player.score, player.doubles
+                ));
+                player.score.insert(score_id - 1, score);
+
+                if player.list_of_doubled_games.contains_key(&score_id) {
+                    player.doubles.insert(score_id, score);
+                }
+            }
+        }
+        self.check_round();
+        self.check_status();
+    }
+
+    /// Give the tile bonus of the current round to a player.
+    pub fn grant_bonus(&mut self, player_id: usize, kind: usize) {
+        if !self.tile_bonus_granted {
+            self.record(GameEvent::GrantBonus {
+                player_id,
+                round: self.round,
+                kind,
+            });
+        }
+    }
+
+    /// Whether anyone got the bonus of a round (0-based).
+    fn has_bonus(&self, round: usize) -> bool {
+        self.players
+            .iter()
+            .any(|player| player.bonus.contains_key(&(round + 1)))
+    }
+
+    /// Whether a round (0-based) was doubled for anyone.
+    fn is_doubled(&self, round: usize) -> bool {
+        self.players
+            .iter()
+            .any(|player| player.list_of_doubled_games.contains_key(&(round + 1)))
+    }
+
+    fn apply_grant_bonus(&mut self, player_id: usize, round: usize, kind: usize) {
+        // Two devices may both have granted the bonus - the first one counts.
+        if self.has_bonus(round) {
+            log!("The round's bonus was already granted.");
+            return;
+        }
+        log!("Granting player bonus.");
+        let value = self.bonus_value(kind);
+
+        for player in &mut self.players {
+            if player.id == player_id {
+                player.bonus.insert(round + 1, value);
+
+                if player.list_of_doubled_games.contains_key(&(round + 1)) {
+                    player.doubles.insert(round + 1001, value);
+                }
+            }
+        }
+        self.tile_bonus_granted = self.has_bonus(self.round);
+        self.new_round_started = false;
+        self.tile_bonus_button_active = false;
+        self.check_status();
+    }
+
+    /// Double the current round for every player.
+    pub fn double_game_total(&mut self) {
+        if !self.double_game_granted {
+            self.record(GameEvent::DoubleGameTotal { round: self.round });
+        }
+    }
+
+    /// Double the current round for a single player.
+    pub fn double_game_for_player(&mut self, player_id: usize) {
+        if !self.double_game_granted {
+            self.record(GameEvent::DoubleGameForPlayer {
+                player_id,
+                round: self.round,
+            });
+        }
+    }
+
+    fn double_game(&mut self, player_ids: &[usize], round: usize) {
+        // Two devices may both have doubled the round - the first one counts.
+        if self.is_doubled(round) {
+            log!("The round was already doubled.");
+            return;
+        }
+        log!("Doubling scores this round.");
+
+        for player in &mut self.players {
+            if player_ids.contains(&player.id) {
+                player.list_of_doubled_games.insert(round + 1, true);
+
+                if let Some(score) = player.score.get(&round) {
+                    player.doubles.insert(round + 1, *score);
+                }
+                if let Some(bonus) = player.bonus.get(&(round + 1)) {
+                    player.doubles.insert(round + 1001, *bonus);
+                }
+            }
+        }
+        self.double_game_granted = self.is_doubled(self.round);
+        self.double_game_button_active = false;
+        self.new_round_started = false;
+        self.check_status();
     }
 
     pub fn sort_players(&mut self) {
@@ -167,18 +647,27 @@ impl Game {
         self.sorted_players = self.players.clone();
         log!("Getting players worked.");
 
-        self.sorted_players.sort_by(|a, b| {
-            let temp_sum_a = a.sum;
-            let temp_sum_b = b.sum;
-
-            temp_sum_a.cmp(&temp_sum_b)
-        });
+        let scoring = self.scoring;
+        self.sorted_players
+            .sort_by(|a, b| scoring.rank(a.sum, b.sum));
         log!("Sorting players worked.");
 
-        self.sorted_players.reverse();
-        log!("Reversing players worked.");
-
-        self.sorted_players[0].winner = true;
+        // Before anyone has won, everyone tied for the lead is shown as winning.
+        let best = self.sorted_players[0].sum;
+        let winning: Vec<bool> = self
+            .sorted_players
+            .iter()
+            .map(|player| {
+                if self.winners.is_empty() {
+                    player.sum == best
+                } else {
+                    self.is_winner(player)
+                }
+            })
+            .collect();
+        for (player, winner) in self.sorted_players.iter_mut().zip(winning) {
+            player.winner = winner;
+        }
         self.is_sorted = true;
         log!("Finishing players worked.");
     }
@@ -220,67 +709,110 @@ impl Game {
             })
             .collect();
 
-        let max = *(total_scores.iter().max().unwrap());
+        let round_limit_reached = self.round_limit.is_some_and(|limit| self.round >= limit);
 
-        if max >= self.max_score && self.new_round_started {
-            let no_of_winners = self
-                .players
-                .iter()
-                .filter(|player| {
-                    player.score.values().sum::<i32>()
-                        + player.bonus.values().sum::<i32>()
-                        + player.doubles.values().sum::<i32>()
-                        >= max
-                })
-                .count();
-
-            if no_of_winners == 1 {
-                let winner: Vec<&Player> = self
-                    .players
+        if self.new_round_started
+            && (round_limit_reached
+                || self.end_game_at_score
+                    && self.scoring.reached_target(&total_scores, self.max_score))
+        {
+            self.decide_winners();
+        }
+
+        log!("Done checking status.")
+    }
+
+    /// End the game if the time is up and the round just finished.
+    pub fn check_deadline(&mut self, now: i64) {
+        if self.status == GameStatus::Ongoing
+            && self.round > 0
+            && self.new_round_started
+            && self.deadline.is_some_and(|deadline| now >= deadline)
+        {
+            self.decide_winners();
+        }
+    }
+
+    /// Finish the game with whoever leads, as long as the tie rule allows it.
+    fn decide_winners(&mut self) {
+        let totals: Vec<i32> = self.players.iter().map(|player| player.sum).collect();
+        let best = self.scoring.best_total(&totals).unwrap_or_default();
+        let leaders: Vec<&Player> = self
+            .players
+            .iter()
+            .filter(|player| player.sum == best)
+            .collect();
+
+        let winners: Vec<&Player> = match (leaders.len(), self.tie_rule) {
+            (1, _) | (_, TieRule::ShareWin) => leaders,
+            (_, TieRule::PlayAnotherRound) => Vec::new(),
+            (_, TieRule::FewestRoundsOverLimit) => {
+                let rounds_over: Vec<usize> = leaders
                     .iter()
-                    .filter(|player| player.sum >= self.max_score)
+                    .map(|player| self.rounds_over_limit(player))
+                    .collect();
+                let fewest = rounds_over.iter().min().copied().unwrap_or_default();
+
+                let winners: Vec<&Player> = leaders
+                    .into_iter()
+                    .zip(rounds_over)
+                    .filter(|(_, rounds)| *rounds == fewest)
+                    .map(|(player, _)| player)
                     .collect();
-                self.winner_name = winner[0].name.clone();
 
-                self.status = GameStatus::Finished;
-                self.save_game();
+                if winners.len() == 1 {
+                    winners
+                } else {
+                    Vec::new()
+                }
             }
+        };
+
+        if !winners.is_empty() {
+            self.winners = winners.iter().map(|player| player.name.clone()).collect();
+            self.winner_identities = winners.iter().map(|player| player.identity()).collect();
+            self.status = GameStatus::Finished;
         }
+    }
 
-        log!("Done checking status.")
+    pub fn get_winners(&self) -> &[String] {
+        &self.winners
     }
 
-    pub fn get_winner(&self) -> String {
-        self.winner_name.clone()
+    /// Whether the player won, matched by identity - or by name, for games finished
+    /// before identities were recorded.
+    pub fn is_winner(&self, player: &Player) -> bool {
+        if self.winner_identities.is_empty() {
+            self.winners.contains(&player.name)
+        } else {
+            self.winner_identities.contains(&player.identity())
+        }
+    }
+
+    /// How many rounds ended with the player's running total past the max score.
+    fn rounds_over_limit(&self, player: &Player) -> usize {
+        let mut total = 0;
+
+        (0..self.round)
+            .filter(|round| {
+                total += player.round_points(*round);
+                self.scoring.reached_target(&[total], self.max_score)
+            })
+            .count()
     }
 
     pub fn start_game(&mut self) -> bool {
         log!("Starting new game.");
 
         if self.players.len() >= 2 {
-            let mut counter = 1;
-            for player in &mut self.players {
-                player.id = counter;
-                counter += 1;
-            }
-
-            LocalStorage::delete("state");
-            SessionStorage::delete("session");
-
+            self.renumber_players();
             self.status = GameStatus::Ongoing;
-            self.save_game();
 
             true
         } else {
             false
         }
     }
-    pub fn save_game(&self) {
-        log!("Saving game.");
-
-        LocalStorage::set("game", self.clone()).unwrap();
-        SessionStorage::set("session", true).unwrap();
-    }
 }
 
 impl Default for Game {
@@ -288,3 +820,491 @@ impl Default for Game {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_player_game() -> Game {
+        let mut game = Game::new();
+        game.add_player(String::from("Antonio"), 0);
+        game.add_player(String::from("Vlad"), 1);
+        assert!(game.start_game());
+        game
+    }
+
+    fn play_round(game: &mut Game, scores: &[i32]) {
+        for (index, score) in scores.iter().enumerate() {
+            game.add_score(index + 1, *score);
+        }
+        game.check_status();
+    }
+
+    #[test]
+    fn needs_two_players_to_start() {
+        let mut game = Game::new();
+        game.add_player(String::from("Antonio"), 0);
+        assert!(!game.start_game());
+        assert_eq!(game.status, GameStatus::NotStarted);
+    }
+
+    #[test]
+    fn sums_scores_and_advances_rounds() {
+        let mut game = two_player_game();
+
+        game.add_score(1, 100);
+        assert_eq!(game.round, 0);
+        assert!(!game.new_round_started);
+
+        game.add_score(2, 50);
+        assert_eq!(game.round, 1);
+        assert!(game.new_round_started);
+
+        play_round(&mut game, &[25, 75]);
+        assert_eq!(game.round, 2);
+        assert_eq!(game.players[0].sum, 125);
+        assert_eq!(game.players[1].sum, 125);
+    }
+
+    #[test]
+    fn edited_score_replaces_the_old_one() {
+        let mut game = two_player_game();
+        play_round(&mut game, &[100, 50]);
+
+        game.edit_score(1, 1, 200);
+        game.check_status();
+
+        assert_eq!(game.players[0].score.get(&0), Some(&200));
+        assert_eq!(game.players[0].sum, 200);
+    }
+
+    #[test]
+    fn only_multiples_of_five_are_valid() {
+        let game = Game::new();
+        assert!(game.is_score_valid(0));
+        assert!(game.is_score_valid(-15));
+        assert!(game.is_score_valid(120));
+        assert!(!game.is_score_valid(121));
+    }
+
+    #[test]
+    fn rummikub_ends_at_the_penalty_limit_with_the_lowest_winning() {
+        let mut game = two_player_game();
+        game.set_variant(Variant::Rummikub);
+        assert!(game.is_score_valid(13));
+
+        play_round(&mut game, &[60, 20]);
+        assert_eq!(game.status, GameStatus::Ongoing);
+
+        play_round(&mut game, &[45, 30]);
+        play_round(&mut game, &[0, 0]);
+        assert_eq!(game.status, GameStatus::Finished);
+        assert_eq!(game.winners, ["Vlad"]);
+
+        game.sort_players();
+        assert_eq!(game.sorted_players[0].name, "Vlad");
+        assert!(game.sorted_players[0].winner);
+    }
+
+    #[test]
+    fn negative_penalties_end_the_game_below_zero() {
+        let mut game = two_player_game();
+        game.scoring = ScoringMode::NegativePenalty;
+        game.max_score = 100;
+
+        play_round(&mut game, &[-60, -20]);
+        play_round(&mut game, &[-50, -15]);
+        assert_eq!(game.status, GameStatus::Finished);
+        assert_eq!(game.winners, ["Vlad"]);
+
+        game.sort_players();
+        assert_eq!(game.sorted_players[0].name, "Vlad");
+        assert!(!game.sorted_players[1].winner);
+    }
+
+    #[test]
+    fn gin_bonuses_have_their_own_values() {
+        let mut game = two_player_game();
+        game.set_variant(Variant::GinRummy);
+        game.tile_bonus_value = 50;
+
+        game.grant_bonus(2, 1);
+        play_round(&mut game, &[0, 12]);
+
+        assert_eq!(game.players[1].sum, 37);
+        assert_eq!(
+            game.events[0].event,
+            GameEvent::GrantBonus {
+                player_id: 2,
+                round: 0,
+                kind: 1
+            }
+        );
+    }
+
+    #[test]
+    fn tile_bonus_is_granted_once_per_round() {
+        let mut game = two_player_game();
+
+        game.grant_bonus(1, 0);
+        game.grant_bonus(2, 0);
+        assert_eq!(game.players[0].sum, 50);
+        assert_eq!(game.players[1].sum, 0);
+
+        play_round(&mut game, &[10, 20]);
+        assert!(!game.tile_bonus_granted);
+
+        game.grant_bonus(2, 0);
+        assert_eq!(game.players[1].sum, 70);
+    }
+
+    #[test]
+    fn doubled_player_gets_round_score_twice() {
+        let mut game = two_player_game();
+
+        game.double_game_for_player(2);
+        play_round(&mut game, &[100, 40]);
+
+        assert_eq!(game.players[0].sum, 100);
+        assert_eq!(game.players[1].sum, 80);
+    }
+
+    #[test]
+    fn doubling_everyone_also_doubles_the_bonus() {
+        let mut game = two_player_game();
+
+        game.grant_bonus(1, 0);
+        game.double_game_total();
+        play_round(&mut game, &[100, 40]);
+
+        assert_eq!(game.players[0].sum, 300);
+        assert_eq!(game.players[1].sum, 80);
+        assert!(!game.double_game_granted);
+    }
+
+    #[test]
+    fn game_ends_when_a_single_player_reaches_max_score() {
+        let mut game = two_player_game();
+        game.max_score = 200;
+
+        play_round(&mut game, &[150, 100]);
+        assert_eq!(game.status, GameStatus::Ongoing);
+
+        play_round(&mut game, &[100, 50]);
+        assert_eq!(game.status, GameStatus::Finished);
+        assert_eq!(game.get_winners(), ["Antonio"]);
+
+        game.sort_players();
+        assert!(game.sorted_players[0].winner);
+        assert_eq!(game.sorted_players[0].name, "Antonio");
+    }
+
+    #[test]
+    fn game_does_not_end_mid_round_or_on_a_tie() {
+        let mut game = two_player_game();
+        game.max_score = 200;
+
+        game.add_score(1, 250);
+        game.check_status();
+        assert_eq!(game.status, GameStatus::Ongoing);
+
+        game.add_score(2, 250);
+        game.check_status();
+        assert_eq!(game.status, GameStatus::Ongoing);
+    }
+
+    #[test]
+    fn tied_leaders_can_share_the_win() {
+        let mut game = two_player_game();
+        game.add_player(String::from("Ioana"), 2);
+        game.max_score = 200;
+        game.tie_rule = TieRule::ShareWin;
+
+        play_round(&mut game, &[250, 250, 100]);
+        assert_eq!(game.status, GameStatus::Finished);
+        assert_eq!(game.get_winners(), ["Antonio", "Vlad"]);
+
+        game.sort_players();
+        let winners: Vec<bool> = game.sorted_players.iter().map(|p| p.winner).collect();
+        assert_eq!(winners, [true, true, false]);
+    }
+
+    #[test]
+    fn tie_goes_to_the_fewest_rounds_over_the_limit() {
+        let mut game = two_player_game();
+        game.add_player(String::from("Ioana"), 2);
+        game.max_score = 200;
+        game.tie_rule = TieRule::FewestRoundsOverLimit;
+
+        play_round(&mut game, &[210, 210, 0]);
+        assert_eq!(game.status, GameStatus::Ongoing);
+
+        play_round(&mut game, &[10, 0, 220]);
+        assert_eq!(game.status, GameStatus::Finished);
+        assert_eq!(game.get_winners(), ["Ioana"]);
+    }
+
+    #[test]
+    fn round_limited_game_ends_after_the_last_round() {
+        let mut game = two_player_game();
+        game.end_game_at_score = false;
+        game.round_limit = Some(2);
+
+        play_round(&mut game, &[100, 50]);
+        assert_eq!(game.status, GameStatus::Ongoing);
+
+        play_round(&mut game, &[0, 20]);
+        assert_eq!(game.status, GameStatus::Finished);
+        assert_eq!(game.get_winners(), ["Antonio"]);
+    }
+
+    #[test]
+    fn time_limited_game_ends_once_the_round_is_over() {
+        let mut game = two_player_game();
+        game.deadline = Some(1000);
+
+        game.add_score(1, 50);
+        game.check_deadline(2000);
+        assert_eq!(game.status, GameStatus::Ongoing);
+
+        game.add_score(2, 20);
+        game.check_deadline(999);
+        assert_eq!(game.status, GameStatus::Ongoing);
+
+        game.check_deadline(1000);
+        assert_eq!(game.status, GameStatus::Finished);
+        assert_eq!(game.get_winners(), ["Antonio"]);
+    }
+
+    #[test]
+    fn undo_and_redo_rebuild_the_scores() {
+        let mut game = two_player_game();
+        play_round(&mut game, &[100, 50]);
+        game.grant_bonus(2, 0);
+        game.add_score(1, 20);
+
+        assert!(game.undo());
+        assert_eq!(game.players[0].sum, 100);
+        assert_eq!(game.players[1].sum, 100);
+
+        assert!(game.undo());
+        assert_eq!(game.players[1].sum, 50);
+        assert!(!game.tile_bonus_granted);
+        assert_eq!(game.round, 1);
+
+        assert!(game.redo());
+        assert!(game.redo());
+        assert!(!game.redo());
+        assert_eq!(game.players[0].sum, 120);
+        assert_eq!(game.players[1].sum, 100);
+    }
+
+    #[test]
+    fn new_action_clears_redo() {
+        let mut game = two_player_game();
+        game.add_score(1, 100);
+        game.undo();
+        assert!(game.can_redo());
+
+        game.add_score(1, 40);
+        assert!(!game.can_redo());
+        assert_eq!(game.players[0].sum, 40);
+    }
+
+    #[test]
+    fn undoing_the_winning_round_resumes_the_game() {
+        let mut game = two_player_game();
+        game.max_score = 100;
+        play_round(&mut game, &[100, 50]);
+        assert_eq!(game.status, GameStatus::Finished);
+
+        game.undo();
+        assert_eq!(game.status, GameStatus::Ongoing);
+        assert!(game.winners.is_empty());
+    }
+
+    #[test]
+    fn removing_a_player_keeps_the_log_consistent() {
+        let mut game = Game::new();
+        game.add_player(String::from("Antonio"), 0);
+        game.add_player(String::from("Vlad"), 1);
+        game.add_player(String::from("Ioana"), 2);
+        game.start_game();
+        play_round(&mut game, &[10, 20, 30]);
+
+        game.remove_player(2);
+        game.rebuild();
+
+        assert_eq!(game.players[1].name, "Ioana");
+        assert_eq!(game.players[1].sum, 30);
+    }
+
+    #[test]
+    fn legacy_games_get_an_equivalent_log() {
+        let mut game = two_player_game();
+        game.grant_bonus(1, 0);
+        game.double_game_for_player(2);
+        play_round(&mut game, &[100, 50]);
+        play_round(&mut game, &[10, 20]);
+
+        let expected = game.clone();
+        game.events.clear();
+        game.restore_legacy_events();
+        game.rebuild();
+
+        assert!(game.players == expected.players);
+        assert_eq!(game.events, expected.events);
+    }
+
+    /// The same game on two devices, as if one had been shared with the other.
+    fn two_devices() -> (Game, Game) {
+        crate::backend::ledger::set_device_id("phone");
+        let mut phone = two_player_game();
+        play_round(&mut phone, &[100, 50]);
+        let tablet = phone.clone();
+        (phone, tablet)
+    }
+
+    #[test]
+    fn rounds_scored_offline_on_two_devices_merge() {
+        let (mut phone, mut tablet) = two_devices();
+
+        play_round(&mut phone, &[10, 20]);
+        crate::backend::ledger::set_device_id("tablet");
+        tablet.add_score(2, 20);
+        tablet.add_score(2, 35);
+
+        let mut merged_phone = phone.clone();
+        merged_phone.merge(&tablet);
+        tablet.merge(&phone);
+
+        assert_eq!(merged_phone.events, tablet.events);
+        assert!(merged_phone.players == tablet.players);
+        assert_eq!(tablet.players[0].sum, 110);
+        assert_eq!(tablet.players[1].sum, 105);
+        assert_eq!(tablet.players[1].score.len(), 3);
+    }
+
+    #[test]
+    fn the_later_score_for_a_round_wins() {
+        let (mut phone, mut tablet) = two_devices();
+
+        phone.add_score(1, 10);
+        crate::backend::ledger::set_device_id("tablet");
+        tablet.add_score(1, 15);
+
+        let mut merged_phone = phone.clone();
+        merged_phone.merge(&tablet);
+        tablet.merge(&phone);
+
+        // Same clock, so the author breaks the tie on both devices.
+        assert_eq!(merged_phone.players[0].sum, 115);
+        assert_eq!(tablet.players[0].sum, 115);
+    }
+
+    #[test]
+    fn bonuses_keep_their_round_when_merged_after_other_scores() {
+        let (mut phone, mut tablet) = two_devices();
+
+        // The phone scores the next round while the tablet, still in it,
+        // grants its bonus - the tablet's bonus sorts after the phone's scores.
+        play_round(&mut phone, &[10, 20]);
+        crate::backend::ledger::set_device_id("tablet");
+        tablet.add_score(1, 15);
+        tablet.grant_bonus(2, 0);
+
+        let mut merged_phone = phone.clone();
+        merged_phone.merge(&tablet);
+        tablet.merge(&phone);
+
+        assert!(merged_phone.players == tablet.players);
+        assert_eq!(tablet.players[1].bonus.keys().collect::<Vec<_>>(), [&2]);
+        assert_eq!(tablet.round, 2);
+        assert!(!tablet.tile_bonus_granted);
+    }
+
+    #[test]
+    fn a_round_gets_one_bonus_and_one_double_across_devices() {
+        let (mut phone, mut tablet) = two_devices();
+
+        phone.grant_bonus(1, 0);
+        phone.double_game_for_player(1);
+        crate::backend::ledger::set_device_id("tablet");
+        tablet.grant_bonus(2, 0);
+        tablet.double_game_total();
+
+        let mut merged_phone = phone.clone();
+        merged_phone.merge(&tablet);
+        tablet.merge(&phone);
+        play_round(&mut tablet, &[10, 20]);
+
+        let bonuses: usize = tablet.players.iter().map(|player| player.bonus.len()).sum();
+        assert_eq!(bonuses, 1);
+        assert!(merged_phone.players[0].bonus.contains_key(&2));
+        assert!(tablet.players[0].list_of_doubled_games.contains_key(&2));
+        assert!(!tablet.players[1].list_of_doubled_games.contains_key(&2));
+        assert_eq!(tablet.players[0].sum, 100 + 2 * (10 + 50));
+        assert_eq!(tablet.players[1].sum, 50 + 20);
+    }
+
+    #[test]
+    fn undone_actions_stay_undone_after_merging() {
+        let (mut phone, mut tablet) = two_devices();
+
+        phone.undo();
+        tablet.merge(&phone);
+        phone.merge(&tablet);
+
+        assert_eq!(phone.players[1].sum, 0);
+        assert_eq!(tablet.players[1].sum, 0);
+        assert_eq!(phone.events, tablet.events);
+    }
+
+    #[test]
+    fn player_limit_comes_from_the_game() {
+        let mut game = Game::new();
+        game.max_players = 6;
+        for index in 0..8 {
+            game.add_player(format!("Player {index}"), index % PLAYER_COLORS);
+        }
+        assert_eq!(game.players.len(), 6);
+    }
+
+    #[test]
+    fn dealer_rotates_through_every_seat() {
+        let mut game = Game::new();
+        game.max_players = 6;
+        for index in 0..6 {
+            game.add_player(format!("Player {index}"), index);
+        }
+        game.start_game();
+
+        let mut dealers = Vec::new();
+        for _ in 0..7 {
+            dealers.push(game.dealer_id());
+            play_round(&mut game, &[5, 10, 15, 20, 25, 30]);
+        }
+        assert_eq!(dealers, vec![1, 2, 3, 4, 5, 6, 1]);
+    }
+
+    #[test]
+    fn focus_wraps_to_the_first_player() {
+        let mut game = Game::new();
+        game.max_players = 5;
+        for index in 0..5 {
+            game.add_player(format!("Player {index}"), index);
+        }
+
+        assert_eq!(game.next_player_id(4), 5);
+        assert_eq!(game.next_player_id(5), 1);
+    }
+
+    #[test]
+    fn new_players_get_an_unused_color() {
+        let mut game = Game::new();
+        game.add_player(String::from("Antonio"), 0);
+        game.add_player(String::from("Vlad"), 2);
+
+        assert_eq!(game.next_free_color(), 1);
+    }
+}
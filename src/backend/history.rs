@@ -0,0 +1,34 @@
+//! The archive of finished games.
+
+use crate::backend::clock::format_date;
+use crate::backend::prelude::*;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+#[derive(Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchivedGame {
+    pub id: usize,
+    /// When the game ended, as a Unix timestamp in seconds.
+    pub finished_at: i64,
+    /// The full game, including every player's per-round scores, bonuses and doubles.
+    pub game: Game,
+}
+
+impl ArchivedGame {
+    pub fn new(id: usize, game: Game) -> Self {
+        Self {
+            id,
+            finished_at: OffsetDateTime::now_utc().unix_timestamp(),
+            game,
+        }
+    }
+
+    /// The date the game ended, formatted as day.month.year.
+    pub fn date(&self) -> String {
+        format_date(self.finished_at)
+    }
+
+    pub fn winners(&self) -> &[String] {
+        self.game.get_winners()
+    }
+}
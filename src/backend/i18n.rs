@@ -0,0 +1,451 @@
+//! The app's text in every language it speaks.
+//!
+//! Each [`Language`] has a [Fluent](https://projectfluent.org/) resource in
+//! `locales/`, built into the app. Messages take named arguments, so a
+//! translation can put them in whatever order its grammar needs and pick the
+//! right plural form. A message a language doesn't have yet is shown in English.
+
+use crate::log;
+use fluent::{FluentArgs, FluentBundle, FluentResource};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use unic_langid::LanguageIdentifier;
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug, Default)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "ro")]
+    Romanian,
+    #[serde(rename = "hu")]
+    Hungarian,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "he")]
+    Hebrew,
+}
+
+impl Language {
+    pub const ALL: [Language; 7] = [
+        Language::English,
+        Language::Romanian,
+        Language::Hungarian,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Hebrew,
+    ];
+
+    /// The language's ISO 639-1 code, also the name of its resource file.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Romanian => "ro",
+            Language::Hungarian => "hu",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Hebrew => "he",
+        }
+    }
+
+    /// The language's name, in the language itself.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Romanian => "Română",
+            Language::Hungarian => "Magyar",
+            Language::German => "Deutsch",
+            Language::French => "Français",
+            Language::Spanish => "Español",
+            Language::Hebrew => "עברית",
+        }
+    }
+
+    pub fn direction(&self) -> TextDirection {
+        match self {
+            Language::English
+            | Language::Romanian
+            | Language::Hungarian
+            | Language::German
+            | Language::French
+            | Language::Spanish => TextDirection::LeftToRight,
+            Language::Hebrew => TextDirection::RightToLeft,
+        }
+    }
+
+    /// The Fluent source of the language's messages.
+    pub fn resource(&self) -> &'static str {
+        match self {
+            Language::English => include_str!("../../locales/en.ftl"),
+            Language::Romanian => include_str!("../../locales/ro.ftl"),
+            Language::Hungarian => include_str!("../../locales/hu.ftl"),
+            Language::German => include_str!("../../locales/de.ftl"),
+            Language::French => include_str!("../../locales/fr.ftl"),
+            Language::Spanish => include_str!("../../locales/es.ftl"),
+            Language::Hebrew => include_str!("../../locales/he.ftl"),
+        }
+    }
+
+    /// The language of a code such as `ro`, `ro-RO` or `pt_BR`, if the app has it.
+    pub fn from_code(code: &str) -> Option<Language> {
+        let primary = code.split(['-', '_']).next()?.to_lowercase();
+        Language::ALL
+            .into_iter()
+            .find(|language| language.code() == primary)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// The value of HTML's `dir` attribute.
+    pub fn attribute(&self) -> &'static str {
+        match self {
+            TextDirection::LeftToRight => "ltr",
+            TextDirection::RightToLeft => "rtl",
+        }
+    }
+}
+
+/// The first of the languages someone prefers that the app has, or English.
+pub fn preferred_language(codes: &[String]) -> Language {
+    codes
+        .iter()
+        .find_map(|code| Language::from_code(code))
+        .unwrap_or_default()
+}
+
+/// The languages the browser or the operating system asks for, most preferred first.
+pub fn system_languages() -> Vec<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let navigator = js_sys::Reflect::get(&js_sys::global(), &"navigator".into());
+        let languages =
+            navigator.and_then(|navigator| js_sys::Reflect::get(&navigator, &"languages".into()));
+        match languages {
+            Ok(languages) if js_sys::Array::is_array(&languages) => js_sys::Array::from(&languages)
+                .iter()
+                .filter_map(|code| code.as_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // `LANGUAGE` is a list like `ro:en`, the others a locale like `ro_RO.UTF-8`.
+        ["LANGUAGE", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .flat_map(|value| {
+                value
+                    .split(':')
+                    .map(|code| code.split('.').next().unwrap_or_default().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|code| !code.is_empty() && code != "C" && code != "POSIX")
+            .collect()
+    }
+}
+
+type Bundle = FluentBundle<FluentResource>;
+
+thread_local! {
+    static BUNDLES: RefCell<HashMap<Language, Rc<Bundle>>> = RefCell::new(HashMap::new());
+}
+
+fn build_bundle(language: Language) -> Bundle {
+    let id: LanguageIdentifier = language.code().parse().unwrap_or_default();
+    let mut bundle = FluentBundle::new(vec![id]);
+    // Names and numbers only need direction marks around them to keep their
+    // order inside text written right to left.
+    bundle.set_use_isolating(language.direction() == TextDirection::RightToLeft);
+
+    let resource = FluentResource::try_new(language.resource().to_string()).unwrap_or_else(
+        |(resource, errors)| {
+            log!(format!(
+                "Errors in the {} translation: {errors:?}",
+                language.code()
+            ));
+            resource
+        },
+    );
+    if let Err(errors) = bundle.add_resource(resource) {
+        log!(format!(
+            "Errors in the {} translation: {errors:?}",
+            language.code()
+        ));
+    }
+    bundle
+}
+
+fn bundle(language: Language) -> Rc<Bundle> {
+    BUNDLES.with(|bundles| {
+        bundles
+            .borrow_mut()
+            .entry(language)
+            .or_insert_with(|| Rc::new(build_bundle(language)))
+            .clone()
+    })
+}
+
+fn format(language: Language, key: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let bundle = bundle(language);
+    let pattern = bundle.get_message(key)?.value()?;
+
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+    if !errors.is_empty() {
+        log!(format!("Errors in {key} ({}): {errors:?}", language.code()));
+    }
+    Some(text.into_owned())
+}
+
+/// Whether `language`'s own resource has the message `key`.
+pub fn has_message(language: Language, key: &str) -> bool {
+    bundle(language).has_message(key)
+}
+
+/// The text of `key` in `language`, falling back to English, then to the key
+/// itself so a missing translation never stops the app.
+pub fn translate(language: Language, key: &str, args: Option<&FluentArgs>) -> String {
+    format(language, key, args)
+        .or_else(|| format(Language::English, key, args))
+        .unwrap_or_else(|| {
+            log!(format!("Missing translation: {key}"));
+            key.to_string()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent::fluent_args;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn languages_are_found_by_code() {
+        assert_eq!(Language::from_code("ro-RO"), Some(Language::Romanian));
+        assert_eq!(Language::from_code("de_AT"), Some(Language::German));
+        assert_eq!(Language::from_code("FR"), Some(Language::French));
+        assert_eq!(Language::from_code("pt-BR"), None);
+    }
+
+    #[test]
+    fn the_first_preferred_language_the_app_has_is_picked() {
+        let codes = |codes: &[&str]| {
+            codes
+                .iter()
+                .map(|code| code.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            preferred_language(&codes(&["pt-BR", "de-DE", "en-US"])),
+            Language::German
+        );
+        assert_eq!(preferred_language(&codes(&["ja"])), Language::English);
+        assert_eq!(preferred_language(&[]), Language::English);
+    }
+
+    #[test]
+    fn arguments_choose_the_plural_form() {
+        let one = fluent_args!["names" => "Vlad", "count" => 1];
+        let two = fluent_args!["names" => "Antonio & Vlad", "count" => 2];
+
+        assert_eq!(
+            translate(Language::English, "winners", Some(&one)),
+            "Vlad won!"
+        );
+        assert_eq!(
+            translate(Language::English, "winners", Some(&two)),
+            "Antonio & Vlad share the win!"
+        );
+        assert_eq!(
+            translate(
+                Language::Romanian,
+                "import_done",
+                Some(&fluent_args!["count" => 3])
+            ),
+            "Am importat 3 jocuri."
+        );
+        assert_eq!(
+            translate(
+                Language::Romanian,
+                "import_done",
+                Some(&fluent_args!["count" => 20])
+            ),
+            "Am importat 20 de jocuri."
+        );
+    }
+
+    #[test]
+    fn unknown_messages_show_their_key() {
+        assert!(has_message(Language::German, "start_game"));
+        assert!(!has_message(Language::German, "no_such_key"));
+
+        assert_eq!(
+            translate(Language::German, "no_such_key", None),
+            "no_such_key"
+        );
+    }
+
+    /// The messages of a resource, by id, with the lines they're written on.
+    fn messages(source: &str) -> BTreeMap<&str, String> {
+        let mut messages = BTreeMap::new();
+        let mut current = None;
+
+        for line in source.lines() {
+            if line.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                if let Some((id, value)) = line.split_once('=') {
+                    current = Some(id.trim());
+                    messages.insert(id.trim(), value.to_string());
+                    continue;
+                }
+            }
+            if line.starts_with('#') || line.is_empty() {
+                current = None;
+            } else if let Some(body) = current.and_then(|id| messages.get_mut(id)) {
+                body.push_str(line);
+            }
+        }
+        messages
+    }
+
+    /// The `$variables` a message uses.
+    fn variables(message: &str) -> BTreeSet<&str> {
+        message
+            .split('$')
+            .skip(1)
+            .map(|rest| {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                    .unwrap_or(rest.len());
+                &rest[..end]
+            })
+            .collect()
+    }
+
+    /// The app's Rust sources by path, except this file, whose tests name keys of their own.
+    fn app_sources() -> Vec<(PathBuf, String)> {
+        fn collect(dir: &Path, sources: &mut Vec<(PathBuf, String)>) {
+            for entry in fs::read_dir(dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    collect(&path, sources);
+                } else if path.extension().is_some_and(|extension| extension == "rs")
+                    && !path.ends_with("backend/i18n.rs")
+                {
+                    let source = fs::read_to_string(&path).unwrap();
+                    let relative = path
+                        .strip_prefix(env!("CARGO_MANIFEST_DIR"))
+                        .unwrap_or(&path);
+                    sources.push((relative.to_path_buf(), source));
+                }
+            }
+        }
+
+        let mut sources = Vec::new();
+        collect(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut sources,
+        );
+        sources
+    }
+
+    /// The keys of `get_text(cx, "...")` and `get_text_with(cx, "...", ...)` calls.
+    fn literal_keys(source: &str) -> Vec<&str> {
+        let mut keys = Vec::new();
+        for call in ["get_text(", "get_text_with("] {
+            for (start, _) in source.match_indices(call) {
+                let Some((_, rest)) = source[start + call.len()..].split_once(',') else {
+                    continue;
+                };
+                if let Some((key, _)) = rest
+                    .trim_start()
+                    .strip_prefix('"')
+                    .and_then(|literal| literal.split_once('"'))
+                {
+                    keys.push(key);
+                }
+            }
+        }
+        keys
+    }
+
+    #[test]
+    fn every_language_has_the_messages_of_english() {
+        let reference = messages(Language::English.resource());
+        let mut problems = Vec::new();
+
+        for language in Language::ALL {
+            let file = format!("locales/{}.ftl", language.code());
+            if let Err((_, errors)) = FluentResource::try_new(language.resource().to_string()) {
+                problems.push(format!("{file} can't be read: {errors:?}"));
+            }
+
+            let translated = messages(language.resource());
+            for (id, message) in &reference {
+                match translated.get(id) {
+                    None => problems.push(format!("{file} is missing {id}")),
+                    Some(translation) if variables(translation) != variables(message) => problems
+                        .push(format!(
+                            "{file} uses other variables than English in {id}: {:?}",
+                            variables(translation)
+                        )),
+                    Some(_) => (),
+                }
+            }
+            for id in translated.keys().filter(|id| !reference.contains_key(*id)) {
+                problems.push(format!("{file} has {id}, which English doesn't"));
+            }
+        }
+
+        assert!(problems.is_empty(), "{}", problems.join("\n"));
+    }
+
+    #[test]
+    fn every_text_the_app_asks_for_exists() {
+        let reference = messages(Language::English.resource());
+        let mut problems = Vec::new();
+
+        for (path, source) in app_sources() {
+            for key in literal_keys(&source) {
+                if !reference.contains_key(key) {
+                    problems.push(format!("{} asks for {key}", path.display()));
+                }
+            }
+        }
+
+        assert!(problems.is_empty(), "{}", problems.join("\n"));
+    }
+
+    #[test]
+    fn every_message_is_used() {
+        // Many keys reach `get_text` through labels, like `RuleSet::label`, so a
+        // key counts as used wherever it's written as a string.
+        let sources = app_sources();
+        let unused: Vec<&str> = messages(Language::English.resource())
+            .into_keys()
+            .filter(|id| {
+                let literal = format!("\"{id}\"");
+                !sources.iter().any(|(_, source)| source.contains(&literal))
+            })
+            .collect();
+
+        assert!(unused.is_empty(), "Unused messages: {unused:?}");
+    }
+}
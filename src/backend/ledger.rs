@@ -0,0 +1,85 @@
+//! Who recorded each action of a game, and in what order.
+//!
+//! Several devices can record actions for the same game at once, even
+//! offline. Every action gets a [`Stamp`]: a Lamport clock, one higher than
+//! anything the device has seen, and the device that recorded it. Ordering by
+//! stamp puts the actions of each device in the order they were made, and
+//! orders actions made at the same time the same way on every device - so any
+//! two devices that have seen the same actions replay them into the same game,
+//! whatever order they got them in.
+
+use crate::backend::events::GameEvent;
+use crate::backend::uid::new_uid;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+thread_local! {
+    static DEVICE_ID: RefCell<String> = RefCell::new(new_uid());
+}
+
+/// Identifies this device as the author of the actions it records.
+///
+/// A new id is made every time the app starts, which is enough to tell devices apart.
+pub fn device_id() -> String {
+    DEVICE_ID.with(|id| id.borrow().clone())
+}
+
+/// Act as another device from now on - used by tests to play several devices at once.
+pub fn set_device_id(id: &str) {
+    DEVICE_ID.with(|device| *device.borrow_mut() = id.to_string());
+}
+
+/// When and by whom an action was recorded. Unique for every action.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Stamp {
+    /// One higher than every stamp the device had seen when recording.
+    pub clock: u64,
+    /// The id of the device that recorded the action - breaks ties between devices.
+    #[serde(default)]
+    pub author: String,
+}
+
+/// An action of the game's log, along with its stamp.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub stamp: Stamp,
+    pub event: GameEvent,
+}
+
+impl Entry {
+    pub fn new(clock: u64, event: GameEvent) -> Self {
+        Self {
+            stamp: Stamp {
+                clock,
+                author: device_id(),
+            },
+            event,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamps_order_by_clock_then_author() {
+        let stamp = |clock, author: &str| Stamp {
+            clock,
+            author: author.to_string(),
+        };
+
+        let mut stamps = vec![stamp(2, "a"), stamp(1, "b"), stamp(1, "a")];
+        stamps.sort();
+        assert_eq!(stamps, [stamp(1, "a"), stamp(1, "b"), stamp(2, "a")]);
+    }
+
+    #[test]
+    fn entries_are_signed_by_this_device() {
+        set_device_id("phone");
+        let entry = Entry::new(3, GameEvent::DoubleGameTotal { round: 0 });
+
+        assert_eq!(entry.stamp.author, "phone");
+        assert_eq!(entry.stamp.clock, 3);
+    }
+}
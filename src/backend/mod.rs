@@ -1,23 +1,60 @@
+#[cfg(feature = "app")]
+use crate::log;
+#[cfg(feature = "app")]
 use dioxus::prelude::*;
-use gloo_console::log;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+pub mod backup;
+pub mod breakdown;
+pub mod clock;
+pub mod events;
+pub mod export;
 mod game;
+pub mod history;
+pub mod i18n;
+pub mod ledger;
 mod model;
+pub mod qr;
+pub mod roster;
+pub mod rules;
+pub mod schema;
 mod settings;
+pub mod share;
+pub mod stats;
+pub mod storage;
+pub mod sync;
+pub mod uid;
 
 pub mod prelude {
+    pub use crate::backend::events::GameEvent;
     pub use crate::backend::game::Game;
+    pub use crate::backend::history::ArchivedGame;
+    pub use crate::backend::i18n::Language;
     pub use crate::backend::model::Model;
+    #[cfg(feature = "app")]
     pub use crate::backend::model::STATE;
+    pub use crate::backend::roster::{Roster, RosterEntry};
+    pub use crate::backend::rules::{BonusRule, RuleSet, ScoringMode, TieRule, Variant};
     pub use crate::backend::settings::Settings;
+    pub use crate::backend::stats::PlayerStats;
+    #[cfg(feature = "web")]
+    pub use crate::backend::storage::BrowserStorage;
+    pub use crate::backend::storage::{MemoryStorage, Storage, StorageArea};
     pub use crate::backend::GameStatus;
     pub use crate::backend::GameTemplate;
     pub use crate::backend::Player;
     pub use crate::backend::Screen;
+    pub use crate::backend::{MIN_PLAYERS, PLAYER_COLORS};
 }
 
+/// How many player colors there are - also the most players a game can have,
+/// so everyone at the table gets their own color.
+pub const PLAYER_COLORS: usize = 7;
+
+/// The fewest players a game can have.
+pub const MIN_PLAYERS: usize = 2;
+
 #[derive(PartialEq, Eq, Clone, Serialize, Deserialize, Copy, Debug)]
 pub enum GameStatus {
     NotStarted,
@@ -25,7 +62,7 @@ pub enum GameStatus {
     Finished,
 }
 
-#[derive(PartialEq, Eq, Clone, Serialize, Deserialize, Copy)]
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize, Copy, Debug)]
 pub enum Screen {
     Menu,
     PlayerSelect,
@@ -34,12 +71,21 @@ pub enum Screen {
     EndGame,
     Settings,
     Credits,
+    History,
+    Stats,
+    Data,
+    Sync,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct GameTemplate {
     pub id: usize,
     pub name: String,
+    /// Roster ids of the players, in seating order.
+    #[serde(default)]
+    pub player_ids: Vec<String>,
+    /// Full copies of the players, as older versions saved them - moved to the roster on load.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub players: Vec<prelude::Player>,
     pub color: usize,
 }
@@ -55,12 +101,34 @@ pub struct Player {
     pub doubles: BTreeMap<usize, i32>,
     pub color_index: usize,
     pub winner: bool,
+    /// Identifies the same person across games - the id of their roster entry.
+    #[serde(default)]
+    pub uid: Option<String>,
+}
+
+impl Player {
+    /// Everything a player got in one round (0-based): the score, the tile bonus and any doubling.
+    pub fn round_points(&self, round: usize) -> i32 {
+        self.score.get(&round).copied().unwrap_or(0)
+            + self.bonus.get(&(round + 1)).copied().unwrap_or(0)
+            + self.doubles.get(&(round + 1)).copied().unwrap_or(0)
+            + self.doubles.get(&(round + 1001)).copied().unwrap_or(0)
+    }
+
+    /// The key used to recognize a player across games - the uid if there is one, otherwise the name.
+    pub fn identity(&self) -> String {
+        match &self.uid {
+            Some(uid) => uid.clone(),
+            None => self.name.trim().to_lowercase(),
+        }
+    }
 }
 
 /// Renders the version number (for releases) or the timestamp
 /// (for dev builds).
 ///
 /// It uses the `BUILD_VERSION` environment variable created in build.rs.
+#[cfg(feature = "app")]
 pub fn VersionNumber(cx: Scope) -> Element {
     log!("Calculating version number/timestamp.");
     let version = env!("BUILD_VERSION");
@@ -1,16 +1,29 @@
-use gloo_console::log;
-use gloo_storage::{LocalStorage, SessionStorage, Storage};
-use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::rc::Rc;
 
+use crate::backend::backup::{Backup, RestoreMode};
+use crate::backend::clock;
+use crate::backend::export::{games_to_csv, ExportDocument, ImportError};
+use crate::backend::history::ArchivedGame;
 use crate::backend::prelude::*;
+use crate::backend::schema::{self, StorageProblem};
+use crate::backend::share::{self, ShareError};
+use crate::backend::stats::compute_stats;
+#[cfg(feature = "app")]
+use crate::backend::storage::default_storage;
+use crate::backend::storage::{Storage, StorageArea};
+use crate::backend::sync::{self, SyncMessage, SyncRole, SyncSession};
 use crate::backend::GameTemplate;
+use crate::log;
+#[cfg(feature = "app")]
 use dioxus::prelude::*;
+#[cfg(feature = "app")]
 use fermi::AtomRef;
 
+#[cfg(feature = "app")]
 pub static STATE: fermi::AtomRef<Model> = AtomRef(|_| Model::new());
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone)]
 pub struct Model {
     pub game: Game,
     pub screen: Screen,
@@ -18,6 +31,26 @@ pub struct Model {
     pub checked_storage: bool,
     pub settings: Settings,
     pub templates: Vec<GameTemplate>,
+    pub roster: Roster,
+    pub history: Vec<ArchivedGame>,
+    /// True while an archived game is shown - nothing can be changed or saved.
+    pub read_only: bool,
+    /// The current game, put aside while an archived game is shown.
+    stashed_game: Option<Game>,
+    /// Where the back button of the data screen leads.
+    data_return_screen: Screen,
+    /// Saved data that could not be loaded, kept aside in storage.
+    pub storage_problems: Vec<StorageProblem>,
+    /// Why the shared game the app was opened with couldn't be loaded.
+    pub share_error: Option<ShareError>,
+    /// The live session this device is part of, if any.
+    pub sync: Option<SyncSession>,
+    /// Messages waiting to be sent to the other devices in the session.
+    sync_outbox: Vec<SyncMessage>,
+    /// How many messages were ever queued, so the app notices new ones even if
+    /// the outbox was never seen empty in between.
+    sync_queued: usize,
+    pub storage: Rc<dyn Storage>,
 }
 
 impl Model {
@@ -38,6 +71,7 @@ impl Model {
                 doubles: BTreeMap::new(),
                 color_index: 0,
                 winner: false,
+                uid: None,
             },
             Player {
                 id: 2,
@@ -49,12 +83,18 @@ impl Model {
                 doubles: BTreeMap::new(),
                 color_index: 1,
                 winner: false,
+                uid: None,
             },
         ];
         self.screen = Screen::Game;
     }
 
+    #[cfg(feature = "app")]
     pub fn new() -> Self {
+        Self::with_storage(default_storage())
+    }
+
+    pub fn with_storage(storage: Rc<dyn Storage>) -> Self {
         log!("Creating new state.");
         Self {
             game: Game::new(),
@@ -63,6 +103,17 @@ impl Model {
             checked_storage: false,
             settings: Settings::new(),
             templates: Vec::new(),
+            roster: Roster::new(),
+            history: Vec::new(),
+            read_only: false,
+            stashed_game: None,
+            data_return_screen: Screen::Menu,
+            storage_problems: Vec::new(),
+            share_error: None,
+            sync: None,
+            sync_outbox: Vec::new(),
+            sync_queued: 0,
+            storage,
         }
     }
 
@@ -70,42 +121,102 @@ impl Model {
         log!("Initializing storage.");
         if !self.checked_storage {
             self.load_existing_game();
-            self.settings.load();
+            if let Err(problem) = self.settings.load(self.storage.as_ref()) {
+                self.report(problem);
+            }
+            // The setting applies to the game in progress, including games saved before it was stored there.
+            self.game.end_game_at_score = self.settings.end_game_at_score;
+            self.load_roster();
             self.load_saved_templates();
+            self.load_history();
+
+            if let Some(link) = share::take_opened_link() {
+                if let Err(err) = self.open_shared_game(&link) {
+                    self.share_error = Some(err);
+                }
+            }
         } else {
             log!("Storage already checked this session - skipping.");
         }
     }
 
     pub fn get_dealer(&self) -> usize {
-        let mut position = 0;
-
-        for player in self.game.players.iter() {
-            if self.settings.enable_dealer_tracking
-                && (((self.game.round + self.game.players.len() + 1) - player.id
-                    + self.game.total_rounds)
-                    % self.game.players.len()
-                    == 0)
-                && self.game.status == GameStatus::Ongoing
-            {
-                position = player.id;
-            }
+        if self.settings.enable_dealer_tracking && self.game.status == GameStatus::Ongoing {
+            self.game.dealer_id()
+        } else {
+            0
         }
-
-        position
     }
 
-    pub fn add_player(&mut self, name: String, color_index: usize) {
-        self.game.add_player(name, color_index);
+    /// Add a player to the game, linking them to the roster.
+    ///
+    /// Known players get their roster name and, unless another one is picked, their usual color.
+    pub fn add_player(&mut self, name: String, color_index: Option<usize>) {
+        if name.trim().is_empty() {
+            return;
+        }
+
+        let known = self.roster.find_by_name(&name).cloned();
+        let name = known.as_ref().map_or(name, |entry| entry.name.clone());
+        let color_index = color_index
+            .or(known.as_ref().map(|entry| entry.color_index))
+            .unwrap_or_else(|| self.game.next_free_color());
+
+        let player_count = self.game.players.len();
+        self.game.add_player(name.clone(), color_index);
+
+        if self.game.players.len() > player_count {
+            let uid = self.roster.register(&name, color_index);
+            if let Some(player) = self.game.players.last_mut() {
+                player.uid = Some(uid);
+            }
+            self.save_roster();
+        }
     }
 
+    #[cfg(feature = "app")]
     pub fn edit_player_name(&mut self, evt: FormEvent, id: usize) {
         let name = evt.values.get("player-name").unwrap().join("");
         if !name.is_empty() {
-            self.game.edit_player_name(id - 1, name);
+            self.rename_player(id, name);
         };
     }
 
+    /// Rename a player in the game, along with their roster entry.
+    pub fn rename_player(&mut self, id: usize, name: String) {
+        self.game.edit_player_name(id - 1, name.clone());
+
+        if let Some(uid) = self.game.players[id - 1].uid.clone() {
+            self.roster.rename(&uid, &name);
+            self.save_roster();
+        }
+    }
+
+    pub fn save_roster(&self) {
+        schema::save(self.storage.as_ref(), "roster", &self.roster);
+    }
+
+    pub fn load_roster(&mut self) {
+        log!("Trying to load roster.");
+
+        match schema::load(self.storage.as_ref(), "roster") {
+            Ok(Some(roster)) => self.roster = roster,
+            Ok(None) => log!("Could not read roster."),
+            Err(problem) => self.report(problem),
+        }
+    }
+
+    fn report(&mut self, problem: StorageProblem) {
+        log!(format!("Could not load saved data - {problem}"));
+        if !self.storage_problems.contains(&problem) {
+            self.storage_problems.push(problem);
+        }
+    }
+
+    pub fn dismiss_storage_problems(&mut self) {
+        self.storage_problems.clear();
+    }
+
     pub fn go_to_screen(&mut self, screen: Screen) {
         self.screen = screen
     }
@@ -113,17 +224,19 @@ impl Model {
     pub fn clear_and_go_to_menu(&mut self) {
         self.go_to_screen(Screen::Menu);
         self.checked_storage = false;
-        SessionStorage::delete("session");
+        self.storage.delete(StorageArea::Session, "session");
     }
 
-    pub fn toggle_tile_bonus(&mut self) {
-        if self.game.tile_bonus_button_active {
+    /// Arm or disarm the button of one of the variant's bonuses.
+    pub fn toggle_tile_bonus(&mut self, kind: usize) {
+        if self.game.tile_bonus_button_active && self.game.active_bonus == kind {
             self.game.warn_incorrect_score = false;
             self.game.tile_bonus_button_active = false;
             self.game.double_game_button_active = false;
         } else if !self.game.tile_bonus_granted && self.game.status == GameStatus::Ongoing {
             self.game.warn_incorrect_score = false;
             self.game.tile_bonus_button_active = true;
+            self.game.active_bonus = kind;
             self.game.double_game_button_active = false;
         };
     }
@@ -141,72 +254,54 @@ impl Model {
     }
 
     pub fn double_game_total(&mut self) {
-        if !self.game.double_game_granted && self.settings.use_double_games {
-            for player in &mut self.game.players {
-                log!("Doubling all scores this round.");
-
-                player
-                    .list_of_doubled_games
-                    .insert(self.game.round + 1, true);
-
-                if player.bonus.contains_key(&(self.game.round + 1)) {
-                    player
-                        .doubles
-                        .insert(self.game.round + 1001, self.game.tile_bonus_value);
-                }
-            }
-            self.game.double_game_granted = true;
-            self.game.double_game_button_active = false;
-            self.game.new_round_started = false;
-            self.game.check_status();
-            self.game.save_game();
+        if self.read_only {
+            return;
+        }
+        if self.settings.use_double_games && self.game.rules().double_games {
+            self.game.double_game_total();
+            self.save_game();
         }
     }
 
     pub fn double_game_for_player(&mut self, player_id: usize) {
-        if !self.game.double_game_granted && self.settings.use_double_games {
-            for player in &mut self.game.players {
-                if player.id == player_id {
-                    log!(format!("Doubling {}'s score.", player.name));
-
-                    player
-                        .list_of_doubled_games
-                        .insert(self.game.round + 1, true);
-
-                    if player.bonus.contains_key(&(self.game.round + 1)) {
-                        player
-                            .doubles
-                            .insert(self.game.round + 1001, self.game.tile_bonus_value);
-                    }
-                }
-            }
-            self.game.double_game_granted = true;
-            self.game.double_game_button_active = false;
-            self.game.new_round_started = false;
-            self.game.check_status();
-            self.game.save_game();
+        if self.read_only {
+            return;
+        }
+        if self.settings.use_double_games && self.game.rules().double_games {
+            self.game.double_game_for_player(player_id);
+            self.save_game();
         }
     }
 
     pub fn finish_game(&mut self) {
         log!("Deleting game and returning to main menu.");
-        LocalStorage::delete("state");
-        SessionStorage::delete("session");
-        *self = Model::new();
+        self.archive_game();
+        self.storage.delete(StorageArea::Local, "state");
+        self.storage.delete(StorageArea::Session, "session");
+        *self = Model::with_storage(self.storage.clone());
     }
 
     pub fn create_game(&mut self) {
         log!("Creating new game.");
 
+        self.archive_game();
+
         let settings = self.settings;
         let templates = self.templates.clone();
+        let roster = self.roster.clone();
+        let history = self.history.clone();
         log!(format!("Backed up settings are {settings:?}"));
 
-        *self = Model::new();
+        *self = Model::with_storage(self.storage.clone());
         self.settings = settings;
         self.game.tile_bonus_value = self.settings.tile_bonus_value;
-        self.game.max_score = self.settings.max_score;
+        self.apply_variant(self.settings.variant);
+        self.game.end_game_at_score = self.settings.end_game_at_score;
+        self.game.tie_rule = self.settings.tie_rule;
+        self.game.max_players = self.settings.max_players;
         self.templates = templates;
+        self.roster = roster;
+        self.history = history;
 
         log!(format!("Actual settings are {:?}", self.settings));
         log!(format!(
@@ -221,49 +316,59 @@ impl Model {
 
     pub fn load_existing_game(&mut self) {
         log!("Trying to load game from storage.");
-        match LocalStorage::get::<serde_json::Value>("game") {
-            Ok(json_state) => match serde_json::from_value::<Game>(json_state) {
-                Ok(new_state) => {
-                    self.game = new_state;
-                    self.screen = Screen::Menu;
-
-                    log!("Loaded game.");
-                    match SessionStorage::get::<serde_json::Value>("session") {
-                        Ok(json_state) => match serde_json::from_value::<bool>(json_state) {
-                            Ok(_) => {
-                                self.screen = Screen::Game;
-                                log!("Loaded session.");
-                            }
-                            Err(_) => log!("Could not parse session storage."),
-                        },
-                        Err(_) => log!("Could not read session storage."),
-                    }
+        match schema::load::<Game>(self.storage.as_ref(), "game") {
+            Ok(Some(new_state)) => {
+                self.game = new_state;
+                self.game.restore_legacy_events();
+                self.screen = Screen::Menu;
+
+                log!("Loaded game.");
+                match self.storage.get(StorageArea::Session, "session") {
+                    Some(json_state) => match serde_json::from_value::<bool>(json_state) {
+                        Ok(_) => {
+                            self.screen = Screen::Game;
+                            log!("Loaded session.");
+                        }
+                        Err(_) => log!("Could not parse session storage."),
+                    },
+                    None => log!("Could not read session storage."),
                 }
-                Err(_) => log!("Could not parse local storage."),
-            },
-            Err(_) => log!("Could not read local storage."),
+            }
+            Ok(None) => log!("Could not read local storage."),
+            Err(problem) => self.report(problem),
         }
         self.checked_storage = true;
     }
 
+    #[cfg(feature = "app")]
     pub fn add_score(&mut self, evt: FormEvent, player_id: usize) -> bool {
         log!("Adding score.");
 
         if let Ok(score) = evt.values.get("score").unwrap().join("").parse::<i32>() {
-            if !self.settings.enable_score_checking || (score % 5 == 0) {
-                self.game.warn_incorrect_score = false;
-                self.game.add_score(player_id, score, self.game.round);
-                self.check_status();
-                true
-            } else {
-                self.game.warn_incorrect_score = true;
-                false
-            }
+            self.submit_score(player_id, score)
+        } else {
+            false
+        }
+    }
+
+    /// Add a score for the current round, returning false if it was rejected.
+    pub fn submit_score(&mut self, player_id: usize, score: i32) -> bool {
+        if self.read_only {
+            return false;
+        }
+        if !self.settings.enable_score_checking || self.game.is_score_valid(score) {
+            self.game.warn_incorrect_score = false;
+            self.game.add_score(player_id, score);
+            self.save_game();
+            self.check_status();
+            true
         } else {
+            self.game.warn_incorrect_score = true;
             false
         }
     }
 
+    #[cfg(feature = "app")]
     pub fn edit_score(&mut self, evt: FormEvent) {
         log!(format!("This has {:?}", evt.values));
         if let Ok(score) = evt.values.get("score").unwrap().join("").parse::<i32>() {
@@ -281,44 +386,91 @@ impl Model {
                     .join("")
                     .parse::<usize>()
                 {
-                    if !self.settings.enable_score_checking || (score % 5 == 0) {
-                        self.game.warn_incorrect_score = false;
-
-                        for player in &mut self.game.players {
-                            if player_id == player.id {
-                                log!(format!(
-                                    "Player {} has score: {:?}, doubles: {:?}",
-                                    player.name, player.score, player.doubles
-                                ));
-                                player.score.insert(score_id - 1, score);
-
-                                if player.list_of_doubled_games.contains_key(&score_id) {
-                                    player.doubles.insert(score_id, score);
-                                }
-                            }
-                        }
-                        self.game.check_round();
-                        self.game.save_game();
-                        self.check_status()
-                    } else {
-                        self.game.warn_incorrect_score = true;
-                    }
+                    self.change_score(player_id, score_id, score);
                 }
             }
         };
     }
 
+    /// Replace an already-entered score, with the same checks as new scores.
+    pub fn change_score(&mut self, player_id: usize, score_id: usize, score: i32) {
+        if self.read_only {
+            return;
+        }
+        if !self.settings.enable_score_checking || self.game.is_score_valid(score) {
+            self.game.warn_incorrect_score = false;
+            self.game.edit_score(player_id, score_id, score);
+            self.save_game();
+            self.check_status()
+        } else {
+            self.game.warn_incorrect_score = true;
+        }
+    }
+
+    /// Archived games are read-only, so their log can't be undone or redone.
+    pub fn undo(&mut self) {
+        if !self.read_only && self.game.undo() {
+            self.after_rebuild();
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if !self.read_only && self.game.redo() {
+            self.after_rebuild();
+        }
+    }
+
+    /// The scores were rebuilt from the log, so the game may have ended or resumed.
+    fn after_rebuild(&mut self) {
+        if self.game.status != GameStatus::Finished {
+            self.show_end_once = true;
+        }
+        self.save_game();
+        self.check_status();
+    }
+
     pub fn start_game(&mut self) {
         log!("Starting game.");
 
+        self.apply_limits();
         if self.game.start_game() {
+            self.storage.delete(StorageArea::Local, "state");
+            self.storage.delete(StorageArea::Session, "session");
+            self.save_game();
             self.screen = Screen::Game;
         };
     }
 
+    /// Set the round and time limits from the settings, counting time from now.
+    fn apply_limits(&mut self) {
+        self.game.round_limit = self
+            .settings
+            .end_after_rounds
+            .then_some(self.settings.round_limit);
+        self.game.deadline = self.settings.end_at_time.then(|| {
+            clock::next_time_of_day(self.settings.end_time, clock::now(), clock::local_offset())
+        });
+    }
+
+    pub fn save_game(&mut self) {
+        if self.read_only {
+            return;
+        }
+        log!("Saving game.");
+        self.send_snapshot();
+
+        schema::save(self.storage.as_ref(), "game", &self.game);
+        self.storage.set(
+            StorageArea::Session,
+            "session",
+            serde_json::Value::Bool(true),
+        );
+    }
+
     /// Start a new game with the same players and settings as the previous game.
     pub fn reset_game(&mut self) {
         log!("Resetting game.");
+        self.archive_game();
 
         for player in &mut self.game.players {
             player.score.clear();
@@ -332,20 +484,398 @@ impl Model {
         let mut new_game = Game::new();
         new_game.players = self.game.players.clone();
         new_game.status = GameStatus::Ongoing;
+        new_game.tile_bonus_value = self.game.tile_bonus_value;
+        new_game.variant = self.game.variant;
+        new_game.scoring = self.game.scoring;
+        new_game.tie_rule = self.game.tie_rule;
+        new_game.max_score = self.game.max_score;
+        new_game.end_game_at_score = self.settings.end_game_at_score;
+        new_game.max_players = self.game.max_players;
 
         self.game = new_game;
+        self.apply_limits();
         self.screen = Screen::Game;
         self.show_end_once = true;
+        self.save_game();
     }
 
-    pub fn check_status(&mut self) {
-        log!("Check game status.");
+    /// Move a finished game into the history, so it's not lost when a new one starts.
+    pub fn archive_game(&mut self) {
+        if self.game.status != GameStatus::Finished || self.read_only {
+            return;
+        }
+        log!("Archiving finished game.");
+
+        let id = self.history.iter().map(|entry| entry.id).max().unwrap_or(0) + 1;
+        self.history.push(ArchivedGame::new(id, self.game.clone()));
+        self.save_history();
+        schema::delete(self.storage.as_ref(), "game");
+    }
+
+    /// The current game as an exported JSON document.
+    pub fn export_current_game(&self) -> String {
+        ExportDocument::new(Some(self.game.clone()), Vec::new()).to_json()
+    }
+
+    /// Every finished game as an exported JSON document.
+    pub fn export_history(&self) -> String {
+        ExportDocument::new(None, self.history.clone()).to_json()
+    }
 
-        if !self.settings.end_game_at_score {
+    pub fn current_game_csv(&self) -> String {
+        games_to_csv([(String::from("current"), &self.game)])
+    }
+
+    pub fn history_csv(&self) -> String {
+        games_to_csv(
+            self.history
+                .iter()
+                .map(|entry| (format!("{} #{}", entry.date(), entry.id), &entry.game)),
+        )
+    }
+
+    /// Restore games from an exported JSON document.
+    ///
+    /// Finished games are added to the history, skipping ones already there,
+    /// and an exported current game replaces the current one.
+    /// Returns how many games were imported.
+    pub fn import(&mut self, text: &str) -> Result<usize, ImportError> {
+        let document = ExportDocument::from_json(text)?;
+        let mut imported = self.merge_history(document.history);
+
+        if let Some(game) = document.current_game {
+            self.archive_game();
+            self.game = game;
+            self.game.restore_legacy_events();
+            self.show_end_once = self.game.status != GameStatus::Finished;
+            self.save_game();
+            imported += 1;
+        }
+
+        Ok(imported)
+    }
+
+    /// A link that opens the current game on another device.
+    pub fn share_link(&self) -> String {
+        share::share_link(&self.game)
+    }
+
+    /// Make a shared game the current one, moving a finished current game to the history.
+    pub fn open_shared_game(&mut self, text: &str) -> Result<(), ShareError> {
+        let game = share::decode_game(text)?;
+
+        self.archive_game();
+        self.game = game;
+        self.show_end_once = self.game.status != GameStatus::Finished;
+        self.screen = Screen::Game;
+        self.save_game();
+        Ok(())
+    }
+
+    pub fn dismiss_share_error(&mut self) {
+        self.share_error = None;
+    }
+
+    /// Add finished games to the history, skipping ones already there.
+    ///
+    /// Returns how many were added.
+    fn merge_history(&mut self, entries: Vec<ArchivedGame>) -> usize {
+        let mut added = 0;
+
+        for entry in entries {
+            if self.history.iter().any(|existing| {
+                existing.finished_at == entry.finished_at && existing.game == entry.game
+            }) {
+                continue;
+            }
+
+            let id = self.history.iter().map(|entry| entry.id).max().unwrap_or(0) + 1;
+            self.history.push(ArchivedGame { id, ..entry });
+            added += 1;
+        }
+
+        self.save_history();
+        added
+    }
+
+    /// Everything the app has saved, as a backup file.
+    pub fn create_backup(&self) -> String {
+        let game = (self.game.status != GameStatus::NotStarted).then(|| self.game.clone());
+
+        Backup::new(
+            self.settings,
+            self.templates.clone(),
+            self.roster.clone(),
+            game,
+            self.history.clone(),
+        )
+        .to_json()
+    }
+
+    /// Bring back the data from a backup.
+    ///
+    /// Merging keeps the current settings, and the current game unless none is in progress.
+    pub fn restore_backup(&mut self, backup: Backup, mode: RestoreMode) {
+        log!(format!("Restoring backup - {mode:?}."));
+
+        match mode {
+            RestoreMode::Replace => {
+                self.settings = backup.settings;
+                self.roster = backup.roster;
+                self.templates = backup.templates;
+                self.history = backup.history;
+                self.game = backup.game.unwrap_or_default();
+                self.save_history();
+            }
+            RestoreMode::Merge => {
+                let mut backup = backup;
+
+                // The same person may be in both rosters under different ids - keep ours.
+                for entry in backup.roster.entries.clone() {
+                    if self.roster.get(&entry.id).is_some() {
+                        continue;
+                    }
+
+                    let id = self.roster.register(&entry.name, entry.color_index);
+                    if id != entry.id {
+                        backup.rename_roster_id(&entry.id, &id);
+                    }
+                }
+
+                for template in backup.templates {
+                    let known = self
+                        .templates
+                        .iter()
+                        .any(|existing| existing.player_ids == template.player_ids);
+                    if !known && self.templates.len() < 5 {
+                        self.templates.push(GameTemplate {
+                            id: self.templates.len() + 1,
+                            ..template
+                        });
+                    }
+                }
+
+                self.merge_history(backup.history);
+
+                if let Some(game) = backup.game {
+                    if self.game.status != GameStatus::Ongoing {
+                        self.archive_game();
+                        self.game = game;
+                    }
+                }
+            }
+        }
+
+        self.game.restore_legacy_events();
+        self.show_end_once = self.game.status != GameStatus::Finished;
+        self.settings.checked_storage = true;
+        self.save_settings();
+        self.save_roster();
+        self.save_templates();
+        if self.game.status == GameStatus::NotStarted {
+            schema::delete(self.storage.as_ref(), "game");
+        } else {
+            self.save_game();
+        }
+        self.screen = Screen::Menu;
+    }
+
+    /// Host the current game for other devices to join.
+    pub fn host_session(&mut self, relay: &str) {
+        log!("Hosting live session.");
+        self.sync = Some(SyncSession::new(
+            SyncRole::Host,
+            relay,
+            &sync::new_session_code(),
+        ));
+        self.sync_outbox.clear();
+    }
+
+    /// Join a game hosted on another device, which replaces the current game once it arrives.
+    pub fn join_session(&mut self, relay: &str, code: &str) {
+        log!("Joining live session.");
+        let session = SyncSession::new(SyncRole::Guest, relay, code);
+        self.sync_outbox = vec![SyncMessage::Join {
+            device: session.device.clone(),
+        }];
+        self.sync_queued += 1;
+        self.sync = Some(session);
+    }
+
+    pub fn leave_session(&mut self) {
+        log!("Leaving live session.");
+        self.sync = None;
+        self.sync_outbox.clear();
+    }
+
+    pub fn set_sync_connected(&mut self, connected: bool) {
+        if let Some(session) = &mut self.sync {
+            session.connected = connected;
+        }
+    }
+
+    pub fn has_sync_messages(&self) -> bool {
+        !self.sync_outbox.is_empty()
+    }
+
+    /// Changes whenever a message is queued.
+    pub fn sync_queued(&self) -> usize {
+        self.sync_queued
+    }
+
+    /// The messages to send to the other devices, as JSON.
+    pub fn take_sync_messages(&mut self) -> Vec<String> {
+        self.sync_outbox
+            .drain(..)
+            .map(|message| message.to_json())
+            .collect()
+    }
+
+    /// Handle a message from another device in the session.
+    pub fn receive_sync(&mut self, text: &str) {
+        let Some(session) = &self.sync else {
             return;
         };
+        let role = session.role;
+        let device = session.device.clone();
+        let received_game = session.received_game;
+
+        let message = match SyncMessage::from_json(text) {
+            Ok(message) if message.device() != device => message,
+            Ok(_) => return,
+            Err(err) => {
+                log!(format!("Ignoring sync message: {err}"));
+                return;
+            }
+        };
+
+        match (role, message) {
+            (SyncRole::Host, SyncMessage::Join { .. }) => self.send_snapshot(),
+            (SyncRole::Guest, SyncMessage::Snapshot { game, .. }) if !received_game => {
+                if let Some(session) = &mut self.sync {
+                    session.received_game = true;
+                }
+                if self.read_only {
+                    self.close_archived_game();
+                }
+                if self.game.status == GameStatus::Ongoing && game.status == GameStatus::Finished {
+                    self.show_end_once = true;
+                }
+                self.game = *game;
+                if self.screen == Screen::Sync {
+                    self.screen = Screen::Game;
+                }
+                self.check_status();
+                self.save_game();
+            }
+            (_, SyncMessage::Snapshot { game, .. }) if game.uid == self.game.uid => {
+                self.merge_game(&game)
+            }
+            (_, SyncMessage::Snapshot { .. }) => log!("Ignoring a snapshot of another game."),
+            _ => (),
+        }
+    }
+
+    /// Take in the entries another device recorded, and send back any it's missing.
+    fn merge_game(&mut self, game: &Game) {
+        if self.read_only {
+            return;
+        }
+
+        let before = self.game.events.clone();
+        self.game.merge(game);
+
+        if self.game.events != before {
+            self.after_rebuild();
+        } else if self.game.events != game.events {
+            self.send_snapshot();
+        }
+    }
+
+    /// Let the other devices know what the game looks like now.
+    ///
+    /// Guests wait for the host's game first, so theirs doesn't get mixed into it.
+    fn send_snapshot(&mut self) {
+        if let Some(session) = &self.sync {
+            let joined = session.role == SyncRole::Host || session.received_game;
+            if joined && !self.read_only {
+                // Only the latest game matters.
+                self.sync_outbox
+                    .retain(|message| !matches!(message, SyncMessage::Snapshot { .. }));
+                self.sync_outbox.push(SyncMessage::Snapshot {
+                    device: session.device.clone(),
+                    game: Box::new(self.game.clone()),
+                });
+                self.sync_queued += 1;
+            }
+        }
+    }
+
+    pub fn save_history(&self) {
+        schema::save(self.storage.as_ref(), "history", &self.history);
+    }
+
+    pub fn load_history(&mut self) {
+        log!("Trying to load game history.");
+
+        match schema::load(self.storage.as_ref(), "history") {
+            Ok(Some(history)) => self.history = history,
+            Ok(None) => log!("Could not read game history."),
+            Err(problem) => self.report(problem),
+        }
+    }
+
+    /// Lifetime statistics of everyone in the finished games.
+    pub fn player_stats(&self) -> Vec<PlayerStats> {
+        compute_stats(self.history.iter().map(|entry| &entry.game))
+    }
+
+    pub fn delete_archived_game(&mut self, id: usize) {
+        self.history.retain(|entry| entry.id != id);
+        self.save_history();
+    }
+
+    /// Show an archived game in the game screen, without allowing any changes.
+    pub fn view_archived_game(&mut self, id: usize) {
+        if self.read_only {
+            return;
+        }
+
+        if let Some(entry) = self.history.iter().find(|entry| entry.id == id) {
+            let archived = entry.game.clone();
+            self.stashed_game = Some(std::mem::replace(&mut self.game, archived));
+            self.read_only = true;
+            self.screen = Screen::Game;
+        }
+    }
+
+    /// Open the export, import and backup screen, remembering where it was opened from.
+    pub fn open_data_screen(&mut self) {
+        self.data_return_screen = self.screen;
+        self.screen = Screen::Data;
+    }
+
+    pub fn close_data_screen(&mut self) {
+        self.screen = self.data_return_screen;
+    }
+
+    pub fn close_archived_game(&mut self) {
+        if let Some(game) = self.stashed_game.take() {
+            self.game = game;
+        }
+        self.read_only = false;
+        self.screen = Screen::History;
+    }
+
+    pub fn check_status(&mut self) {
+        log!("Check game status.");
 
         self.game.check_status();
+        self.game.check_deadline(clock::now());
+
+        if self.game.status == GameStatus::Finished {
+            self.save_game();
+        }
 
         if self.game.status == GameStatus::Finished && self.show_end_once {
             self.screen = Screen::EndGame;
@@ -358,13 +888,24 @@ impl Model {
             return;
         }
 
+        for player in &mut self.game.players {
+            self.roster.register_player(player);
+        }
+        self.save_roster();
+
         let mut template_name = String::new();
         template_name.push_str(&(self.templates.len() + 1).to_string());
 
         self.templates.push(GameTemplate {
             id: self.templates.len() + 1,
             name: template_name,
-            players: self.game.players.clone(),
+            player_ids: self
+                .game
+                .players
+                .iter()
+                .filter_map(|player| player.uid.clone())
+                .collect(),
+            players: Vec::new(),
             color: 1,
         });
 
@@ -373,6 +914,7 @@ impl Model {
         log!(format!("Saved templates: {:#?}", self.templates));
     }
 
+    #[cfg(feature = "app")]
     pub fn edit_template(&mut self, evt: FormEvent, color_index: usize) {
         let name = evt.values.get("template-name").unwrap().join("");
         if !name.is_empty() {
@@ -395,12 +937,22 @@ impl Model {
     }
 
     pub fn load_template(&mut self, id: usize) {
-        for template in &self.templates {
-            if template.id == id {
-                self.game.players = template.players.clone();
-                self.screen = Screen::PlayerSelect;
+        let Some(template) = self.templates.iter().find(|template| template.id == id) else {
+            return;
+        };
+
+        self.game.players.clear();
+        for entry in template
+            .player_ids
+            .iter()
+            .filter_map(|player_id| self.roster.get(player_id))
+        {
+            self.game.add_player(entry.name.clone(), entry.color_index);
+            if let Some(player) = self.game.players.last_mut() {
+                player.uid = Some(entry.id.clone());
             }
         }
+        self.screen = Screen::PlayerSelect;
     }
 
     pub fn delete_template(&mut self, id: usize) {
@@ -409,51 +961,119 @@ impl Model {
     }
 
     pub fn save_templates(&mut self) {
-        LocalStorage::set("templates", self.templates.clone()).unwrap();
+        schema::save(self.storage.as_ref(), "templates", &self.templates);
     }
 
     pub fn load_saved_templates(&mut self) {
         log!("Trying to load templates.");
 
-        match LocalStorage::get::<serde_json::Value>("templates") {
-            Ok(json_state) => match serde_json::from_value::<Vec<GameTemplate>>(json_state) {
-                Ok(saved_templates) => {
-                    log!(format!("Loaded: {saved_templates:#?}"));
-                    self.templates = saved_templates;
-                    log!(format!("Live is: {:#?}", self.templates));
-                }
-                Err(_) => log!("Could not parse templates."),
-            },
-            Err(_) => log!("Could not read templates."),
+        match schema::load::<Vec<GameTemplate>>(self.storage.as_ref(), "templates") {
+            Ok(Some(saved_templates)) => {
+                log!(format!("Loaded: {saved_templates:#?}"));
+                self.templates = saved_templates;
+                self.move_template_players_to_roster();
+                log!(format!("Live is: {:#?}", self.templates));
+            }
+            Ok(None) => log!("Could not read templates."),
+            Err(problem) => self.report(problem),
         }
     }
 
-    pub fn grant_bonus(&mut self, id: usize) {
-        if !self.game.tile_bonus_granted && self.settings.use_tile_bonus {
-            log!("Granting player bonus.");
-
-            for player in &mut self.game.players {
-                if player.id == id {
-                    player
-                        .bonus
-                        .insert(self.game.round + 1, self.game.tile_bonus_value);
-
-                    if player
-                        .list_of_doubled_games
-                        .contains_key(&(self.game.round + 1))
-                    {
-                        player
-                            .doubles
-                            .insert(self.game.round + 1001, self.game.tile_bonus_value);
-                    }
+    /// Older templates kept whole copies of their players - swap them for roster ids.
+    fn move_template_players_to_roster(&mut self) {
+        if self
+            .templates
+            .iter()
+            .all(|template| template.players.is_empty())
+        {
+            return;
+        }
+        log!("Moving template players to the roster.");
+
+        for template in &mut self.templates {
+            for mut player in template.players.drain(..) {
+                self.roster.register_player(&mut player);
+                if let Some(uid) = player.uid {
+                    template.player_ids.push(uid);
                 }
             }
-            self.game.tile_bonus_granted = true;
-            self.game.new_round_started = false;
-            self.game.tile_bonus_button_active = false;
-            self.game.check_status();
-            self.game.save_game();
         }
+        self.save_roster();
+        self.save_templates();
+    }
+
+    /// Grant the armed bonus to a player.
+    ///
+    /// The tile bonus can be turned off in the settings, the fixed bonuses of other variants can't.
+    pub fn grant_bonus(&mut self, id: usize) {
+        if self.read_only {
+            return;
+        }
+        let kind = self.game.active_bonus;
+        let Some(bonus) = self.game.rules().bonuses.get(kind).copied() else {
+            return;
+        };
+        if bonus.value.is_some() || self.settings.use_tile_bonus {
+            self.game.grant_bonus(id, kind);
+            self.save_game();
+        }
+    }
+
+    /// Pick the variant of the game about to start, and remember it for the next games.
+    pub fn set_variant(&mut self, variant: Variant) {
+        if self.game.status != GameStatus::NotStarted {
+            return;
+        }
+
+        self.settings.variant = variant;
+        self.save_settings();
+        self.apply_variant(variant);
+    }
+
+    /// Change how the game about to start is won, without changing its variant.
+    pub fn set_scoring(&mut self, scoring: ScoringMode) {
+        if self.game.status == GameStatus::NotStarted {
+            self.game.scoring = scoring;
+        }
+    }
+
+    /// Romanian rummy keeps the max score from the settings, other variants use their own target.
+    fn apply_variant(&mut self, variant: Variant) {
+        self.game.set_variant(variant);
+        if variant == Variant::RomanianRummy {
+            self.game.max_score = self.settings.max_score;
+        }
+    }
+
+    pub fn save_settings(&self) {
+        self.settings.save(self.storage.as_ref());
+    }
+
+    pub fn set_max_score(&mut self, value: i32) {
+        self.settings.set_max_score(value);
+        self.save_settings();
+    }
+
+    pub fn set_max_players(&mut self, value: usize) {
+        self.settings.set_max_players(value);
+        self.save_settings();
+    }
+
+    pub fn set_tile_bonus(&mut self, value: i32) {
+        self.settings.set_tile_bonus(value);
+        self.save_settings();
+    }
+
+    /// Wipe everything the app has saved.
+    pub fn clear_storage(&self) {
+        self.storage.clear(StorageArea::Local);
+        self.storage.clear(StorageArea::Session);
+    }
+
+    /// Start over as if the app had just been opened, loading everything from storage again.
+    pub fn restart(&mut self) {
+        log!("Restarting.");
+        *self = Self::with_storage(self.storage.clone());
     }
 
     pub fn enable_tile_bonus(&mut self, enabled: bool) {
@@ -461,7 +1081,7 @@ impl Model {
         log!(format!("Tile bonus is {:?}", self.settings.use_tile_bonus));
     }
 
-    pub fn set_language(&mut self, language: usize) {
+    pub fn set_language(&mut self, language: Language) {
         self.settings.language = language
     }
 
@@ -481,11 +1101,42 @@ impl Model {
     }
     pub fn enable_max_score(&mut self, enabled: bool) {
         self.settings.end_game_at_score = enabled;
+        self.game.end_game_at_score = enabled;
         log!(format!(
             "Max score enabled: {:?}",
             self.settings.end_game_at_score
         ));
     }
+    /// Applies to the current game too, since a tie can only be decided once it happens.
+    pub fn set_tie_rule(&mut self, tie_rule: TieRule) {
+        self.settings.tie_rule = tie_rule;
+        self.game.tie_rule = tie_rule;
+        self.save_settings();
+        if self.game.status == GameStatus::Ongoing {
+            self.save_game();
+        }
+    }
+
+    pub fn enable_round_limit(&mut self, enabled: bool) {
+        self.settings.end_after_rounds = enabled;
+        log!(format!("Round limit enabled: {enabled:?}"));
+    }
+
+    pub fn set_round_limit(&mut self, value: usize) {
+        self.settings.set_round_limit(value);
+        self.save_settings();
+    }
+
+    pub fn enable_end_time(&mut self, enabled: bool) {
+        self.settings.end_at_time = enabled;
+        log!(format!("End time enabled: {enabled:?}"));
+    }
+
+    pub fn set_end_time(&mut self, minutes: u16) {
+        self.settings.set_end_time(minutes);
+        self.save_settings();
+    }
+
     pub fn enable_score_checking(&mut self, enabled: bool) {
         self.settings.enable_score_checking = enabled;
         log!(format!(
@@ -495,8 +1146,474 @@ impl Model {
     }
 }
 
+#[cfg(feature = "app")]
 impl Default for Model {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ledger::{self, Entry};
+    use crate::backend::storage::MemoryStorage;
+
+    fn model_with_players() -> Model {
+        let mut model = Model::with_storage(Rc::new(MemoryStorage::new()));
+        model.create_game();
+        model.add_player(String::from("Antonio"), Some(0));
+        model.add_player(String::from("Vlad"), Some(1));
+        model.start_game();
+        model
+    }
+
+    #[test]
+    fn rejects_scores_that_are_not_multiples_of_five() {
+        let mut model = model_with_players();
+
+        assert!(!model.submit_score(1, 12));
+        assert!(model.game.warn_incorrect_score);
+
+        model.enable_score_checking(false);
+        assert!(model.submit_score(1, 12));
+        assert!(!model.game.warn_incorrect_score);
+    }
+
+    #[test]
+    fn game_is_restored_from_storage() {
+        let mut model = model_with_players();
+        model.submit_score(1, 100);
+        model.submit_score(2, 50);
+
+        let mut restored = Model::with_storage(model.storage.clone());
+        restored.initialize_storage();
+
+        assert_eq!(restored.screen, Screen::Game);
+        assert!(restored.game == model.game);
+    }
+
+    #[test]
+    fn finishing_game_switches_to_end_screen() {
+        let mut model = model_with_players();
+        model.game.max_score = 100;
+
+        model.submit_score(1, 100);
+        model.submit_score(2, 50);
+
+        assert_eq!(model.game.status, GameStatus::Finished);
+        assert_eq!(model.screen, Screen::EndGame);
+    }
+
+    #[test]
+    fn settings_survive_a_reload() {
+        let mut model = Model::with_storage(Rc::new(MemoryStorage::new()));
+        model.set_max_score(500);
+
+        let mut restored = Model::with_storage(model.storage.clone());
+        restored.initialize_storage();
+
+        assert_eq!(restored.settings.max_score, 500);
+    }
+
+    #[test]
+    fn undo_survives_a_reload() {
+        let mut model = model_with_players();
+        model.submit_score(1, 100);
+        model.submit_score(2, 50);
+        model.undo();
+
+        let mut restored = Model::with_storage(model.storage.clone());
+        restored.initialize_storage();
+        assert_eq!(restored.game.players[1].sum, 0);
+
+        restored.redo();
+        assert_eq!(restored.game.players[1].sum, 50);
+    }
+
+    fn finish_with_winner(model: &mut Model) {
+        model.game.max_score = 100;
+        model.submit_score(1, 100);
+        model.submit_score(2, 50);
+    }
+
+    #[test]
+    fn finished_games_are_archived_once() {
+        let mut model = model_with_players();
+        finish_with_winner(&mut model);
+        model.finish_game();
+
+        let mut restored = Model::with_storage(model.storage.clone());
+        restored.initialize_storage();
+        assert_eq!(restored.history.len(), 1);
+        assert_eq!(restored.history[0].winners(), ["Antonio"]);
+        assert_eq!(restored.history[0].game.players[1].sum, 50);
+
+        restored.create_game();
+        assert_eq!(restored.history.len(), 1);
+    }
+
+    #[test]
+    fn replaying_archives_the_previous_game() {
+        let mut model = model_with_players();
+        finish_with_winner(&mut model);
+        model.reset_game();
+
+        assert_eq!(model.history.len(), 1);
+        assert_eq!(model.game.status, GameStatus::Ongoing);
+        assert_eq!(model.game.players[0].sum, 0);
+    }
+
+    #[test]
+    fn archived_games_open_read_only() {
+        let mut model = model_with_players();
+        finish_with_winner(&mut model);
+        model.reset_game();
+        model.submit_score(1, 30);
+        let current = model.game.clone();
+
+        model.view_archived_game(1);
+        assert!(model.read_only);
+        assert_eq!(model.game.get_winners(), ["Antonio"]);
+
+        model.save_game();
+        model.close_archived_game();
+        assert!(model.game == current);
+        assert_eq!(model.screen, Screen::History);
+
+        let mut restored = Model::with_storage(model.storage.clone());
+        restored.initialize_storage();
+        assert!(restored.game == current);
+    }
+
+    #[test]
+    fn archived_games_cannot_be_changed() {
+        let mut model = model_with_players();
+        finish_with_winner(&mut model);
+        model.reset_game();
+
+        model.view_archived_game(1);
+        let archived = model.game.clone();
+
+        model.undo();
+        model.redo();
+        assert!(!model.submit_score(1, 30));
+        model.change_score(1, 1, 500);
+        model.toggle_tile_bonus(0);
+        model.grant_bonus(1);
+        model.double_game_total();
+        model.double_game_for_player(1);
+        assert!(model.game == archived);
+
+        model.close_archived_game();
+        assert_eq!(
+            model.history[0].game.players[0].sum,
+            archived.players[0].sum
+        );
+    }
+
+    #[test]
+    fn players_are_linked_to_the_roster() {
+        let mut model = model_with_players();
+        let vlad = model.game.players[1].uid.clone().unwrap();
+
+        model.create_game();
+        model.add_player(String::from("vlad"), None);
+
+        assert_eq!(model.roster.entries.len(), 2);
+        assert_eq!(model.game.players[0].uid.as_ref(), Some(&vlad));
+        assert_eq!(model.game.players[0].name, "Vlad");
+        assert_eq!(model.game.players[0].color_index, 1);
+    }
+
+    #[test]
+    fn templates_reference_roster_ids() {
+        let mut model = model_with_players();
+        model.add_template();
+
+        let template = model.templates[0].clone();
+        assert!(template.players.is_empty());
+        assert_eq!(template.player_ids.len(), 2);
+
+        model.rename_player(2, String::from("Vlăduț"));
+
+        let mut restored = Model::with_storage(model.storage.clone());
+        restored.initialize_storage();
+        restored.create_game();
+        restored.load_template(template.id);
+
+        assert_eq!(restored.game.players[1].name, "Vlăduț");
+        assert_eq!(restored.game.players[1].uid, model.game.players[1].uid);
+    }
+
+    #[test]
+    fn legacy_templates_move_to_the_roster() {
+        let storage: Rc<dyn Storage> = Rc::new(MemoryStorage::new());
+        let mut legacy = Game::new();
+        legacy.add_player(String::from("Antonio"), 0);
+        legacy.add_player(String::from("Vlad"), 1);
+        let template = GameTemplate {
+            id: 1,
+            name: String::from("1"),
+            player_ids: Vec::new(),
+            players: legacy.players,
+            color: 1,
+        };
+        storage.set(
+            StorageArea::Local,
+            "templates",
+            serde_json::to_value(vec![template]).unwrap(),
+        );
+
+        let mut model = Model::with_storage(storage);
+        model.initialize_storage();
+
+        assert_eq!(model.roster.entries.len(), 2);
+        assert_eq!(model.templates[0].player_ids.len(), 2);
+        assert!(model.templates[0].players.is_empty());
+    }
+
+    #[test]
+    fn chosen_variant_carries_over_to_the_next_game() {
+        let mut model = Model::with_storage(Rc::new(MemoryStorage::new()));
+        model.create_game();
+        model.set_variant(Variant::Rummikub);
+        assert_eq!(model.game.max_score, 100);
+
+        model.create_game();
+        assert_eq!(model.game.variant, Variant::Rummikub);
+        assert!(model.game.is_score_valid(13));
+
+        model.set_variant(Variant::RomanianRummy);
+        assert_eq!(model.game.max_score, model.settings.max_score);
+    }
+
+    #[test]
+    fn round_limit_from_the_settings_ends_the_game() {
+        let mut model = Model::with_storage(Rc::new(MemoryStorage::new()));
+        model.enable_round_limit(true);
+        model.set_round_limit(1);
+        model.create_game();
+        model.add_player(String::from("Antonio"), Some(0));
+        model.add_player(String::from("Vlad"), Some(1));
+        model.start_game();
+        assert_eq!(model.game.round_limit, Some(1));
+        assert_eq!(model.game.deadline, None);
+
+        model.submit_score(1, 50);
+        model.submit_score(2, 20);
+        assert_eq!(model.game.status, GameStatus::Finished);
+        assert_eq!(model.screen, Screen::EndGame);
+    }
+
+    #[test]
+    fn imported_history_skips_games_already_there() {
+        let mut model = model_with_players();
+        finish_with_winner(&mut model);
+        model.archive_game();
+        let export = model.export_history();
+
+        let mut other = Model::with_storage(Rc::new(MemoryStorage::new()));
+        assert_eq!(other.import(&export), Ok(1));
+        assert_eq!(other.import(&export), Ok(0));
+        assert_eq!(other.history.len(), 1);
+        assert_eq!(other.history[0].winners(), ["Antonio"]);
+
+        let csv = other.history_csv();
+        assert_eq!(csv.lines().count(), 1 + 2);
+    }
+
+    #[test]
+    fn imported_current_game_replaces_the_current_one() {
+        let model = model_with_players();
+        let export = model.export_current_game();
+
+        let mut other = Model::with_storage(Rc::new(MemoryStorage::new()));
+        assert_eq!(other.import(&export), Ok(1));
+        assert_eq!(other.game.players.len(), 2);
+        assert!(other.storage.get(StorageArea::Local, "game").is_some());
+        assert!(other.import("{}").is_err());
+    }
+
+    #[test]
+    fn unreadable_settings_are_reported_and_kept() {
+        let storage = Rc::new(MemoryStorage::new());
+        storage.set(
+            StorageArea::Local,
+            "settings",
+            serde_json::json!({ "max_score": "a lot" }),
+        );
+
+        let mut model = Model::with_storage(storage.clone());
+        model.initialize_storage();
+        assert_eq!(model.storage_problems.len(), 1);
+        assert_eq!(model.storage_problems[0].key, "settings");
+
+        model.save_settings();
+        assert!(storage
+            .get(StorageArea::Local, "settings.unreadable")
+            .is_some());
+    }
+
+    #[test]
+    fn backup_restores_everything_after_clearing() {
+        let mut model = model_with_players();
+        model.add_template();
+        model.set_max_score(500);
+        finish_with_winner(&mut model);
+        model.archive_game();
+        let backup = model.create_backup();
+
+        model.clear_storage();
+        let mut fresh = Model::with_storage(model.storage.clone());
+        fresh.initialize_storage();
+        assert!(fresh.history.is_empty());
+
+        fresh.restore_backup(Backup::from_json(&backup).unwrap(), RestoreMode::Replace);
+        let mut restored = Model::with_storage(model.storage.clone());
+        restored.initialize_storage();
+
+        assert_eq!(restored.history.len(), 1);
+        assert_eq!(restored.templates.len(), 1);
+        assert_eq!(restored.roster.entries.len(), 2);
+        assert_eq!(restored.settings.max_score, 500);
+    }
+
+    #[test]
+    fn merging_a_backup_keeps_the_game_in_progress() {
+        let mut model = model_with_players();
+        finish_with_winner(&mut model);
+        model.archive_game();
+        let backup = Backup::from_json(&model.create_backup()).unwrap();
+
+        let mut other = model_with_players();
+        other.submit_score(1, 20);
+        other.restore_backup(backup.clone(), RestoreMode::Merge);
+        other.restore_backup(backup, RestoreMode::Merge);
+
+        assert_eq!(other.history.len(), 1);
+        assert_eq!(other.game.players[0].sum, 20);
+        assert_eq!(other.roster.entries.len(), 2);
+    }
+
+    #[test]
+    fn shared_games_open_on_another_device() {
+        let mut model = model_with_players();
+        model.submit_score(1, 20);
+        model.submit_score(2, 35);
+        let link = model.share_link();
+
+        let mut other = Model::with_storage(Rc::new(MemoryStorage::new()));
+        let payload = share::payload_from_link(&link).unwrap();
+        other.open_shared_game(payload).unwrap();
+
+        assert_eq!(other.screen, Screen::Game);
+        assert_eq!(other.game.players[1].sum, 35);
+        assert!(other.open_shared_game("garbage").is_err());
+        assert_eq!(other.game.players[1].sum, 35);
+    }
+
+    /// Pass every waiting message from one device to the other, as the relay would.
+    fn deliver(from: &mut Model, to: &mut Model) {
+        for message in from.take_sync_messages() {
+            to.receive_sync(&message);
+        }
+    }
+
+    #[test]
+    fn guests_get_the_hosts_game_and_scores_merge_both_ways() {
+        ledger::set_device_id("host");
+        let mut host = model_with_players();
+        host.host_session("ws://localhost:8787");
+        let code = host.sync.as_ref().unwrap().code.clone();
+
+        ledger::set_device_id("guest");
+        let mut guest = Model::with_storage(Rc::new(MemoryStorage::new()));
+        guest.join_session("ws://localhost:8787", &code.to_lowercase());
+        deliver(&mut guest, &mut host);
+        deliver(&mut host, &mut guest);
+        assert_eq!(guest.game.players.len(), 2);
+        assert!(guest.sync.as_ref().unwrap().received_game);
+
+        // Both devices score a round before hearing from each other.
+        assert!(guest.submit_score(1, 40));
+        ledger::set_device_id("host");
+        host.submit_score(2, 25);
+        deliver(&mut guest, &mut host);
+        deliver(&mut host, &mut guest);
+        deliver(&mut guest, &mut host);
+
+        assert_eq!(host.game.players[0].sum, 40);
+        assert_eq!(guest.game.players[1].sum, 25);
+        assert_eq!(guest.game.events, host.game.events);
+
+        // Undoing on one device undoes it everywhere.
+        ledger::set_device_id("guest");
+        guest.undo();
+        deliver(&mut guest, &mut host);
+        assert_eq!(host.game.events, guest.game.events);
+        deliver(&mut host, &mut guest);
+        assert!(!guest.has_sync_messages());
+    }
+
+    #[test]
+    fn snapshots_with_malformed_actions_are_ignored() {
+        let mut host = model_with_players();
+        host.host_session("ws://localhost:8787");
+        host.submit_score(1, 10);
+        host.take_sync_messages();
+        let before = host.game.clone();
+
+        let mut game = host.game.clone();
+        game.events.push(Entry::new(
+            99,
+            GameEvent::EditScore {
+                player_id: 1,
+                score_id: 0,
+                value: 5,
+            },
+        ));
+        let message = SyncMessage::Snapshot {
+            device: String::from("guest"),
+            game: Box::new(game),
+        };
+        host.receive_sync(&message.to_json());
+
+        assert!(host.game == before);
+        assert!(!host.has_sync_messages());
+    }
+
+    #[test]
+    fn snapshots_of_another_game_are_ignored() {
+        let mut host = model_with_players();
+        host.host_session("ws://localhost:8787");
+        host.submit_score(1, 10);
+        host.take_sync_messages();
+
+        let mut other = model_with_players();
+        ledger::set_device_id("guest");
+        other.submit_score(2, 500);
+        let message = SyncMessage::Snapshot {
+            device: String::from("guest"),
+            game: Box::new(other.game.clone()),
+        };
+        host.receive_sync(&message.to_json());
+
+        assert_eq!(host.game.players[1].sum, 0);
+        assert!(!host.has_sync_messages());
+    }
+
+    #[test]
+    fn devices_ignore_their_own_and_broken_messages() {
+        let mut host = model_with_players();
+        host.host_session("ws://localhost:8787");
+        host.submit_score(1, 10);
+        let echoed = host.take_sync_messages();
+
+        for message in &echoed {
+            host.receive_sync(message);
+        }
+        host.receive_sync("not a message");
+        assert!(!host.has_sync_messages());
+    }
+}
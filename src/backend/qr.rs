@@ -0,0 +1,488 @@
+//! A small QR code encoder, for showing a shared game link on screen.
+//!
+//! Only what the app needs is supported: text is encoded in byte mode with
+//! medium error correction, in the smallest version it fits.
+//! Follows the structure of Project Nayuki's QR Code generator.
+
+/// Codewords per error correction block, by version, for the medium level.
+const ECC_CODEWORDS_PER_BLOCK: [usize; 41] = [
+    0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28,
+    28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+];
+
+/// Error correction blocks, by version, for the medium level.
+const NUM_ERROR_CORRECTION_BLOCKS: [usize; 41] = [
+    0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23,
+    25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+];
+
+/// The format bits of the medium error correction level.
+const ECC_MEDIUM_FORMAT_BITS: u32 = 0;
+
+pub const MAX_VERSION: usize = 40;
+
+/// A square grid of modules, `true` being dark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QrCode {
+    pub version: usize,
+    pub size: usize,
+    modules: Vec<bool>,
+    is_function: Vec<bool>,
+}
+
+impl QrCode {
+    /// Encode text, or `None` if it is too long for any QR code.
+    pub fn encode(text: &str) -> Option<Self> {
+        let data = text.as_bytes();
+
+        let version = (1..=MAX_VERSION).find(|version| {
+            let count_bits = if *version <= 9 { 8 } else { 16 };
+            4 + count_bits + data.len() * 8 <= data_codewords(*version) * 8
+        })?;
+
+        let mut bits = BitBuffer::default();
+        bits.append(0b0100, 4);
+        bits.append(data.len() as u32, if version <= 9 { 8 } else { 16 });
+        for byte in data {
+            bits.append(*byte as u32, 8);
+        }
+
+        let capacity = data_codewords(version) * 8;
+        bits.append(0, (capacity - bits.0.len()).min(4));
+        bits.append(0, (8 - bits.0.len() % 8) % 8);
+        for pad in [0xEC, 0x11].iter().cycle() {
+            if bits.0.len() >= capacity {
+                break;
+            }
+            bits.append(*pad, 8);
+        }
+
+        let codewords: Vec<u8> = bits
+            .0
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0, |acc, bit| acc << 1 | *bit as u8))
+            .collect();
+
+        let mut qr = Self::new(version);
+        qr.draw_function_patterns();
+        qr.draw_codewords(&add_ecc_and_interleave(&codewords, version));
+
+        let mask = (0..8)
+            .min_by_key(|mask| {
+                let mut candidate = qr.clone();
+                candidate.apply_mask(*mask);
+                candidate.draw_format_bits(*mask);
+                candidate.penalty()
+            })
+            .unwrap_or(0);
+        qr.apply_mask(mask);
+        qr.draw_format_bits(mask);
+
+        Some(qr)
+    }
+
+    fn new(version: usize) -> Self {
+        let size = version * 4 + 17;
+        Self {
+            version,
+            size,
+            modules: vec![false; size * size],
+            is_function: vec![false; size * size],
+        }
+    }
+
+    /// Whether the module at column `x` and row `y` is dark.
+    pub fn module(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.is_function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        for i in 0..self.size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        self.draw_finder_pattern(3, 3);
+        self.draw_finder_pattern(self.size as i32 - 4, 3);
+        self.draw_finder_pattern(3, self.size as i32 - 4);
+
+        let positions = alignment_pattern_positions(self.version);
+        let last = positions.len().saturating_sub(1);
+        for (i, x) in positions.iter().enumerate() {
+            for (j, y) in positions.iter().enumerate() {
+                let corner = (i == 0 && (j == 0 || j == last)) || (i == last && j == 0);
+                if !corner {
+                    self.draw_alignment_pattern(*x, *y);
+                }
+            }
+        }
+
+        // Reserve the format area, it is drawn for real once the mask is chosen.
+        self.draw_format_bits(0);
+        self.draw_version();
+    }
+
+    fn draw_finder_pattern(&mut self, x: i32, y: i32) {
+        for dy in -4..=4 {
+            for dx in -4..=4 {
+                let (xx, yy) = (x + dx, y + dy);
+                if (0..self.size as i32).contains(&xx) && (0..self.size as i32).contains(&yy) {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(xx as usize, yy as usize, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, x: usize, y: usize) {
+        for dy in -2..=2_i32 {
+            for dx in -2..=2_i32 {
+                let dark = dx.abs().max(dy.abs()) != 1;
+                self.set_function((x as i32 + dx) as usize, (y as i32 + dy) as usize, dark);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        let data = ECC_MEDIUM_FORMAT_BITS << 3 | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = (data << 10 | remainder) ^ 0x5412;
+        let bit = |i: u32| (bits >> i) & 1 != 0;
+
+        for i in 0..=5 {
+            self.set_function(8, i as usize, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i as usize, 8, bit(i));
+        }
+
+        let size = self.size;
+        for i in 0..8 {
+            self.set_function(size - 1 - i as usize, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i as usize, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+
+        let mut remainder = self.version as u32;
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+        }
+        let bits = (self.version as u32) << 12 | remainder;
+
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 != 0;
+            let a = self.size - 11 + i % 3;
+            let b = i / 3;
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Lay out the data in the zigzag order, two columns at a time from the bottom right.
+    fn draw_codewords(&mut self, data: &[u8]) {
+        let mut i = 0;
+        let mut right = self.size as i32 - 1;
+
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..self.size {
+                for j in 0..2 {
+                    let x = (right - j) as usize;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        self.size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+
+                    if !self.is_function[y * self.size + x] && i < data.len() * 8 {
+                        self.modules[y * self.size + x] = (data[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !self.is_function[y * self.size + x] {
+                    self.modules[y * self.size + x] ^= true;
+                }
+            }
+        }
+    }
+
+    /// How hard the code is to scan - lower is better.
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let row = |y: usize| {
+            (0..size)
+                .map(move |x| self.module(x, y))
+                .collect::<Vec<_>>()
+        };
+        let column = |x: usize| {
+            (0..size)
+                .map(move |y| self.module(x, y))
+                .collect::<Vec<_>>()
+        };
+        let lines: Vec<Vec<bool>> = (0..size).map(row).chain((0..size).map(column)).collect();
+
+        let mut penalty = 0;
+
+        for line in &lines {
+            // Runs of five or more modules of the same color.
+            let mut run = 1;
+            for i in 1..=line.len() {
+                if i < line.len() && line[i] == line[i - 1] {
+                    run += 1;
+                } else {
+                    if run >= 5 {
+                        penalty += run - 2;
+                    }
+                    run = 1;
+                }
+            }
+
+            // Patterns that look like a finder pattern.
+            let finder = [true, false, true, true, true, false, true];
+            for start in 0..line.len().saturating_sub(finder.len() - 1) {
+                if line[start..start + finder.len()] != finder {
+                    continue;
+                }
+                let light_before = start >= 4 && line[start - 4..start].iter().all(|dark| !dark);
+                let end = start + finder.len();
+                let light_after =
+                    end + 4 <= line.len() && line[end..end + 4].iter().all(|dark| !dark);
+                if light_before || light_after {
+                    penalty += 40;
+                }
+            }
+        }
+
+        // 2x2 blocks of the same color.
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.module(x, y);
+                if color == self.module(x + 1, y)
+                    && color == self.module(x, y + 1)
+                    && color == self.module(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+
+        // Far from half dark and half light.
+        let dark = self.modules.iter().filter(|dark| **dark).count();
+        let total = size * size;
+        let deviation = (dark * 20).abs_diff(total * 10);
+        penalty += deviation.div_ceil(total).saturating_sub(1) * 10;
+
+        penalty
+    }
+}
+
+#[derive(Default)]
+struct BitBuffer(Vec<bool>);
+
+impl BitBuffer {
+    fn append(&mut self, value: u32, length: usize) {
+        for i in (0..length).rev() {
+            self.0.push((value >> i) & 1 != 0);
+        }
+    }
+}
+
+fn alignment_pattern_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+
+    let count = version / 7 + 2;
+    let step = if version == 32 {
+        26
+    } else {
+        (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2
+    };
+
+    let mut positions = vec![6];
+    let mut position = version * 4 + 17 - 7;
+    for _ in 0..count - 1 {
+        positions.insert(1, position);
+        position -= step;
+    }
+    positions
+}
+
+/// Modules left for data and error correction once the function patterns are drawn.
+fn raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        result -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn data_codewords(version: usize) -> usize {
+    raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[version] * NUM_ERROR_CORRECTION_BLOCKS[version]
+}
+
+/// Split the data into blocks, add error correction to each, and interleave them.
+fn add_ecc_and_interleave(data: &[u8], version: usize) -> Vec<u8> {
+    let blocks_count = NUM_ERROR_CORRECTION_BLOCKS[version];
+    let ecc_length = ECC_CODEWORDS_PER_BLOCK[version];
+    let raw_codewords = raw_data_modules(version) / 8;
+    let short_blocks = blocks_count - raw_codewords % blocks_count;
+    let short_block_length = raw_codewords / blocks_count;
+
+    let divisor = reed_solomon_divisor(ecc_length);
+    let mut blocks: Vec<Vec<u8>> = Vec::with_capacity(blocks_count);
+    let mut start = 0;
+
+    for i in 0..blocks_count {
+        let length = short_block_length - ecc_length + usize::from(i >= short_blocks);
+        let mut block = data[start..start + length].to_vec();
+        start += length;
+
+        let ecc = reed_solomon_remainder(&block, &divisor);
+        if i < short_blocks {
+            block.push(0);
+        }
+        block.extend(ecc);
+        blocks.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..blocks[0].len() {
+        for (j, block) in blocks.iter().enumerate() {
+            // Skip the padding byte of the short blocks.
+            if i != short_block_length - ecc_length || j >= short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+    let mut root = 1;
+
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = reed_solomon_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = reed_solomon_multiply(root, 0x02);
+    }
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+
+    for byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (x, y) in result.iter_mut().zip(divisor) {
+            *x ^= reed_solomon_multiply(*y, factor);
+        }
+    }
+    result
+}
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1.
+fn reed_solomon_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u32 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11D);
+        z ^= ((y as u32 >> i) & 1) * x as u32;
+    }
+    z as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_smallest_version_that_fits() {
+        assert_eq!(QrCode::encode("rummy").unwrap().version, 1);
+        assert_eq!(QrCode::encode(&"x".repeat(100)).unwrap().size, 6 * 4 + 17);
+        assert!(QrCode::encode(&"x".repeat(3000)).is_none());
+    }
+
+    #[test]
+    fn data_capacity_matches_the_standard() {
+        // Byte mode capacities at the medium level, from the QR code specification.
+        assert_eq!(data_codewords(1), 16);
+        assert_eq!(data_codewords(10), 216);
+        assert_eq!(data_codewords(40), 2334);
+    }
+
+    #[test]
+    fn error_correction_divides_the_message() {
+        let divisor = reed_solomon_divisor(10);
+        let data = b"Rummy nights!";
+        let mut message = data.to_vec();
+        message.extend(reed_solomon_remainder(data, &divisor));
+
+        assert!(reed_solomon_remainder(&message, &divisor)
+            .iter()
+            .all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn finder_patterns_sit_in_three_corners() {
+        let qr = QrCode::encode("https://example.com/#game=abc").unwrap();
+        let last = qr.size - 1;
+
+        for (x, y) in [(0, 0), (last, 0), (0, last)] {
+            assert!(qr.module(x, y));
+        }
+        assert!(!qr.module(7, 7));
+        assert!(qr.module(8, qr.size - 8));
+    }
+}
@@ -0,0 +1,103 @@
+//! The roster of people who have played before.
+//!
+//! Every player in a game points to a roster entry through [`Player::uid`],
+//! which is how the same person is recognized across games and templates.
+
+use crate::backend::prelude::*;
+use crate::backend::uid::new_uid;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RosterEntry {
+    pub id: String,
+    pub name: String,
+    /// The color the player gets when added to a game, unless another one is picked.
+    pub color_index: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct Roster {
+    pub entries: Vec<RosterEntry>,
+}
+
+impl Roster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, id: &str) -> Option<&RosterEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// Names are compared ignoring case and surrounding spaces.
+    pub fn find_by_name(&self, name: &str) -> Option<&RosterEntry> {
+        let name = name.trim().to_lowercase();
+        self.entries
+            .iter()
+            .find(|entry| entry.name.trim().to_lowercase() == name)
+    }
+
+    /// Find the roster entry of a player, adding them if they are new.
+    ///
+    /// Returns the id of the entry.
+    pub fn register(&mut self, name: &str, color_index: usize) -> String {
+        if let Some(entry) = self.find_by_name(name) {
+            return entry.id.clone();
+        }
+
+        let id = new_uid();
+        self.entries.push(RosterEntry {
+            id: id.clone(),
+            name: name.trim().to_string(),
+            color_index,
+        });
+        id
+    }
+
+    /// Make sure the player points to a roster entry, adding one if needed.
+    pub fn register_player(&mut self, player: &mut Player) {
+        match &player.uid {
+            Some(uid) if self.get(uid).is_some() => {}
+            Some(uid) => self.entries.push(RosterEntry {
+                id: uid.clone(),
+                name: player.name.clone(),
+                color_index: player.color_index,
+            }),
+            None => player.uid = Some(self.register(&player.name, player.color_index)),
+        }
+    }
+
+    pub fn rename(&mut self, id: &str, name: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+            entry.name = name.trim().to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registering_a_known_name_reuses_the_entry() {
+        let mut roster = Roster::new();
+        let vlad = roster.register("Vlad", 2);
+
+        assert_eq!(roster.register(" vlad ", 5), vlad);
+        assert_eq!(roster.entries.len(), 1);
+        assert_eq!(roster.get(&vlad).unwrap().color_index, 2);
+    }
+
+    #[test]
+    fn players_keep_their_existing_uid() {
+        let mut roster = Roster::new();
+        let mut game = Game::new();
+        game.add_player(String::from("Ana"), 3);
+        game.players[0].uid = Some(String::from("uid-ana"));
+
+        roster.register_player(&mut game.players[0]);
+
+        assert_eq!(roster.get("uid-ana").unwrap().name, "Ana");
+        assert_eq!(roster.find_by_name("ana").unwrap().id, "uid-ana");
+    }
+}
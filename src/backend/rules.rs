@@ -0,0 +1,220 @@
+//! The rummy variants the app can keep score for.
+//!
+//! A game picks its [`Variant`] when it is created, and everything that
+//! differs between variants - valid scores, bonuses, when the game ends and
+//! who wins - is looked up in the variant's [`RuleSet`].
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+pub enum Variant {
+    /// Romanian tile rummy - the app's original rules.
+    #[default]
+    RomanianRummy,
+    /// Penalty points for the tiles left in hand - the lowest total wins.
+    Rummikub,
+    /// First to 100, with bonuses for going gin and for undercutting.
+    GinRummy,
+    /// First to 500, points can go negative.
+    Rummy500,
+}
+
+impl Variant {
+    pub const ALL: [Variant; 4] = [
+        Variant::RomanianRummy,
+        Variant::Rummikub,
+        Variant::GinRummy,
+        Variant::Rummy500,
+    ];
+
+    pub fn rules(&self) -> RuleSet {
+        match self {
+            Variant::RomanianRummy => RuleSet {
+                label: "variant_romanian",
+                target_score: 1000,
+                scoring: ScoringMode::HighestWins,
+                score_step: 5,
+                bonuses: &[BonusRule {
+                    label: "tile_bonus",
+                    value: None,
+                }],
+                double_games: true,
+            },
+            Variant::Rummikub => RuleSet {
+                label: "variant_rummikub",
+                target_score: 100,
+                scoring: ScoringMode::LowestWins,
+                score_step: 1,
+                bonuses: &[],
+                double_games: false,
+            },
+            Variant::GinRummy => RuleSet {
+                label: "variant_gin",
+                target_score: 100,
+                scoring: ScoringMode::HighestWins,
+                score_step: 1,
+                bonuses: &[
+                    BonusRule {
+                        label: "gin_bonus",
+                        value: Some(25),
+                    },
+                    BonusRule {
+                        label: "undercut_bonus",
+                        value: Some(25),
+                    },
+                ],
+                double_games: false,
+            },
+            Variant::Rummy500 => RuleSet {
+                label: "variant_500",
+                target_score: 500,
+                scoring: ScoringMode::HighestWins,
+                score_step: 5,
+                bonuses: &[],
+                double_games: false,
+            },
+        }
+    }
+}
+
+/// How totals decide when the game ends and who wins.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+pub enum ScoringMode {
+    /// The game ends when someone reaches the max score, the highest total wins.
+    #[default]
+    HighestWins,
+    /// Scores are penalties - the game ends when someone reaches the max score, the lowest total wins.
+    LowestWins,
+    /// Penalties are entered as negative scores - the game ends when someone drops to minus
+    /// the max score, the highest total wins.
+    NegativePenalty,
+}
+
+impl ScoringMode {
+    pub const ALL: [ScoringMode; 3] = [
+        ScoringMode::HighestWins,
+        ScoringMode::LowestWins,
+        ScoringMode::NegativePenalty,
+    ];
+
+    /// Translation key of the mode name.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScoringMode::HighestWins => "scoring_highest",
+            ScoringMode::LowestWins => "scoring_lowest",
+            ScoringMode::NegativePenalty => "scoring_penalty",
+        }
+    }
+
+    /// Whether the totals are enough to end the game at `max_score`.
+    pub fn reached_target(&self, totals: &[i32], max_score: i32) -> bool {
+        match self {
+            ScoringMode::HighestWins | ScoringMode::LowestWins => {
+                totals.iter().any(|total| *total >= max_score)
+            }
+            ScoringMode::NegativePenalty => totals.iter().any(|total| *total <= -max_score),
+        }
+    }
+
+    /// Orders totals from best to worst.
+    pub fn rank(&self, a: i32, b: i32) -> Ordering {
+        match self {
+            ScoringMode::LowestWins => a.cmp(&b),
+            ScoringMode::HighestWins | ScoringMode::NegativePenalty => b.cmp(&a),
+        }
+    }
+
+    /// The best of the totals.
+    pub fn best_total(&self, totals: &[i32]) -> Option<i32> {
+        totals.iter().copied().min_by(|a, b| self.rank(*a, *b))
+    }
+}
+
+/// What happens when several players share the best total at the end of the game.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Debug, Default)]
+pub enum TieRule {
+    /// Keep playing until there is a single leader.
+    #[default]
+    PlayAnotherRound,
+    /// Everyone tied for the lead wins.
+    ShareWin,
+    /// The leader who spent the fewest rounds past the max score wins,
+    /// and if that is a tie too, another round is played.
+    FewestRoundsOverLimit,
+}
+
+impl TieRule {
+    pub const ALL: [TieRule; 3] = [
+        TieRule::PlayAnotherRound,
+        TieRule::ShareWin,
+        TieRule::FewestRoundsOverLimit,
+    ];
+
+    /// Translation key of the rule name.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TieRule::PlayAnotherRound => "tie_another_round",
+            TieRule::ShareWin => "tie_share_win",
+            TieRule::FewestRoundsOverLimit => "tie_fewest_rounds",
+        }
+    }
+}
+
+/// A bonus that can be given to one player per round.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BonusRule {
+    /// Translation key of the bonus button.
+    pub label: &'static str,
+    /// The points it is worth, or `None` to use the tile bonus value from the settings.
+    pub value: Option<i32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RuleSet {
+    /// Translation key of the variant name.
+    pub label: &'static str,
+    /// The default score that ends the game.
+    pub target_score: i32,
+    /// The scoring mode a game of this variant starts with.
+    pub scoring: ScoringMode,
+    /// Scores have to be multiples of this - 1 allows any score.
+    pub score_step: i32,
+    pub bonuses: &'static [BonusRule],
+    pub double_games: bool,
+}
+
+impl RuleSet {
+    pub fn is_valid_score(&self, score: i32) -> bool {
+        score % self.score_step == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn romanian_scores_come_in_steps_of_five() {
+        let rules = Variant::RomanianRummy.rules();
+        assert!(rules.is_valid_score(-25));
+        assert!(!rules.is_valid_score(12));
+        assert!(Variant::GinRummy.rules().is_valid_score(12));
+    }
+
+    #[test]
+    fn best_total_follows_the_scoring_mode() {
+        let totals = [120, 40, 95];
+        assert_eq!(ScoringMode::HighestWins.best_total(&totals), Some(120));
+        assert_eq!(ScoringMode::LowestWins.best_total(&totals), Some(40));
+        assert_eq!(Variant::Rummikub.rules().scoring, ScoringMode::LowestWins);
+    }
+
+    #[test]
+    fn negative_penalties_end_below_minus_the_max_score() {
+        let mode = ScoringMode::NegativePenalty;
+        assert!(!mode.reached_target(&[-90, 0], 100));
+        assert!(mode.reached_target(&[-105, -20], 100));
+        assert_eq!(mode.best_total(&[-105, -20]), Some(-20));
+    }
+}
@@ -0,0 +1,448 @@
+//! Versioned layouts of the data kept in local storage.
+//!
+//! Every key is saved together with the schema version it was written in,
+//! under `<key>.version`. When loading, older data is brought up to date by
+//! running the migrations after its version in order. Data that still can't
+//! be read is copied to `<key>.unreadable` and reported, so it isn't lost
+//! when the app saves over it.
+
+use crate::backend::events::LegacyRounds;
+use crate::backend::storage::{Storage, StorageArea};
+use crate::log;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The layout the app currently writes.
+///
+/// Data saved before versions were stored counts as version 0.
+pub const SCHEMA_VERSION: u32 = 4;
+
+/// A step from one version of a key's layout to the next.
+struct Migration {
+    key: &'static str,
+    /// The version this migration upgrades from.
+    from: u32,
+    migrate: fn(&mut Value),
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        key: "game",
+        from: 0,
+        migrate: migrate_game_winner,
+    },
+    Migration {
+        key: "history",
+        from: 0,
+        migrate: migrate_history_winners,
+    },
+    Migration {
+        key: "game",
+        from: 1,
+        migrate: migrate_game_entries,
+    },
+    Migration {
+        key: "history",
+        from: 1,
+        migrate: migrate_history_entries,
+    },
+    Migration {
+        key: "settings",
+        from: 2,
+        migrate: migrate_settings_language,
+    },
+    Migration {
+        key: "game",
+        from: 3,
+        migrate: migrate_game_bonus_rounds,
+    },
+    Migration {
+        key: "history",
+        from: 3,
+        migrate: migrate_history_bonus_rounds,
+    },
+];
+
+/// Games used to keep a single `winner_name`, empty until someone won.
+fn migrate_game_winner(game: &mut Value) {
+    let Some(fields) = game.as_object_mut() else {
+        return;
+    };
+
+    if let Some(Value::String(name)) = fields.remove("winner_name") {
+        let winners: Vec<Value> = if name.is_empty() {
+            Vec::new()
+        } else {
+            vec![Value::String(name)]
+        };
+        fields.insert(String::from("winners"), Value::Array(winners));
+    }
+}
+
+fn migrate_history_winners(history: &mut Value) {
+    if let Some(entries) = history.as_array_mut() {
+        for entry in entries {
+            if let Some(game) = entry.get_mut("game") {
+                migrate_game_winner(game);
+            }
+        }
+    }
+}
+
+/// Events used to be bare actions, scores going to the player's next round.
+/// Now they are stamped entries, and scores say which round they are for.
+fn migrate_game_entries(game: &mut Value) {
+    let mut rounds: HashMap<u64, u64> = HashMap::new();
+    let mut clock = 0;
+
+    for key in ["events", "undone_events"] {
+        let Some(events) = game.get_mut(key).and_then(|events| events.as_array_mut()) else {
+            continue;
+        };
+
+        for event in events {
+            if event.get("stamp").is_some() {
+                continue;
+            }
+            if let Some(score) = event
+                .get_mut("AddScore")
+                .and_then(|score| score.as_object_mut())
+            {
+                let player_id = score
+                    .get("player_id")
+                    .and_then(|id| id.as_u64())
+                    .unwrap_or(0);
+                let round = rounds.entry(player_id).or_default();
+                score.insert(String::from("round"), Value::from(*round));
+                *round += 1;
+            }
+
+            clock += 1;
+            *event = serde_json::json!({
+                "stamp": { "clock": clock, "author": "" },
+                "event": event.take(),
+            });
+        }
+    }
+
+    // Undone actions must stay undone when merged with another device.
+    let retracted: Vec<Value> = game
+        .get("undone_events")
+        .and_then(|events| events.as_array())
+        .map(|events| events.iter().map(|entry| entry["stamp"].clone()).collect())
+        .unwrap_or_default();
+    if let Some(fields) = game.as_object_mut() {
+        fields.insert(String::from("retracted"), Value::Array(retracted));
+    }
+}
+
+fn migrate_history_entries(history: &mut Value) {
+    if let Some(entries) = history.as_array_mut() {
+        for entry in entries {
+            if let Some(game) = entry.get_mut("game") {
+                migrate_game_entries(game);
+            }
+        }
+    }
+}
+
+/// Bonuses and doubles went to whatever round was in progress when the log was
+/// replayed. Now they say which round they are for, like scores.
+fn migrate_game_bonus_rounds(game: &mut Value) {
+    let players = game
+        .get("players")
+        .and_then(|players| players.as_array())
+        .map_or(0, Vec::len);
+    let mut rounds = LegacyRounds::new(players);
+    let number = |fields: &Value, name: &str| {
+        fields
+            .get(name)
+            .and_then(|number| number.as_u64())
+            .unwrap_or(0) as usize
+    };
+
+    for key in ["events", "undone_events"] {
+        let Some(entries) = game.get_mut(key).and_then(|entries| entries.as_array_mut()) else {
+            continue;
+        };
+
+        for entry in entries {
+            let Some(event) = entry.get_mut("event") else {
+                continue;
+            };
+            if event == "DoubleGameTotal" {
+                *event = serde_json::json!({ "DoubleGameTotal": { "round": rounds.current() } });
+                continue;
+            }
+            let Some((kind, fields)) = event
+                .as_object_mut()
+                .and_then(|event| event.iter_mut().next())
+            else {
+                continue;
+            };
+
+            let player_id = number(fields, "player_id");
+            match kind.as_str() {
+                "AddScore" => rounds.scored(player_id, number(fields, "round")),
+                "EditScore" => {
+                    rounds.scored(player_id, number(fields, "score_id").saturating_sub(1))
+                }
+                "GrantBonus" | "DoubleGameForPlayer" => {
+                    if let Some(fields) = fields.as_object_mut() {
+                        fields
+                            .entry("round")
+                            .or_insert_with(|| Value::from(rounds.current()));
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+fn migrate_history_bonus_rounds(history: &mut Value) {
+    if let Some(entries) = history.as_array_mut() {
+        for entry in entries {
+            if let Some(game) = entry.get_mut("game") {
+                migrate_game_bonus_rounds(game);
+            }
+        }
+    }
+}
+
+/// The language used to be a number - 1 for English, 2 for Romanian.
+fn migrate_settings_language(settings: &mut Value) {
+    if let Some(language) = settings.get_mut("language") {
+        if language.is_number() {
+            let code = if language.as_u64() == Some(2) {
+                "ro"
+            } else {
+                "en"
+            };
+            *language = Value::from(code);
+        }
+    }
+}
+
+/// Saved data that could not be loaded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StorageProblem {
+    pub key: String,
+    pub reason: String,
+}
+
+impl fmt::Display for StorageProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.reason)
+    }
+}
+
+fn version_key(key: &str) -> String {
+    format!("{key}.version")
+}
+
+/// Where data that couldn't be read is kept.
+pub fn unreadable_key(key: &str) -> String {
+    format!("{key}.unreadable")
+}
+
+/// Bring a value saved in `version` up to the current layout.
+pub fn migrate(key: &str, version: u32, value: &mut Value) {
+    for migration in MIGRATIONS
+        .iter()
+        .filter(|migration| migration.key == key && migration.from >= version)
+    {
+        log!(format!("Migrating {key} from version {}.", migration.from));
+        (migration.migrate)(value);
+    }
+}
+
+/// Load a key from local storage, migrating it if it was saved by an older version.
+///
+/// Returns `Ok(None)` if nothing is saved under the key.
+pub fn load<T: DeserializeOwned>(
+    storage: &dyn Storage,
+    key: &str,
+) -> Result<Option<T>, StorageProblem> {
+    let Some(mut value) = storage.get(StorageArea::Local, key) else {
+        return Ok(None);
+    };
+
+    let version = storage
+        .get(StorageArea::Local, &version_key(key))
+        .and_then(|version| version.as_u64())
+        .unwrap_or(0) as u32;
+
+    let problem = |reason: String| {
+        storage.set(
+            StorageArea::Local,
+            &unreadable_key(key),
+            storage.get(StorageArea::Local, key).unwrap_or_default(),
+        );
+        StorageProblem {
+            key: key.to_string(),
+            reason,
+        }
+    };
+
+    if version > SCHEMA_VERSION {
+        return Err(problem(format!(
+            "saved by a newer version of the app (schema {version})"
+        )));
+    }
+
+    migrate(key, version, &mut value);
+
+    match serde_json::from_value::<T>(value.clone()) {
+        Ok(data) => {
+            if version < SCHEMA_VERSION {
+                storage.set(StorageArea::Local, key, value);
+                storage.set(
+                    StorageArea::Local,
+                    &version_key(key),
+                    Value::from(SCHEMA_VERSION),
+                );
+            }
+            Ok(Some(data))
+        }
+        Err(err) => Err(problem(err.to_string())),
+    }
+}
+
+/// Save a key to local storage, along with the current schema version.
+pub fn save<T: Serialize>(storage: &dyn Storage, key: &str, data: &T) {
+    match serde_json::to_value(data) {
+        Ok(value) => {
+            storage.set(StorageArea::Local, key, value);
+            storage.set(
+                StorageArea::Local,
+                &version_key(key),
+                Value::from(SCHEMA_VERSION),
+            );
+        }
+        Err(_) => log!(format!("Could not serialize {key}.")),
+    }
+}
+
+/// Remove a key along with its version.
+pub fn delete(storage: &dyn Storage, key: &str) {
+    storage.delete(StorageArea::Local, key);
+    storage.delete(StorageArea::Local, &version_key(key));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::prelude::*;
+
+    #[test]
+    fn legacy_game_is_migrated_and_rewritten() {
+        let storage = MemoryStorage::new();
+        let mut game = serde_json::to_value(Game::new()).unwrap();
+        let fields = game.as_object_mut().unwrap();
+        fields.remove("winners");
+        fields.insert("winner_name".into(), "Vlad".into());
+        storage.set(StorageArea::Local, "game", game);
+
+        let loaded: Game = load(&storage, "game").unwrap().unwrap();
+        assert_eq!(loaded.winners, ["Vlad"]);
+        assert_eq!(
+            storage.get(StorageArea::Local, "game.version"),
+            Some(Value::from(SCHEMA_VERSION))
+        );
+        assert!(storage.get(StorageArea::Local, "game").unwrap()["winner_name"].is_null());
+    }
+
+    #[test]
+    fn bare_events_become_stamped_round_entries() {
+        let mut game = serde_json::json!({
+            "events": [
+                { "AddScore": { "player_id": 1, "value": 100 } },
+                { "AddScore": { "player_id": 2, "value": 40 } },
+                { "AddScore": { "player_id": 1, "value": 10 } },
+            ],
+            "undone_events": ["DoubleGameTotal"],
+        });
+        migrate("game", 1, &mut game);
+
+        let events = game["events"].as_array().unwrap();
+        assert_eq!(events[2]["stamp"]["clock"], 3);
+        assert_eq!(events[2]["event"]["AddScore"]["round"], 1);
+        assert_eq!(events[1]["event"]["AddScore"]["round"], 0);
+        assert_eq!(game["undone_events"][0]["stamp"]["clock"], 4);
+        assert_eq!(game["retracted"][0]["clock"], 4);
+    }
+
+    #[test]
+    fn bonuses_and_doubles_get_the_round_they_were_granted_in() {
+        let mut game = serde_json::json!({
+            "players": [{}, {}],
+            "events": [
+                { "stamp": { "clock": 1 }, "event": { "GrantBonus": { "player_id": 2, "kind": 0 } } },
+                { "stamp": { "clock": 2 }, "event": { "AddScore": { "player_id": 1, "round": 0, "value": 100 } } },
+                { "stamp": { "clock": 3 }, "event": { "AddScore": { "player_id": 2, "round": 0, "value": 40 } } },
+                { "stamp": { "clock": 4 }, "event": { "DoubleGameForPlayer": { "player_id": 1 } } },
+                { "stamp": { "clock": 5 }, "event": { "AddScore": { "player_id": 1, "round": 1, "value": 10 } } },
+            ],
+            "undone_events": [{ "stamp": { "clock": 6 }, "event": "DoubleGameTotal" }],
+        });
+        migrate("game", 3, &mut game);
+
+        let events = &game["events"];
+        assert_eq!(events[0]["event"]["GrantBonus"]["round"], 0);
+        assert_eq!(events[3]["event"]["DoubleGameForPlayer"]["round"], 1);
+        assert_eq!(
+            game["undone_events"][0]["event"]["DoubleGameTotal"]["round"],
+            1
+        );
+    }
+
+    #[test]
+    fn numbered_languages_become_codes() {
+        let storage = MemoryStorage::new();
+        let mut settings = serde_json::to_value(Settings::new()).unwrap();
+        settings["language"] = Value::from(2);
+        storage.set(StorageArea::Local, "settings", settings);
+        storage.set(StorageArea::Local, "settings.version", Value::from(2));
+
+        let loaded: Settings = load(&storage, "settings").unwrap().unwrap();
+        assert_eq!(loaded.language, Language::Romanian);
+        assert_eq!(
+            storage.get(StorageArea::Local, "settings").unwrap()["language"],
+            "ro"
+        );
+    }
+
+    #[test]
+    fn unreadable_data_is_kept_and_reported() {
+        let storage = MemoryStorage::new();
+        let broken = serde_json::json!({ "players": "nobody" });
+        storage.set(StorageArea::Local, "game", broken.clone());
+
+        let problem = load::<Game>(&storage, "game").err().unwrap();
+        assert_eq!(problem.key, "game");
+        assert_eq!(
+            storage.get(StorageArea::Local, "game.unreadable"),
+            Some(broken)
+        );
+    }
+
+    #[test]
+    fn data_from_a_newer_version_is_not_touched() {
+        let storage = MemoryStorage::new();
+        save(&storage, "roster", &Roster::new());
+        storage.set(
+            StorageArea::Local,
+            "roster.version",
+            Value::from(SCHEMA_VERSION + 1),
+        );
+
+        assert!(load::<Roster>(&storage, "roster").is_err());
+        assert!(storage
+            .get(StorageArea::Local, "roster.unreadable")
+            .is_some());
+    }
+}
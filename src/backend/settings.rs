@@ -1,5 +1,9 @@
-use gloo_console::log;
-use gloo_storage::{LocalStorage, Storage};
+use crate::backend::i18n::{self, Language};
+use crate::backend::rules::{TieRule, Variant};
+use crate::backend::schema::{self, StorageProblem};
+use crate::backend::storage::Storage;
+use crate::backend::{MIN_PLAYERS, PLAYER_COLORS};
+use crate::log;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Serialize, Deserialize, Debug, Copy)]
@@ -13,7 +17,35 @@ pub struct Settings {
     pub enable_score_editing: bool,
     pub enable_score_checking: bool,
     pub checked_storage: bool,
-    pub language: usize,
+    pub language: Language,
+    #[serde(default = "default_max_players")]
+    pub max_players: usize,
+    /// The variant picked for the last game, preselected for the next one.
+    #[serde(default)]
+    pub variant: Variant,
+    #[serde(default)]
+    pub tie_rule: TieRule,
+    #[serde(default)]
+    pub end_after_rounds: bool,
+    #[serde(default = "default_round_limit")]
+    pub round_limit: usize,
+    #[serde(default)]
+    pub end_at_time: bool,
+    /// Minutes past midnight, local time.
+    #[serde(default = "default_end_time")]
+    pub end_time: u16,
+}
+
+pub fn default_max_players() -> usize {
+    4
+}
+
+fn default_round_limit() -> usize {
+    10
+}
+
+fn default_end_time() -> u16 {
+    23 * 60
 }
 
 impl Settings {
@@ -29,42 +61,64 @@ impl Settings {
             enable_score_editing: true,
             enable_score_checking: true,
             checked_storage: false,
-            language: 1,
+            language: Language::default(),
+            max_players: default_max_players(),
+            variant: Variant::default(),
+            tie_rule: TieRule::default(),
+            end_after_rounds: false,
+            round_limit: default_round_limit(),
+            end_at_time: false,
+            end_time: default_end_time(),
         }
     }
 
-    pub fn load(&mut self) {
+    pub fn load(&mut self, storage: &dyn Storage) -> Result<(), StorageProblem> {
         log!("Trying to load settings from storage.");
-        match LocalStorage::get::<serde_json::Value>("settings") {
-            Ok(json_settings) => match serde_json::from_value::<Self>(json_settings) {
-                Ok(new_settings) => {
-                    *self = new_settings;
-                    log!(format!("Loaded settings: {self:?}"));
-                }
-                Err(_) => log!("Could not parse settings from local storage."),
-            },
-            Err(_) => log!("Could not read settings from local storage."),
-        }
         self.checked_storage = true;
+
+        if let Some(new_settings) = schema::load::<Self>(storage, "settings")? {
+            *self = new_settings;
+            self.checked_storage = true;
+            log!(format!("Loaded settings: {self:?}"));
+        } else {
+            self.language = i18n::preferred_language(&i18n::system_languages());
+            log!(format!("First launch - speaking {:?}.", self.language));
+        }
+        Ok(())
     }
 
-    pub fn save(&self) {
+    pub fn save(&self, storage: &dyn Storage) {
         log!("Saving settings.");
-
-        LocalStorage::set("settings", *self).unwrap();
+        schema::save(storage, "settings", self);
     }
 
     pub fn set_max_score(&mut self, value: i32) {
         log!("Set max score.");
         self.max_score = value;
-        self.save();
+        self.checked_storage = false;
+    }
+
+    pub fn set_max_players(&mut self, value: usize) {
+        log!("Set max players.");
+        self.max_players = value.clamp(MIN_PLAYERS, PLAYER_COLORS);
+        self.checked_storage = false;
+    }
+
+    pub fn set_round_limit(&mut self, value: usize) {
+        log!("Set round limit.");
+        self.round_limit = value.max(1);
+        self.checked_storage = false;
+    }
+
+    pub fn set_end_time(&mut self, minutes: u16) {
+        log!("Set end time.");
+        self.end_time = minutes % (24 * 60);
         self.checked_storage = false;
     }
 
     pub fn set_tile_bonus(&mut self, value: i32) {
         log!("Set tile bonus.");
         self.tile_bonus_value = value;
-        self.save();
         self.checked_storage = false;
     }
 }
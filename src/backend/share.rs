@@ -0,0 +1,621 @@
+//! Sharing a game as a link.
+//!
+//! A game is reduced to its players, rules and event log, compressed and
+//! written in URL-safe base64, to be put after `#game=` in the app's address.
+//! Opening that address loads the game straight away - the link can also be
+//! scanned from a QR code, see [`crate::backend::qr`].
+
+use crate::backend::events::LegacyRounds;
+use crate::backend::export::{validate_game, ImportError};
+use crate::backend::ledger::Entry;
+use crate::backend::prelude::*;
+use crate::backend::rules::{ScoringMode, TieRule, Variant};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Bumped whenever the shared layout changes in a way older versions can't read.
+pub const SHARE_VERSION: u32 = 3;
+
+/// What comes before the encoded game in a link.
+pub const LINK_PREFIX: &str = "#game=";
+
+/// The longest encoded game accepted - about what still fits in a QR code.
+pub const MAX_ENCODED_LENGTH: usize = 2048;
+
+/// The most a shared game may take up once decompressed.
+const MAX_DECODED_LENGTH: usize = 64 * 1024;
+
+/// A game reduced to what's needed to rebuild it, with short field names.
+#[derive(Serialize, Deserialize)]
+struct SharedGame {
+    #[serde(rename = "v")]
+    version: u32,
+    /// Name and color of each player, in seating order.
+    #[serde(rename = "p")]
+    players: Vec<(String, usize)>,
+    #[serde(rename = "r")]
+    variant: Variant,
+    #[serde(rename = "s")]
+    scoring: ScoringMode,
+    #[serde(rename = "t")]
+    tie_rule: TieRule,
+    #[serde(rename = "m")]
+    max_score: i32,
+    #[serde(rename = "b")]
+    tile_bonus_value: i32,
+    #[serde(rename = "e")]
+    end_game_at_score: bool,
+    #[serde(rename = "l", default)]
+    round_limit: Option<usize>,
+    #[serde(rename = "d", default)]
+    deadline: Option<i64>,
+    #[serde(rename = "x", default)]
+    events: Vec<Vec<i64>>,
+    #[serde(rename = "u", default)]
+    undone_events: Vec<Vec<i64>>,
+    #[serde(rename = "w", default)]
+    winners: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShareError {
+    TooLong,
+    /// Not a valid encoding, or missing required fields.
+    Malformed(String),
+    /// Shared from a newer version of the app.
+    UnsupportedVersion(u32),
+    InvalidGame(String),
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShareError::TooLong => write!(f, "the link is too long"),
+            ShareError::Malformed(reason) => write!(f, "the link could not be read ({reason})"),
+            ShareError::UnsupportedVersion(version) => {
+                write!(f, "shared game version {version} is not supported")
+            }
+            ShareError::InvalidGame(reason) => write!(f, "the game is invalid ({reason})"),
+        }
+    }
+}
+
+impl From<ImportError> for ShareError {
+    fn from(err: ImportError) -> Self {
+        match err {
+            ImportError::InvalidGame(reason) => ShareError::InvalidGame(reason),
+            other => ShareError::Malformed(other.to_string()),
+        }
+    }
+}
+
+/// The game as a compact, URL-safe string.
+pub fn encode_game(game: &Game) -> String {
+    let shared = SharedGame {
+        version: SHARE_VERSION,
+        players: game
+            .players
+            .iter()
+            .map(|player| (player.name.clone(), player.color_index))
+            .collect(),
+        variant: game.variant,
+        scoring: game.scoring,
+        tie_rule: game.tie_rule,
+        max_score: game.max_score,
+        tile_bonus_value: game.tile_bonus_value,
+        end_game_at_score: game.end_game_at_score,
+        round_limit: game.round_limit,
+        deadline: game.deadline,
+        events: game
+            .events
+            .iter()
+            .map(|entry| event_to_codes(&entry.event))
+            .collect(),
+        undone_events: game
+            .undone_events
+            .iter()
+            .map(|entry| event_to_codes(&entry.event))
+            .collect(),
+        winners: game.winners.clone(),
+    };
+
+    let json = serde_json::to_vec(&shared).unwrap_or_default();
+    base64_encode(&compress(&json))
+}
+
+/// Rebuild a game from [`encode_game`]'s output, refusing anything that doesn't check out.
+pub fn decode_game(text: &str) -> Result<Game, ShareError> {
+    let text = text.trim();
+    if text.len() > MAX_ENCODED_LENGTH {
+        return Err(ShareError::TooLong);
+    }
+
+    let malformed = |reason: &str| ShareError::Malformed(String::from(reason));
+    let compressed = base64_decode(text).ok_or_else(|| malformed("not base64"))?;
+    let json = decompress(&compressed, MAX_DECODED_LENGTH)
+        .ok_or_else(|| malformed("not compressed data"))?;
+
+    let value: serde_json::Value =
+        serde_json::from_slice(&json).map_err(|err| ShareError::Malformed(err.to_string()))?;
+    let version = value
+        .get("v")
+        .and_then(|version| version.as_u64())
+        .unwrap_or_default() as u32;
+    if version == 0 || version > SHARE_VERSION {
+        return Err(ShareError::UnsupportedVersion(version));
+    }
+    let shared: SharedGame =
+        serde_json::from_value(value).map_err(|err| ShareError::Malformed(err.to_string()))?;
+
+    let invalid = |reason: &str| ShareError::InvalidGame(String::from(reason));
+    if shared.players.len() < MIN_PLAYERS || shared.players.len() > PLAYER_COLORS {
+        return Err(invalid("wrong number of players"));
+    }
+    if shared.players.iter().any(|(name, _)| name.is_empty()) {
+        return Err(invalid("a player has no name"));
+    }
+    if shared
+        .winners
+        .iter()
+        .any(|winner| !shared.players.iter().any(|(name, _)| name == winner))
+    {
+        return Err(invalid("a winner is not playing"));
+    }
+
+    // Links don't carry stamps, so the actions are stamped again in order, as if
+    // recorded on this device. Before version 2, scores went to the player's next round,
+    // and before version 3 bonuses and doubles went to the round in progress.
+    let mut rounds: HashMap<usize, usize> = HashMap::new();
+    let mut legacy_rounds = LegacyRounds::new(shared.players.len());
+    let mut clock = 0;
+    let mut decode_events = |events: &[Vec<i64>]| {
+        events
+            .iter()
+            .map(|codes| {
+                let mut event = codes_to_event(codes).ok_or_else(|| invalid("unknown action"))?;
+                match &mut event {
+                    GameEvent::AddScore {
+                        player_id, round, ..
+                    } => {
+                        let next = rounds.entry(*player_id).or_default();
+                        if shared.version < 2 {
+                            *round = *next;
+                        }
+                        *next = *round + 1;
+                        legacy_rounds.scored(*player_id, *round);
+                    }
+                    GameEvent::EditScore {
+                        player_id,
+                        score_id,
+                        ..
+                    } => legacy_rounds.scored(*player_id, *score_id - 1),
+                    GameEvent::GrantBonus { round, .. }
+                    | GameEvent::DoubleGameForPlayer { round, .. }
+                    | GameEvent::DoubleGameTotal { round } => {
+                        if shared.version < 3 {
+                            *round = legacy_rounds.current();
+                        }
+                    }
+                }
+                clock += 1;
+                Ok(Entry::new(clock, event))
+            })
+            .collect::<Result<Vec<_>, ShareError>>()
+    };
+    let events = decode_events(&shared.events)?;
+    let undone_events = decode_events(&shared.undone_events)?;
+
+    let mut game = Game::new();
+    game.max_players = PLAYER_COLORS;
+    for (name, color) in shared.players {
+        game.add_player(name, color);
+    }
+    game.status = GameStatus::Ongoing;
+    game.variant = shared.variant;
+    game.scoring = shared.scoring;
+    game.tie_rule = shared.tie_rule;
+    game.max_score = shared.max_score;
+    game.tile_bonus_value = shared.tile_bonus_value;
+    game.end_game_at_score = shared.end_game_at_score;
+    game.round_limit = shared.round_limit;
+    game.deadline = shared.deadline;
+    game.retracted = undone_events
+        .iter()
+        .map(|entry| entry.stamp.clone())
+        .collect();
+    game.events = events;
+    game.undone_events = undone_events;
+    validate_game(&game)?;
+
+    game.rebuild();
+    if !shared.winners.is_empty() {
+        game.winner_identities = game
+            .players
+            .iter()
+            .filter(|player| shared.winners.contains(&player.name))
+            .map(Player::identity)
+            .collect();
+        game.winners = shared.winners;
+        game.status = GameStatus::Finished;
+    }
+
+    Ok(game)
+}
+
+/// The encoded game, if the link has one.
+pub fn payload_from_link(link: &str) -> Option<&str> {
+    link.find(LINK_PREFIX)
+        .map(|start| &link[start + LINK_PREFIX.len()..])
+        .filter(|payload| !payload.is_empty())
+}
+
+/// The encoded game the app was opened with, if any, removing it from the address
+/// so a reload doesn't open it again.
+pub fn take_opened_link() -> Option<String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let window = gloo_utils::window();
+        let hash = window.location().hash().ok()?;
+        let payload = payload_from_link(&hash)?.to_string();
+
+        let path = window.location().pathname().unwrap_or_default();
+        let _ = window.history().and_then(|history| {
+            history.replace_state_with_url(&Default::default(), "", Some(&path))
+        });
+
+        Some(payload)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        None
+    }
+}
+
+/// The address of the app itself, without any link in it.
+pub fn app_address() -> String {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let location = gloo_utils::window().location();
+        format!(
+            "{}{}",
+            location.origin().unwrap_or_default(),
+            location.pathname().unwrap_or_default()
+        )
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        String::from("https://rummy-nights.vercel.app/")
+    }
+}
+
+/// A link that opens the app straight into the game.
+pub fn share_link(game: &Game) -> String {
+    format!("{}{LINK_PREFIX}{}", app_address(), encode_game(game))
+}
+
+/// Events as short arrays of numbers, the first one telling which action it is.
+fn event_to_codes(event: &GameEvent) -> Vec<i64> {
+    match *event {
+        GameEvent::AddScore {
+            player_id,
+            round,
+            value,
+        } => vec![0, player_id as i64, value as i64, round as i64],
+        GameEvent::EditScore {
+            player_id,
+            score_id,
+            value,
+        } => vec![1, player_id as i64, score_id as i64, value as i64],
+        GameEvent::GrantBonus {
+            player_id,
+            round,
+            kind,
+        } => vec![2, player_id as i64, kind as i64, round as i64],
+        GameEvent::DoubleGameForPlayer { player_id, round } => {
+            vec![3, player_id as i64, round as i64]
+        }
+        GameEvent::DoubleGameTotal { round } => vec![4, round as i64],
+    }
+}
+
+fn codes_to_event(codes: &[i64]) -> Option<GameEvent> {
+    let id = |index: usize| {
+        codes
+            .get(index)
+            .and_then(|code| usize::try_from(*code).ok())
+    };
+    let value = |index: usize| codes.get(index).and_then(|code| i32::try_from(*code).ok());
+
+    let event = match codes.first()? {
+        // Links shared before version 2 have no round, see `decode_game`.
+        0 if codes.len() == 3 || codes.len() == 4 => GameEvent::AddScore {
+            player_id: id(1)?,
+            round: if codes.len() == 4 { id(3)? } else { 0 },
+            value: value(2)?,
+        },
+        1 if codes.len() == 4 => GameEvent::EditScore {
+            player_id: id(1)?,
+            score_id: id(2).filter(|score_id| *score_id > 0)?,
+            value: value(3)?,
+        },
+        // Links shared before version 3 have no round for bonuses and doubles either.
+        2 if codes.len() == 3 || codes.len() == 4 => GameEvent::GrantBonus {
+            player_id: id(1)?,
+            round: if codes.len() == 4 { id(3)? } else { 0 },
+            kind: id(2)?,
+        },
+        3 if codes.len() == 2 || codes.len() == 3 => GameEvent::DoubleGameForPlayer {
+            player_id: id(1)?,
+            round: if codes.len() == 3 { id(2)? } else { 0 },
+        },
+        4 if codes.len() == 1 || codes.len() == 2 => GameEvent::DoubleGameTotal {
+            round: if codes.len() == 2 { id(1)? } else { 0 },
+        },
+        _ => return None,
+    };
+    Some(event)
+}
+
+const WINDOW_SIZE: usize = 4096;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = MIN_MATCH + 15;
+
+/// LZSS: every 8 items are preceded by a flag byte, a set bit meaning the item is
+/// a 12-bit distance and 4-bit length pointing back into the output, otherwise a plain byte.
+fn compress(input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(input.len());
+    let mut flags_at = 0;
+    let mut items = 0;
+    let mut position = 0;
+
+    while position < input.len() {
+        if items % 8 == 0 {
+            flags_at = output.len();
+            output.push(0);
+        }
+
+        let start = position.saturating_sub(WINDOW_SIZE);
+        let longest = (MAX_MATCH).min(input.len() - position);
+        let (distance, length) = (start..position)
+            .map(|candidate| {
+                let length = (0..longest)
+                    .take_while(|i| input[candidate + i] == input[position + i])
+                    .count();
+                (position - candidate, length)
+            })
+            .max_by_key(|(distance, length)| (*length, usize::MAX - distance))
+            .unwrap_or((0, 0));
+
+        if length >= MIN_MATCH {
+            output[flags_at] |= 1 << (items % 8);
+            let code = ((distance - 1) << 4) | (length - MIN_MATCH);
+            output.extend([(code >> 8) as u8, code as u8]);
+            position += length;
+        } else {
+            output.push(input[position]);
+            position += 1;
+        }
+        items += 1;
+    }
+
+    output
+}
+
+/// Undo [`compress`], giving up on data that doesn't decode or grows past `limit`.
+fn decompress(input: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let mut output: Vec<u8> = Vec::new();
+    let mut bytes = input.iter();
+
+    while let Some(flags) = bytes.next() {
+        for bit in 0..8 {
+            if flags & (1 << bit) == 0 {
+                match bytes.next() {
+                    Some(byte) => output.push(*byte),
+                    None => return Some(output),
+                }
+            } else {
+                let code = (*bytes.next()? as usize) << 8 | *bytes.next()? as usize;
+                let distance = (code >> 4) + 1;
+                let length = (code & 0xF) + MIN_MATCH;
+                let start = output.len().checked_sub(distance)?;
+                for i in 0..length {
+                    output.push(output[start + i]);
+                }
+            }
+
+            if output.len() > limit {
+                return None;
+            }
+        }
+    }
+
+    Some(output)
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// URL-safe base64, without padding.
+fn base64_encode(data: &[u8]) -> String {
+    let mut text = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, byte)| acc | (*byte as u32) << (16 - i * 8));
+        for i in 0..=chunk.len() {
+            text.push(BASE64_ALPHABET[(bits >> (18 - i * 6)) as usize & 0x3F] as char);
+        }
+    }
+
+    text
+}
+
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    if text.len() % 4 == 1 {
+        return None;
+    }
+    let mut data = Vec::with_capacity(text.len() * 3 / 4);
+
+    for chunk in text.as_bytes().chunks(4) {
+        let mut bits = 0u32;
+        for (i, character) in chunk.iter().enumerate() {
+            let value = BASE64_ALPHABET
+                .iter()
+                .position(|letter| letter == character)?;
+            bits |= (value as u32) << (18 - i * 6);
+        }
+        for i in 0..chunk.len() - 1 {
+            data.push((bits >> (16 - i * 8)) as u8);
+        }
+    }
+
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn played_game() -> Game {
+        let mut game = Game::new();
+        game.add_player(String::from("Antonio"), 0);
+        game.add_player(String::from("Vlad"), 3);
+        game.start_game();
+        game.grant_bonus(1, 0);
+        game.add_score(1, 100);
+        game.add_score(2, 40);
+        game.double_game_for_player(2);
+        game.add_score(1, 10);
+        game.add_score(2, 5);
+        game.edit_score(2, 1, 45);
+        game.undo();
+        game
+    }
+
+    #[test]
+    fn game_round_trips_through_a_link() {
+        let game = played_game();
+        let link = share_link(&game);
+        let decoded = decode_game(payload_from_link(&link).unwrap()).unwrap();
+
+        assert_eq!(decoded.events, game.events);
+        assert_eq!(decoded.undone_events, game.undone_events);
+        assert_eq!(decoded.round, game.round);
+        for (decoded, player) in decoded.players.iter().zip(&game.players) {
+            assert_eq!(decoded.name, player.name);
+            assert_eq!(decoded.color_index, player.color_index);
+            assert_eq!(decoded.sum, player.sum);
+        }
+    }
+
+    #[test]
+    fn finished_games_keep_their_winners() {
+        let mut game = played_game();
+        game.winners = vec![String::from("Vlad")];
+        game.status = GameStatus::Finished;
+
+        let decoded = decode_game(&encode_game(&game)).unwrap();
+        assert_eq!(decoded.status, GameStatus::Finished);
+        assert_eq!(decoded.get_winners(), ["Vlad"]);
+        assert!(decoded.is_winner(&decoded.players[1]));
+        assert!(!decoded.is_winner(&decoded.players[0]));
+    }
+
+    #[test]
+    fn compression_round_trips() {
+        let text = b"AddScore AddScore AddScore, and then some plain text: abcabcabcabcabc!";
+        let compressed = compress(text);
+
+        assert!(compressed.len() < text.len());
+        assert_eq!(decompress(&compressed, 1024).unwrap(), text);
+        assert!(decompress(&compressed, 10).is_none());
+    }
+
+    #[test]
+    fn base64_round_trips_every_length() {
+        for length in 0..8 {
+            let data: Vec<u8> = (0..length).map(|i| (i * 97 + 200) as u8).collect();
+            let text = base64_encode(&data);
+
+            assert!(!text.contains(['+', '/', '=']));
+            assert_eq!(base64_decode(&text).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        assert!(matches!(
+            decode_game("not a game!"),
+            Err(ShareError::Malformed(_))
+        ));
+        assert!(matches!(
+            decode_game(&base64_encode(&compress(b"{\"v\": 1}"))),
+            Err(ShareError::Malformed(_))
+        ));
+        // A back reference to before the start of the data.
+        assert!(matches!(
+            decode_game(&base64_encode(&[1, 0xFF, 0xF0])),
+            Err(ShareError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_long_and_newer_links() {
+        assert_eq!(
+            decode_game(&"A".repeat(MAX_ENCODED_LENGTH + 1)).err(),
+            Some(ShareError::TooLong)
+        );
+
+        let newer = format!("{{\"v\": {}}}", SHARE_VERSION + 1);
+        assert_eq!(
+            decode_game(&base64_encode(&compress(newer.as_bytes()))).err(),
+            Some(ShareError::UnsupportedVersion(SHARE_VERSION + 1))
+        );
+    }
+
+    #[test]
+    fn actions_in_older_links_get_their_rounds() {
+        let mut game = played_game();
+        game.events.truncate(5);
+        game.undone_events.clear();
+
+        let json = decompress(
+            &base64_decode(&encode_game(&game)).unwrap(),
+            MAX_DECODED_LENGTH,
+        )
+        .unwrap();
+        let mut shared: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        shared["v"] = 1.into();
+        for codes in shared["x"].as_array_mut().unwrap() {
+            let length = match codes[0].as_u64().unwrap() {
+                0 | 2 => 3,
+                3 => 2,
+                _ => 1,
+            };
+            codes.as_array_mut().unwrap().truncate(length);
+        }
+
+        let link = base64_encode(&compress(shared.to_string().as_bytes()));
+        assert_eq!(decode_game(&link).unwrap().events, game.events);
+    }
+
+    #[test]
+    fn rejects_actions_for_missing_players() {
+        let mut game = played_game();
+        game.events.push(Entry::new(
+            99,
+            GameEvent::DoubleGameForPlayer {
+                player_id: 5,
+                round: 0,
+            },
+        ));
+
+        assert!(matches!(
+            decode_game(&encode_game(&game)),
+            Err(ShareError::InvalidGame(_))
+        ));
+    }
+}
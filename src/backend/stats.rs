@@ -0,0 +1,226 @@
+//! Lifetime statistics per player, computed from finished games.
+
+use crate::backend::prelude::*;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PlayerStats {
+    /// See [`Player::identity`].
+    pub identity: String,
+    /// The name the player used most recently.
+    pub name: String,
+    pub color_index: usize,
+    pub games_played: usize,
+    pub wins: usize,
+    pub rounds_played: usize,
+    pub total_round_points: i32,
+    pub best_round: Option<i32>,
+    pub worst_round: Option<i32>,
+    pub tile_bonuses: usize,
+    pub times_doubled: usize,
+}
+
+impl PlayerStats {
+    fn new(player: &Player) -> Self {
+        Self {
+            identity: player.identity(),
+            name: player.name.clone(),
+            color_index: player.color_index,
+            games_played: 0,
+            wins: 0,
+            rounds_played: 0,
+            total_round_points: 0,
+            best_round: None,
+            worst_round: None,
+            tile_bonuses: 0,
+            times_doubled: 0,
+        }
+    }
+
+    /// Wins out of games played, from 0 to 100.
+    pub fn win_rate(&self) -> f32 {
+        if self.games_played == 0 {
+            0.0
+        } else {
+            self.wins as f32 * 100.0 / self.games_played as f32
+        }
+    }
+
+    pub fn average_round(&self) -> f32 {
+        if self.rounds_played == 0 {
+            0.0
+        } else {
+            self.total_round_points as f32 / self.rounds_played as f32
+        }
+    }
+
+    fn add_game(&mut self, game: &Game, player: &Player) {
+        self.name = player.name.clone();
+        self.color_index = player.color_index;
+        self.games_played += 1;
+
+        if game.status == GameStatus::Finished && game.is_winner(player) {
+            self.wins += 1;
+        }
+
+        for round in player.score.keys() {
+            let points = player.round_points(*round);
+
+            self.rounds_played += 1;
+            self.total_round_points += points;
+            self.best_round = Some(self.best_round.map_or(points, |best| best.max(points)));
+            self.worst_round = Some(self.worst_round.map_or(points, |worst| worst.min(points)));
+        }
+
+        self.tile_bonuses += player.bonus.len();
+        self.times_doubled += player.list_of_doubled_games.len();
+    }
+}
+
+/// Statistics for everyone who played in the given games, most wins first.
+pub fn compute_stats<'a>(games: impl IntoIterator<Item = &'a Game>) -> Vec<PlayerStats> {
+    let mut stats: Vec<PlayerStats> = Vec::new();
+
+    for game in games {
+        for player in &game.players {
+            let identity = player.identity();
+
+            let index = match stats.iter().position(|entry| entry.identity == identity) {
+                Some(index) => index,
+                None => {
+                    stats.push(PlayerStats::new(player));
+                    stats.len() - 1
+                }
+            };
+
+            stats[index].add_game(game, player);
+        }
+    }
+
+    stats.sort_by(|a, b| {
+        b.wins
+            .cmp(&a.wins)
+            .then(b.games_played.cmp(&a.games_played))
+            .then(a.name.cmp(&b.name))
+    });
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finished_game(players: &[(&str, Option<&str>)], rounds: &[&[i32]]) -> Game {
+        let mut game = Game::new();
+        for (index, (name, uid)) in players.iter().enumerate() {
+            game.add_player(name.to_string(), index);
+            game.players[index].uid = uid.map(String::from);
+        }
+        game.max_score = 10_000;
+        game.start_game();
+
+        for scores in rounds {
+            for (index, score) in scores.iter().enumerate() {
+                game.add_score(index + 1, *score);
+            }
+        }
+
+        game.status = GameStatus::Finished;
+        let best = game.players.iter().max_by_key(|player| player.sum).unwrap();
+        game.winners = vec![best.name.clone()];
+        game.winner_identities = vec![best.identity()];
+        game
+    }
+
+    #[test]
+    fn counts_games_wins_and_rounds() {
+        let first = finished_game(
+            &[("Antonio", None), ("Vlad", None)],
+            &[&[100, 20], &[50, 60]],
+        );
+        let second = finished_game(&[("Vlad", None), ("Antonio", None)], &[&[200, 10]]);
+
+        let stats = compute_stats([&first, &second]);
+        assert_eq!(stats.len(), 2);
+
+        let antonio = stats.iter().find(|entry| entry.name == "Antonio").unwrap();
+        assert_eq!(antonio.games_played, 2);
+        assert_eq!(antonio.wins, 1);
+        assert_eq!(antonio.win_rate(), 50.0);
+        assert_eq!(antonio.rounds_played, 3);
+        assert_eq!(antonio.best_round, Some(100));
+        assert_eq!(antonio.worst_round, Some(10));
+        assert!((antonio.average_round() - 160.0 / 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn bonuses_and_doubles_count_towards_rounds() {
+        let mut game = finished_game(&[("Antonio", None), ("Vlad", None)], &[]);
+        game.status = GameStatus::Ongoing;
+        game.grant_bonus(1, 0);
+        game.double_game_for_player(1);
+        game.add_score(1, 100);
+        game.add_score(2, 40);
+        game.status = GameStatus::Finished;
+
+        let stats = compute_stats([&game]);
+        let antonio = stats.iter().find(|entry| entry.name == "Antonio").unwrap();
+
+        assert_eq!(antonio.best_round, Some(300));
+        assert_eq!(antonio.tile_bonuses, 1);
+        assert_eq!(antonio.times_doubled, 1);
+    }
+
+    #[test]
+    fn template_players_are_matched_by_uid() {
+        let first = finished_game(&[("Vlad", Some("uid-vlad")), ("Ana", None)], &[&[10, 5]]);
+        let second = finished_game(&[("Vlăduț", Some("uid-vlad")), ("ana ", None)], &[&[10, 5]]);
+        let third = finished_game(&[("Vlad", None), ("Ana", None)], &[&[10, 5]]);
+
+        let stats = compute_stats([&first, &second, &third]);
+        assert_eq!(stats.len(), 3);
+
+        let vlad = stats
+            .iter()
+            .find(|entry| entry.identity == "uid-vlad")
+            .unwrap();
+        assert_eq!(vlad.games_played, 2);
+        assert_eq!(vlad.name, "Vlăduț");
+
+        let ana = stats.iter().find(|entry| entry.identity == "ana").unwrap();
+        assert_eq!(ana.games_played, 3);
+    }
+
+    #[test]
+    fn wins_are_matched_by_identity() {
+        let first = finished_game(
+            &[("Ana", Some("uid-ana-1")), ("Ana", Some("uid-ana-2"))],
+            &[&[100, 20]],
+        );
+        let second = finished_game(
+            &[("Ana M.", Some("uid-ana-1")), ("Ana", Some("uid-ana-2"))],
+            &[&[100, 20]],
+        );
+
+        let stats = compute_stats([&first, &second]);
+        let winner = stats
+            .iter()
+            .find(|entry| entry.identity == "uid-ana-1")
+            .unwrap();
+        let other = stats
+            .iter()
+            .find(|entry| entry.identity == "uid-ana-2")
+            .unwrap();
+        assert_eq!(winner.wins, 2);
+        assert_eq!(other.wins, 0);
+    }
+
+    #[test]
+    fn older_games_fall_back_to_winner_names() {
+        let mut game = finished_game(&[("Antonio", None), ("Vlad", None)], &[&[100, 20]]);
+        game.winner_identities.clear();
+
+        let stats = compute_stats([&game]);
+        let antonio = stats.iter().find(|entry| entry.name == "Antonio").unwrap();
+        assert_eq!(antonio.wins, 1);
+    }
+}
@@ -0,0 +1,243 @@
+//! Persistence for the app state, kept behind a trait so the game rules
+//! don't depend on the browser.
+
+use crate::log;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+#[cfg(feature = "app")]
+use std::rc::Rc;
+
+/// Which storage area a key lives in.
+///
+/// `Local` survives reloads and restarts, `Session` only lives as long as the tab.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum StorageArea {
+    Local,
+    Session,
+}
+
+/// A key-value store for JSON values.
+pub trait Storage {
+    fn get(&self, area: StorageArea, key: &str) -> Option<Value>;
+    fn set(&self, area: StorageArea, key: &str, value: Value);
+    fn delete(&self, area: StorageArea, key: &str);
+    fn clear(&self, area: StorageArea);
+}
+
+/// Storage backed by the browser's `localStorage` and `sessionStorage`.
+#[cfg(feature = "web")]
+#[derive(Clone, Copy, Default)]
+pub struct BrowserStorage;
+
+#[cfg(feature = "web")]
+impl Storage for BrowserStorage {
+    fn get(&self, area: StorageArea, key: &str) -> Option<Value> {
+        use gloo_storage::{LocalStorage, SessionStorage, Storage};
+
+        match area {
+            StorageArea::Local => LocalStorage::get::<Value>(key).ok(),
+            StorageArea::Session => SessionStorage::get::<Value>(key).ok(),
+        }
+    }
+
+    fn set(&self, area: StorageArea, key: &str, value: Value) {
+        use gloo_storage::{LocalStorage, SessionStorage, Storage};
+
+        let result = match area {
+            StorageArea::Local => LocalStorage::set(key, value),
+            StorageArea::Session => SessionStorage::set(key, value),
+        };
+
+        if result.is_err() {
+            log!(format!("Could not write {key} to storage."));
+        }
+    }
+
+    fn delete(&self, area: StorageArea, key: &str) {
+        use gloo_storage::{LocalStorage, SessionStorage, Storage};
+
+        match area {
+            StorageArea::Local => LocalStorage::delete(key),
+            StorageArea::Session => SessionStorage::delete(key),
+        }
+    }
+
+    fn clear(&self, area: StorageArea) {
+        use gloo_storage::{LocalStorage, SessionStorage, Storage};
+
+        match area {
+            StorageArea::Local => LocalStorage::clear(),
+            StorageArea::Session => SessionStorage::clear(),
+        }
+    }
+}
+
+/// Storage that only lives in memory - used for tests and anywhere
+/// there is no browser.
+#[derive(Default)]
+pub struct MemoryStorage {
+    values: RefCell<HashMap<(StorageArea, String), Value>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, area: StorageArea, key: &str) -> Option<Value> {
+        self.values.borrow().get(&(area, key.to_string())).cloned()
+    }
+
+    fn set(&self, area: StorageArea, key: &str, value: Value) {
+        self.values
+            .borrow_mut()
+            .insert((area, key.to_string()), value);
+    }
+
+    fn delete(&self, area: StorageArea, key: &str) {
+        self.values.borrow_mut().remove(&(area, key.to_string()));
+    }
+
+    fn clear(&self, area: StorageArea) {
+        self.values
+            .borrow_mut()
+            .retain(|(stored_area, _), _| *stored_area != area);
+    }
+}
+
+/// Storage kept as one JSON file per key in a directory - used by the desktop app.
+///
+/// Session values only live in memory, as long as the app is open, like a browser tab's.
+pub struct FileStorage {
+    dir: PathBuf,
+    session: MemoryStorage,
+}
+
+impl FileStorage {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            session: MemoryStorage::new(),
+        }
+    }
+
+    /// The app's folder in the user's data directory, e.g. `~/.local/share/rummy-nights` on Linux.
+    #[cfg(feature = "desktop")]
+    pub fn in_data_dir() -> Self {
+        let data_dir = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
+        Self::new(data_dir.join("rummy-nights"))
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let name: String = key
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+impl Storage for FileStorage {
+    fn get(&self, area: StorageArea, key: &str) -> Option<Value> {
+        match area {
+            StorageArea::Local => {
+                let text = fs::read_to_string(self.path(key)).ok()?;
+                serde_json::from_str(&text).ok()
+            }
+            StorageArea::Session => self.session.get(area, key),
+        }
+    }
+
+    fn set(&self, area: StorageArea, key: &str, value: Value) {
+        if area == StorageArea::Session {
+            return self.session.set(area, key, value);
+        }
+
+        // Write next to the file first, so a crash can't leave half of it behind.
+        let path = self.path(key);
+        let temporary = path.with_extension("json.tmp");
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|_| fs::write(&temporary, value.to_string()))
+            .and_then(|_| fs::rename(&temporary, &path));
+
+        if result.is_err() {
+            log!(format!("Could not write {key} to storage."));
+        }
+    }
+
+    fn delete(&self, area: StorageArea, key: &str) {
+        match area {
+            StorageArea::Local => {
+                let _ = fs::remove_file(self.path(key));
+            }
+            StorageArea::Session => self.session.delete(area, key),
+        }
+    }
+
+    fn clear(&self, area: StorageArea) {
+        match area {
+            StorageArea::Local => {
+                let Ok(entries) = fs::read_dir(&self.dir) else {
+                    return;
+                };
+                for entry in entries.flatten() {
+                    if entry.path().extension().is_some_and(|ext| ext == "json") {
+                        let _ = fs::remove_file(entry.path());
+                    }
+                }
+            }
+            StorageArea::Session => self.session.clear(area),
+        }
+    }
+}
+
+/// Where the app keeps its data: the browser's storage on the web, files on the desktop.
+#[cfg(feature = "web")]
+pub fn default_storage() -> Rc<dyn Storage> {
+    Rc::new(BrowserStorage)
+}
+
+#[cfg(all(feature = "desktop", not(feature = "web")))]
+pub fn default_storage() -> Rc<dyn Storage> {
+    Rc::new(FileStorage::in_data_dir())
+}
+
+#[cfg(all(feature = "app", not(any(feature = "web", feature = "desktop"))))]
+pub fn default_storage() -> Rc<dyn Storage> {
+    Rc::new(MemoryStorage::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_survive_a_restart_and_sessions_do_not() {
+        let dir = std::env::temp_dir().join(format!("rummy-nights-storage-{}", std::process::id()));
+        let storage = FileStorage::new(&dir);
+        storage.set(StorageArea::Local, "game.version", Value::from(2));
+        storage.set(StorageArea::Session, "session", Value::Bool(true));
+
+        let restarted = FileStorage::new(&dir);
+        assert_eq!(
+            restarted.get(StorageArea::Local, "game.version"),
+            Some(Value::from(2))
+        );
+        assert_eq!(restarted.get(StorageArea::Session, "session"), None);
+
+        restarted.clear(StorageArea::Local);
+        assert_eq!(storage.get(StorageArea::Local, "game.version"), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
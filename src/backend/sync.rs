@@ -0,0 +1,182 @@
+//! Live score sync between the devices at a table.
+//!
+//! One device hosts the game and the others join it through a relay server
+//! (see `src/bin/relay.rs`), which passes every message on to the other
+//! devices in the same session. Guests start from the host's game, and from
+//! then on every device records its own changes and sends its whole game to
+//! the others after each one. Games are merged entry by entry (see
+//! [`Game::merge`]), so scores entered on several devices at once, or while
+//! one of them was offline, all end up in the same game everywhere.
+
+use crate::backend::export::{validate_game, ImportError};
+use crate::backend::ledger::device_id;
+use crate::backend::prelude::*;
+use crate::backend::uid::new_uid;
+use serde::{Deserialize, Serialize};
+
+/// Where the relay listens when run locally with its default settings.
+pub const DEFAULT_RELAY: &str = "ws://localhost:8787";
+
+/// How many characters a session code has.
+pub const SESSION_CODE_LENGTH: usize = 6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncRole {
+    /// Started the session with its game, and sends it to devices that join.
+    Host,
+    /// Replaces its game with the host's when it joins.
+    Guest,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyncSession {
+    pub role: SyncRole,
+    /// Shared by every device in the session, and typed in by the guests.
+    pub code: String,
+    /// The address of the relay server, e.g. `ws://192.168.1.10:8787`.
+    pub relay: String,
+    /// Tells this device's messages apart from the others' - the author of its entries.
+    pub device: String,
+    /// Whether the relay can be reached at the moment.
+    pub connected: bool,
+    /// For guests, whether the host's game has arrived yet.
+    pub received_game: bool,
+}
+
+impl SyncSession {
+    pub fn new(role: SyncRole, relay: &str, code: &str) -> Self {
+        Self {
+            role,
+            code: normalize_code(code),
+            relay: relay.trim().trim_end_matches('/').to_string(),
+            device: device_id(),
+            connected: false,
+            received_game: false,
+        }
+    }
+
+    /// The address the device connects to.
+    pub fn url(&self) -> String {
+        format!("{}/{}", self.relay, self.code)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum SyncMessage {
+    /// A guest asks for the game.
+    Join { device: String },
+    /// A device's game, sent after every change, to be merged with the others'.
+    Snapshot { device: String, game: Box<Game> },
+}
+
+impl SyncMessage {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Parse a message, checking that a game sent along is safe to show.
+    pub fn from_json(text: &str) -> Result<Self, ImportError> {
+        let message: Self =
+            serde_json::from_str(text).map_err(|err| ImportError::Malformed(err.to_string()))?;
+
+        if let SyncMessage::Snapshot { game, .. } = &message {
+            validate_game(game)?;
+        }
+        Ok(message)
+    }
+
+    /// The device that sent the message.
+    pub fn device(&self) -> &str {
+        match self {
+            SyncMessage::Join { device } | SyncMessage::Snapshot { device, .. } => device,
+        }
+    }
+}
+
+/// A short code for a new session, easy to read out across the table.
+pub fn new_session_code() -> String {
+    normalize_code(&new_uid().replace('-', "")[..SESSION_CODE_LENGTH])
+}
+
+/// Session codes are typed in by hand, so case and stray characters don't matter.
+pub fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|character| character.is_ascii_alphanumeric())
+        .map(|character| character.to_ascii_uppercase())
+        .collect()
+}
+
+/// JavaScript that connects to the relay and passes messages between it and the app.
+///
+/// It sends `{kind: "open" | "closed" | "message", text}` objects to the app, and
+/// sends on every string it receives - anything else closes the connection.
+pub fn socket_script(url: &str) -> String {
+    let url = serde_json::to_string(url).unwrap_or_default();
+    format!(
+        r#"const socket = new WebSocket({url});
+        const pending = [];
+        socket.onopen = () => {{
+            pending.splice(0).forEach((message) => socket.send(message));
+            dioxus.send({{ kind: "open" }});
+        }};
+        socket.onclose = () => dioxus.send({{ kind: "closed" }});
+        socket.onmessage = (event) => dioxus.send({{ kind: "message", text: event.data }});
+        while (true) {{
+            const message = await dioxus.recv();
+            if (typeof message !== "string") {{
+                socket.close();
+                break;
+            }}
+            if (socket.readyState === WebSocket.OPEN) {{
+                socket.send(message);
+            }} else {{
+                pending.push(message);
+            }}
+        }}"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ledger::Entry;
+
+    #[test]
+    fn codes_ignore_case_and_spaces() {
+        let code = new_session_code();
+        assert_eq!(code.len(), SESSION_CODE_LENGTH);
+        assert_eq!(normalize_code(&code.to_lowercase()), code);
+        assert_eq!(normalize_code(" ab-12 c "), "AB12C");
+    }
+
+    #[test]
+    fn session_url_joins_relay_and_code() {
+        let session = SyncSession::new(SyncRole::Guest, "ws://10.0.0.2:8787/ ", "x7k2qa");
+        assert_eq!(session.url(), "ws://10.0.0.2:8787/X7K2QA");
+    }
+
+    #[test]
+    fn snapshots_with_bad_games_are_refused() {
+        let mut game = Game::new();
+        game.add_player(String::from("Antonio"), 0);
+        game.add_player(String::from("Vlad"), 1);
+        game.start_game();
+        game.events.push(Entry::new(
+            99,
+            GameEvent::DoubleGameForPlayer {
+                player_id: 4,
+                round: 0,
+            },
+        ));
+        let message = SyncMessage::Snapshot {
+            device: new_uid(),
+            game: Box::new(game),
+        };
+
+        assert!(matches!(
+            SyncMessage::from_json(&message.to_json()),
+            Err(ImportError::InvalidGame(_))
+        ));
+        assert!(SyncMessage::from_json("{\"Unknown\": {}}").is_err());
+    }
+}
@@ -0,0 +1,51 @@
+//! Generation of UUID-like identifiers.
+//!
+//! These only need to be unique within one person's data, so they are mixed
+//! from the current time and a counter instead of a proper random source.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use time::OffsetDateTime;
+
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// SplitMix64, to spread the time and counter bits over the whole value.
+fn mix(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+/// A new identifier in the usual `xxxxxxxx-xxxx-4xxx-xxxx-xxxxxxxxxxxx` format.
+pub fn new_uid() -> String {
+    let now = OffsetDateTime::now_utc().unix_timestamp_nanos() as u64;
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+
+    let high = mix(now ^ count.rotate_left(32));
+    let low = mix(high ^ count);
+
+    format!(
+        "{:08x}-{:04x}-4{:03x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xFFFF,
+        high & 0x0FFF,
+        ((low >> 48) & 0x3FFF) | 0x8000,
+        low & 0xFFFF_FFFF_FFFF,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uids_are_uuid_shaped_and_unique() {
+        let first = new_uid();
+        let second = new_uid();
+
+        assert_ne!(first, second);
+        assert_eq!(first.len(), 36);
+        assert_eq!(first.split('-').count(), 5);
+        assert_eq!(&first[14..15], "4");
+    }
+}
@@ -0,0 +1,395 @@
+//! Keep score in the terminal, with the same rules as the web app.
+//!
+//! The game is kept in a JSON file in the app's export format, so it can be
+//! imported in the web app (Data > Import) and the web app's exports can be
+//! carried on with here.
+//!
+//! Build it without the web app with
+//! `cargo build --bin rummy-nights-cli --no-default-features`.
+
+use rummy_nights::backend::clock;
+use rummy_nights::backend::export::ExportDocument;
+use rummy_nights::backend::history::ArchivedGame;
+use rummy_nights::backend::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_FILE: &str = "rummy-nights.json";
+
+const USAGE: &str = "Usage: rummy-nights-cli [--file <path>] <command>
+
+Commands:
+  show                     Show the totals and who deals (the default)
+  add <name>...            Add players to a game that hasn't started yet
+  start                    Start the game
+  score <player> <points>  Enter a player's score for the round
+  bonus <player>           Give a player the tile bonus of the round
+  double <player>|all      Double the round for a player or for everyone
+  undo                     Take back the last action
+  redo                     Do the last undone action again
+  new                      Archive the finished game and start another with the same players
+
+Players are given by name or by seat number. The game is kept in
+rummy-nights.json unless another file is given.";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Show,
+    Add(Vec<String>),
+    Start,
+    Score(String, i32),
+    Bonus(String),
+    Double(Option<String>),
+    Undo,
+    Redo,
+    New,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        return;
+    }
+
+    let (path, command) = match parse(&args) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    let result = load(&path).and_then(|(mut game, mut history)| {
+        run(&command, &mut game, &mut history)?;
+        save(&path, &game, &history)?;
+        Ok(game)
+    });
+
+    match result {
+        Ok(game) => print!("{}", summary(&game)),
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn parse(args: &[String]) -> Result<(PathBuf, Command), String> {
+    let mut path = PathBuf::from(DEFAULT_FILE);
+    let mut words = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-f" | "--file" => {
+                path = PathBuf::from(args.next().ok_or("--file needs a path")?);
+            }
+            _ => words.push(arg.as_str()),
+        }
+    }
+
+    let command = match words.as_slice() {
+        [] | ["show"] => Command::Show,
+        ["add", names @ ..] if !names.is_empty() => {
+            Command::Add(names.iter().map(|name| name.to_string()).collect())
+        }
+        ["start"] => Command::Start,
+        ["score", player, points] => Command::Score(
+            player.to_string(),
+            points
+                .parse()
+                .map_err(|_| format!("{points} is not a score"))?,
+        ),
+        ["bonus", player] => Command::Bonus(player.to_string()),
+        ["double", "all"] => Command::Double(None),
+        ["double", player] => Command::Double(Some(player.to_string())),
+        ["undo"] => Command::Undo,
+        ["redo"] => Command::Redo,
+        ["new"] => Command::New,
+        _ => return Err(format!("Unknown command: {}", words.join(" "))),
+    };
+
+    Ok((path, command))
+}
+
+/// The game and the finished games in the file, or a new game if there is no file yet.
+fn load(path: &Path) -> Result<(Game, Vec<ArchivedGame>), String> {
+    if !path.exists() {
+        return Ok((Game::new(), Vec::new()));
+    }
+
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+    let document = ExportDocument::from_json(&text)
+        .map_err(|err| format!("Could not load {}: {err}", path.display()))?;
+
+    let mut game = document.current_game.unwrap_or_default();
+    game.restore_legacy_events();
+    Ok((game, document.history))
+}
+
+fn save(path: &Path, game: &Game, history: &[ArchivedGame]) -> Result<(), String> {
+    let document = ExportDocument::new(Some(game.clone()), history.to_vec());
+    fs::write(path, document.to_json())
+        .map_err(|err| format!("Could not write {}: {err}", path.display()))
+}
+
+fn run(command: &Command, game: &mut Game, history: &mut Vec<ArchivedGame>) -> Result<(), String> {
+    let ongoing = |game: &Game| match game.status {
+        GameStatus::NotStarted => Err(String::from("The game hasn't started yet.")),
+        GameStatus::Ongoing => Ok(()),
+        GameStatus::Finished => Err(String::from("The game is over - start a new one.")),
+    };
+
+    match command {
+        Command::Show => (),
+        Command::Add(names) => {
+            if game.status != GameStatus::NotStarted {
+                return Err(String::from(
+                    "Players can only be added before the game starts.",
+                ));
+            }
+            for name in names {
+                if game.players.len() >= game.max_players {
+                    return Err(format!(
+                        "There can't be more than {} players.",
+                        game.max_players
+                    ));
+                }
+                game.add_player(name.clone(), game.next_free_color());
+            }
+        }
+        Command::Start => {
+            if game.status != GameStatus::NotStarted {
+                return Err(String::from("The game has already started."));
+            }
+            if !game.start_game() {
+                return Err(format!("Add at least {MIN_PLAYERS} players first."));
+            }
+        }
+        Command::Score(player, points) => {
+            ongoing(game)?;
+            let player_id = find_player(game, player)?;
+            if !game.is_score_valid(*points) {
+                return Err(format!("{points} is not a valid score in this variant."));
+            }
+            game.add_score(player_id, *points);
+        }
+        Command::Bonus(player) => {
+            ongoing(game)?;
+            let player_id = find_player(game, player)?;
+            if game.rules().bonuses.is_empty() {
+                return Err(String::from("This variant has no bonuses."));
+            }
+            if game.tile_bonus_granted {
+                return Err(String::from("The bonus was already given this round."));
+            }
+            game.grant_bonus(player_id, 0);
+        }
+        Command::Double(player) => {
+            ongoing(game)?;
+            if !game.rules().double_games {
+                return Err(String::from("This variant has no double games."));
+            }
+            if game.double_game_granted {
+                return Err(String::from("This round is already doubled."));
+            }
+            match player {
+                Some(player) => {
+                    let player_id = find_player(game, player)?;
+                    game.double_game_for_player(player_id);
+                }
+                None => game.double_game_total(),
+            }
+        }
+        Command::Undo => {
+            if !game.undo() {
+                return Err(String::from("Nothing to undo."));
+            }
+        }
+        Command::Redo => {
+            if !game.redo() {
+                return Err(String::from("Nothing to redo."));
+            }
+        }
+        Command::New => {
+            if game.status == GameStatus::Ongoing {
+                return Err(String::from("The game isn't over yet."));
+            }
+            if game.status == GameStatus::Finished {
+                let id = history.iter().map(|entry| entry.id).max().unwrap_or(0) + 1;
+                history.push(ArchivedGame::new(id, game.clone()));
+            }
+
+            let mut new_game = Game::new();
+            new_game.max_players = game.max_players;
+            new_game.set_variant(game.variant);
+            new_game.max_score = game.max_score;
+            new_game.tile_bonus_value = game.tile_bonus_value;
+            for player in &game.players {
+                new_game.add_player(player.name.clone(), player.color_index);
+            }
+            new_game.start_game();
+            *game = new_game;
+        }
+    }
+
+    game.check_status();
+    game.check_deadline(clock::now());
+    Ok(())
+}
+
+/// A player's id, from their name or seat number.
+fn find_player(game: &Game, player: &str) -> Result<usize, String> {
+    let by_seat = player
+        .parse::<usize>()
+        .ok()
+        .filter(|seat| (1..=game.players.len()).contains(seat));
+    let by_name = || {
+        game.players
+            .iter()
+            .find(|candidate| candidate.name.eq_ignore_ascii_case(player.trim()))
+            .map(|candidate| candidate.id)
+    };
+
+    by_seat
+        .or_else(by_name)
+        .ok_or_else(|| format!("Nobody called {player} is playing."))
+}
+
+/// The totals of every player, and who deals or who won.
+fn summary(game: &Game) -> String {
+    let mut text = String::new();
+
+    match game.status {
+        GameStatus::NotStarted => text.push_str("Not started yet.\n"),
+        GameStatus::Ongoing => {
+            let dealer = game
+                .players
+                .iter()
+                .find(|player| player.id == game.dealer_id())
+                .map(|player| player.name.as_str())
+                .unwrap_or_default();
+            text.push_str(&format!("Round {} - {dealer} deals.\n", game.round + 1));
+        }
+        GameStatus::Finished => {
+            text.push_str(&format!(
+                "Game over - {} won.\n",
+                game.get_winners().join(" and ")
+            ));
+        }
+    }
+
+    let width = game
+        .players
+        .iter()
+        .map(|player| player.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    for player in &game.players {
+        text.push_str(&format!(
+            "{:>2}. {:<width$}  {:>6}\n",
+            player.id, player.name, player.sum
+        ));
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(line: &str) -> Command {
+        let args: Vec<String> = line.split_whitespace().map(String::from).collect();
+        parse(&args).unwrap().1
+    }
+
+    fn play(lines: &[&str]) -> (Game, Vec<ArchivedGame>) {
+        let mut game = Game::new();
+        let mut history = Vec::new();
+        for line in lines {
+            run(&command(line), &mut game, &mut history).unwrap();
+        }
+        (game, history)
+    }
+
+    #[test]
+    fn parses_commands_and_the_file() {
+        let args: Vec<String> = ["--file", "night.json", "score", "vlad", "-25"]
+            .map(String::from)
+            .to_vec();
+        let (path, command) = parse(&args).unwrap();
+
+        assert_eq!(path, PathBuf::from("night.json"));
+        assert_eq!(command, Command::Score(String::from("vlad"), -25));
+        assert_eq!(parse(&[]).unwrap().1, Command::Show);
+        assert!(parse(&[String::from("score"), String::from("vlad")]).is_err());
+    }
+
+    #[test]
+    fn plays_a_round_with_bonus_and_double() {
+        let (game, _) = play(&[
+            "add Antonio Vlad",
+            "start",
+            "bonus antonio",
+            "double 2",
+            "score 1 100",
+            "score Vlad 50",
+        ]);
+
+        assert_eq!(game.players[0].sum, 150);
+        assert_eq!(game.players[1].sum, 100);
+        assert_eq!(game.round, 1);
+        assert!(summary(&game).starts_with("Round 2 - Vlad deals."));
+    }
+
+    #[test]
+    fn refuses_what_the_app_would() {
+        let mut game = Game::new();
+        let mut history = Vec::new();
+
+        assert!(run(&command("score 1 100"), &mut game, &mut history).is_err());
+        run(&command("add Antonio"), &mut game, &mut history).unwrap();
+        assert!(run(&command("start"), &mut game, &mut history).is_err());
+        run(&command("add Vlad"), &mut game, &mut history).unwrap();
+        run(&command("start"), &mut game, &mut history).unwrap();
+
+        assert!(run(&command("score 1 101"), &mut game, &mut history).is_err());
+        assert!(run(&command("score Ioana 100"), &mut game, &mut history).is_err());
+        run(&command("bonus 1"), &mut game, &mut history).unwrap();
+        assert!(run(&command("bonus 2"), &mut game, &mut history).is_err());
+    }
+
+    #[test]
+    fn finished_games_are_archived_for_a_new_one() {
+        let (mut game, mut history) = play(&["add Antonio Vlad", "start"]);
+        game.max_score = 100;
+        run(&command("score 1 100"), &mut game, &mut history).unwrap();
+        run(&command("score 2 50"), &mut game, &mut history).unwrap();
+        assert!(summary(&game).starts_with("Game over - Antonio won."));
+
+        run(&command("new"), &mut game, &mut history).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(game.status, GameStatus::Ongoing);
+        assert_eq!(game.players.len(), 2);
+        assert_eq!(game.players[0].sum, 0);
+    }
+
+    #[test]
+    fn games_are_kept_as_app_exports() {
+        let (game, history) = play(&["add Antonio Vlad", "start", "score 1 100"]);
+        let path =
+            std::env::temp_dir().join(format!("rummy-nights-cli-{}.json", std::process::id()));
+
+        save(&path, &game, &history).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let (loaded, _) = load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(ExportDocument::from_json(&text).is_ok());
+        assert_eq!(loaded.events, game.events);
+        assert_eq!(loaded.players[0].sum, 100);
+    }
+}
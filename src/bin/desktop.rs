@@ -0,0 +1,37 @@
+//! The app in a desktop window, with the same screens as the web app.
+//!
+//! Run it with `cargo run --bin rummy-nights-desktop --no-default-features --features desktop`.
+//! Settings, templates and games are kept as files in the user's data directory,
+//! see `rummy_nights::backend::storage::FileStorage`.
+
+#![allow(non_snake_case)]
+use dioxus_desktop::{Config, LogicalSize, WindowBuilder};
+use rummy_nights::prelude::*;
+use std::path::PathBuf;
+
+/// The Tailwind styles of the web app.
+const STYLE: &str = include_str!("../../public/style.css");
+
+fn main() {
+    log!("Initializing app.");
+
+    let window = WindowBuilder::new()
+        .with_title("Rummy Nights")
+        .with_inner_size(LogicalSize::new(480.0, 860.0));
+    let config = Config::new()
+        .with_window(window)
+        .with_custom_head(format!("<style>{STYLE}</style>"))
+        .with_resource_directory(resource_directory());
+
+    dioxus_desktop::launch_cfg(rummy_nights::frontend::app::Root, config);
+}
+
+/// Where images and other files of `public/` are found - next to the executable
+/// when the app is installed, or in the repository when run with cargo.
+fn resource_directory() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join("public")))
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/public")))
+}
@@ -0,0 +1,27 @@
+//! Pre-render the first screens into a built web app, so the menu shows before
+//! the wasm bundle has loaded.
+//!
+//! Run it after `dx build --release` with
+//! `cargo run --bin rummy-nights-prerender -- dist`. It fills the app's root
+//! element in `index.html` with the menu, which the app takes over when it starts
+//! if it would draw the same menu, and writes `credits.html`, a static page of the credits. Last, it writes
+//! `version.js`, which names the service worker's cache after the built files,
+//! so any change to them replaces what devices have cached.
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    let dist = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("dist"));
+
+    let path = std::path::Path::new(&dist);
+    if let Err(err) = rummy_nights::frontend::prerender::prerender_dist(path) {
+        eprintln!("Could not pre-render {dist}: {err}");
+        std::process::exit(1);
+    }
+    println!("Pre-rendered the menu and credits into {dist}.");
+}
+
+/// Pre-rendering runs natively, next to the web build - a browser has nothing to run.
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,552 @@
+//! A small WebSocket relay for live score sync between devices.
+//!
+//! Devices connect to `ws://<address>/<session code>`, and every text message
+//! one of them sends is passed on to the other devices in the same session.
+//! The relay knows nothing about games - see `rummy_nights::backend::sync`.
+//!
+//! Run it with `cargo run --bin rummy-nights-relay -- 0.0.0.0:8787` to let
+//! other devices on the network reach it. It listens on localhost by default,
+//! and takes up to [`MAX_CONNECTIONS`] devices at a time.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:8787";
+
+/// Messages are whole games at most, so anything bigger is refused.
+const MAX_MESSAGE_LENGTH: u64 = 1024 * 1024;
+
+/// Every connection has a thread of its own, so there's a limit to how many are open.
+/// A few tables' worth of devices is plenty for a relay on a local network.
+const MAX_CONNECTIONS: usize = 64;
+
+/// A connection that doesn't finish its handshake in time is dropped, so it
+/// can't hold on to one of the connections for nothing.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Appended to the client's key to prove the server speaks WebSocket (RFC 6455).
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// Why the relay closes a connection (RFC 6455 section 7.4.1).
+const STATUS_PROTOCOL_ERROR: u16 = 1002;
+const STATUS_UNSUPPORTED: u16 = 1003;
+const STATUS_TOO_BIG: u16 = 1009;
+
+type Peer = (usize, Arc<Mutex<TcpStream>>);
+type Sessions = Arc<Mutex<HashMap<String, Vec<Peer>>>>;
+
+fn main() {
+    let address = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from(DEFAULT_ADDRESS));
+
+    let listener = match TcpListener::bind(&address) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("Could not listen on {address}: {err}");
+            std::process::exit(1);
+        }
+    };
+    println!("Relaying sessions on ws://{address}/<session code>");
+
+    listen(listener, MAX_CONNECTIONS);
+}
+
+/// Serve every connection on a thread of its own, turning away those over the limit.
+fn listen(listener: TcpListener, max_connections: usize) {
+    let sessions = Sessions::default();
+    let open = Arc::new(AtomicUsize::new(0));
+
+    for (id, stream) in listener.incoming().enumerate() {
+        let Ok(mut stream) = stream else {
+            continue;
+        };
+        if open.fetch_add(1, Ordering::SeqCst) >= max_connections {
+            open.fetch_sub(1, Ordering::SeqCst);
+            eprintln!("Connection {id} refused: too many connections.");
+            let _ = write!(
+                stream,
+                "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n"
+            );
+            continue;
+        }
+
+        let sessions = sessions.clone();
+        let open = open.clone();
+        thread::spawn(move || {
+            if let Err(err) = serve(id, stream, &sessions) {
+                eprintln!("Connection {id} closed: {err}");
+            }
+            open.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+/// Upgrade one connection to WebSocket, then relay its messages until it closes.
+fn serve(id: usize, stream: TcpStream, sessions: &Sessions) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let (path, key) = read_handshake(&mut reader)?;
+    stream.set_read_timeout(None)?;
+
+    let session = path.trim_matches('/').to_string();
+    let writer = Arc::new(Mutex::new(stream));
+    if session.is_empty() || !session.chars().all(|c| c.is_ascii_alphanumeric()) {
+        let mut stream = writer.lock().unwrap();
+        write!(
+            stream,
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n"
+        )?;
+        return Ok(());
+    }
+
+    // Joined before the handshake is answered, so nothing sent to the session
+    // after the device knows it's connected passes it by - and while holding on
+    // to its stream, so nothing sent to it comes before the answer.
+    let answer = {
+        let mut stream = writer.lock().unwrap();
+        println!("Connection {id} joined session {session}.");
+        sessions
+            .lock()
+            .unwrap()
+            .entry(session.clone())
+            .or_default()
+            .push((id, writer.clone()));
+
+        write!(
+            stream,
+            "HTTP/1.1 101 Switching Protocols\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(&key)
+        )
+    };
+    let result = answer.and_then(|_| relay(id, &session, &mut reader, &writer, sessions));
+
+    let mut sessions = sessions.lock().unwrap();
+    if let Some(peers) = sessions.get_mut(&session) {
+        peers.retain(|(peer, _)| *peer != id);
+        if peers.is_empty() {
+            sessions.remove(&session);
+        }
+    }
+    println!("Connection {id} left session {session}.");
+
+    result
+}
+
+fn relay(
+    id: usize,
+    session: &str,
+    reader: &mut impl Read,
+    writer: &Mutex<TcpStream>,
+    sessions: &Sessions,
+) -> io::Result<()> {
+    loop {
+        let frame = match read_frame(reader) {
+            Ok(frame) => frame,
+            Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                return Err(refuse(writer, STATUS_TOO_BIG, err));
+            }
+            Err(err) => return Err(err),
+        };
+        // Clients mask every frame they send, and servers must drop those that
+        // don't (RFC 6455 section 5.1).
+        if !frame.masked {
+            let err = io::Error::new(io::ErrorKind::InvalidData, "unmasked frame");
+            return Err(refuse(writer, STATUS_PROTOCOL_ERROR, err));
+        }
+
+        match frame.opcode {
+            OPCODE_TEXT if frame.fin => {
+                let peers: Vec<Peer> = sessions
+                    .lock()
+                    .unwrap()
+                    .get(session)
+                    .map(|peers| {
+                        peers
+                            .iter()
+                            .filter(|(peer, _)| *peer != id)
+                            .cloned()
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                for (_, peer) in peers {
+                    // A peer that can't be written to is cleaned up by its own thread.
+                    let _ = write_frame(&mut *peer.lock().unwrap(), OPCODE_TEXT, &frame.payload);
+                }
+            }
+            OPCODE_CLOSE => {
+                let _ = write_frame(&mut *writer.lock().unwrap(), OPCODE_CLOSE, &[]);
+                return Ok(());
+            }
+            OPCODE_PING => write_frame(&mut *writer.lock().unwrap(), OPCODE_PONG, &frame.payload)?,
+            OPCODE_PONG => (),
+            _ => {
+                let err = io::Error::new(io::ErrorKind::InvalidData, "unsupported frame");
+                return Err(refuse(writer, STATUS_UNSUPPORTED, err));
+            }
+        }
+    }
+}
+
+/// Tell the device why its connection ends, passing on the error that ended it.
+fn refuse(writer: &Mutex<TcpStream>, status: u16, err: io::Error) -> io::Error {
+    let _ = write_frame(
+        &mut *writer.lock().unwrap(),
+        OPCODE_CLOSE,
+        &status.to_be_bytes(),
+    );
+    err
+}
+
+/// Read the HTTP upgrade request, returning the path and the client's key.
+fn read_handshake(reader: &mut impl BufRead) -> io::Result<(String, String)> {
+    let invalid = |reason: &str| io::Error::new(io::ErrorKind::InvalidData, reason.to_string());
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let path = request_line
+        .split_whitespace()
+        .nth(1)
+        .ok_or_else(|| invalid("not an HTTP request"))?
+        .to_string();
+
+    let mut key = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(invalid("connection closed during the handshake"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("sec-websocket-key") {
+                key = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    Ok((path, key.ok_or_else(|| invalid("not a WebSocket request"))?))
+}
+
+fn accept_key(key: &str) -> String {
+    base64(&sha1(format!("{key}{HANDSHAKE_GUID}").as_bytes()))
+}
+
+struct Frame {
+    fin: bool,
+    opcode: u8,
+    /// Whether the sender masked the payload, as clients have to.
+    masked: bool,
+    payload: Vec<u8>,
+}
+
+fn read_frame(reader: &mut impl Read) -> io::Result<Frame> {
+    let mut head = [0; 2];
+    reader.read_exact(&mut head)?;
+
+    let mut length = (head[1] & 0x7F) as u64;
+    if length == 126 {
+        let mut bytes = [0; 2];
+        reader.read_exact(&mut bytes)?;
+        length = u16::from_be_bytes(bytes) as u64;
+    } else if length == 127 {
+        let mut bytes = [0; 8];
+        reader.read_exact(&mut bytes)?;
+        length = u64::from_be_bytes(bytes);
+    }
+    if length > MAX_MESSAGE_LENGTH {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message too long",
+        ));
+    }
+
+    let mut mask = [0; 4];
+    let masked = head[1] & 0x80 != 0;
+    if masked {
+        reader.read_exact(&mut mask)?;
+    }
+
+    let mut payload = vec![0; length as usize];
+    reader.read_exact(&mut payload)?;
+    if masked {
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+    }
+
+    Ok(Frame {
+        fin: head[0] & 0x80 != 0,
+        opcode: head[0] & 0x0F,
+        masked,
+        payload,
+    })
+}
+
+/// Write a whole, unmasked frame - servers never mask what they send.
+fn write_frame(writer: &mut impl Write, opcode: u8, payload: &[u8]) -> io::Result<()> {
+    let mut frame = vec![0x80 | opcode];
+
+    match payload.len() {
+        length @ 0..=125 => frame.push(length as u8),
+        length @ 126..=0xFFFF => {
+            frame.push(126);
+            frame.extend((length as u16).to_be_bytes());
+        }
+        length => {
+            frame.push(127);
+            frame.extend((length as u64).to_be_bytes());
+        }
+    }
+    frame.extend(payload);
+
+    writer.write_all(&frame)
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend((data.len() as u64 * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut words = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            words[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (value, new) in state.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(new);
+        }
+    }
+
+    let mut digest = [0; 20];
+    for (bytes, value) in digest.chunks_mut(4).zip(state) {
+        bytes.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+/// Standard base64, with padding.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::new();
+
+    for chunk in data.chunks(3) {
+        let bits = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, byte)| acc | (*byte as u32) << (16 - i * 8));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                text.push(ALPHABET[(bits >> (18 - i * 6)) as usize & 0x3F] as char);
+            } else {
+                text.push('=');
+            }
+        }
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_key_matches_the_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn reads_masked_frames_from_clients() {
+        // "Hello", masked, from RFC 6455 section 5.7.
+        let bytes = [
+            0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+        ];
+        let frame = read_frame(&mut &bytes[..]).unwrap();
+
+        assert!(frame.fin);
+        assert!(frame.masked);
+        assert_eq!(frame.opcode, OPCODE_TEXT);
+        assert_eq!(frame.payload, b"Hello");
+    }
+
+    #[test]
+    fn written_frames_read_back() {
+        for length in [0, 125, 126, 70_000] {
+            let payload = vec![b'x'; length];
+            let mut bytes = Vec::new();
+            write_frame(&mut bytes, OPCODE_TEXT, &payload).unwrap();
+
+            assert_eq!(read_frame(&mut &bytes[..]).unwrap().payload, payload);
+        }
+    }
+
+    #[test]
+    fn handshake_finds_the_session_and_key() {
+        let request = "GET /AB12CD HTTP/1.1\r\nHost: localhost\r\nSec-WebSocket-Key: abc==\r\n\r\n";
+        let (path, key) = read_handshake(&mut request.as_bytes()).unwrap();
+
+        assert_eq!(path, "/AB12CD");
+        assert_eq!(key, "abc==");
+    }
+
+    /// Start a relay on a free local port, returning its address.
+    fn start_relay(max_connections: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || listen(listener, max_connections));
+        address
+    }
+
+    /// The response to a request, up to the blank line that ends its head.
+    fn read_head(stream: &mut TcpStream) -> String {
+        let mut head = Vec::new();
+        let mut byte = [0];
+        while !head.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap() == 1 {
+            head.push(byte[0]);
+        }
+        String::from_utf8(head).unwrap()
+    }
+
+    fn open(address: &str, path: &str) -> (TcpStream, String) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: {address}\r\nUpgrade: websocket\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n"
+        )
+        .unwrap();
+        let head = read_head(&mut stream);
+        (stream, head)
+    }
+
+    /// A frame the way a browser sends it, masked.
+    fn client_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x12, 0x34, 0x56, 0x78];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend(mask);
+        frame.extend(
+            payload
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| byte ^ mask[i % 4]),
+        );
+        frame
+    }
+
+    #[test]
+    fn unknown_paths_are_not_found() {
+        let address = start_relay(MAX_CONNECTIONS);
+
+        for path in ["/", "/not-a-code"] {
+            let (_, head) = open(&address, path);
+            assert!(head.starts_with("HTTP/1.1 404"), "{path}: {head}");
+        }
+    }
+
+    #[test]
+    fn messages_reach_the_other_devices_in_the_session() {
+        let address = start_relay(MAX_CONNECTIONS);
+        let (mut host, head) = open(&address, "/AB12CD");
+        assert!(head.starts_with("HTTP/1.1 101"), "{head}");
+        assert!(head.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+        let (mut guest, _) = open(&address, "/AB12CD");
+        let (mut stranger, _) = open(&address, "/ZZ99ZZ");
+
+        host.write_all(&client_frame(OPCODE_TEXT, b"snapshot"))
+            .unwrap();
+        let frame = read_frame(&mut guest).unwrap();
+        assert_eq!(
+            (frame.opcode, frame.payload),
+            (OPCODE_TEXT, b"snapshot".to_vec())
+        );
+
+        // Only the guest gets it - the host answers a ping next, and the other
+        // session hears nothing.
+        host.write_all(&client_frame(OPCODE_PING, b"?")).unwrap();
+        assert_eq!(read_frame(&mut host).unwrap().opcode, OPCODE_PONG);
+        stranger
+            .set_read_timeout(Some(Duration::from_millis(200)))
+            .unwrap();
+        assert!(read_frame(&mut stranger).is_err());
+
+        guest.write_all(&client_frame(OPCODE_CLOSE, &[])).unwrap();
+        assert_eq!(read_frame(&mut guest).unwrap().opcode, OPCODE_CLOSE);
+    }
+
+    #[test]
+    fn unmasked_frames_are_refused() {
+        let address = start_relay(MAX_CONNECTIONS);
+        let (mut device, _) = open(&address, "/AB12CD");
+
+        write_frame(&mut device, OPCODE_TEXT, b"snapshot").unwrap();
+        let frame = read_frame(&mut device).unwrap();
+        assert_eq!(frame.opcode, OPCODE_CLOSE);
+        assert_eq!(frame.payload, STATUS_PROTOCOL_ERROR.to_be_bytes());
+    }
+
+    #[test]
+    fn connections_over_the_limit_are_turned_away() {
+        let address = start_relay(1);
+        let (_first, head) = open(&address, "/AB12CD");
+        assert!(head.starts_with("HTTP/1.1 101"), "{head}");
+
+        let mut second = TcpStream::connect(&address).unwrap();
+        second
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        assert!(read_head(&mut second).starts_with("HTTP/1.1 503"));
+    }
+}
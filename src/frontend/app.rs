@@ -0,0 +1,95 @@
+//! The root of the app, shared by the web and desktop builds.
+
+use crate::prelude::*;
+use dioxus::prelude::*;
+use std::cell::Cell;
+
+/// Launched by the desktop app - sets up the global state, then renders the app.
+pub fn Root(cx: Scope) -> Element {
+    fermi::use_init_atom_root(cx);
+    render!(App {})
+}
+
+pub struct LoadedRootProps {
+    model: Cell<Option<Model>>,
+}
+
+impl LoadedRootProps {
+    pub fn new(model: Model) -> Self {
+        Self {
+            model: Cell::new(Some(model)),
+        }
+    }
+}
+
+/// Like [`Root`], but starts from a model that was set up beforehand - loaded by
+/// the web app before it decides whether to hydrate, or made up for pre-rendering.
+pub fn LoadedRoot(cx: Scope<LoadedRootProps>) -> Element {
+    fermi::use_init_atom_root(cx);
+    let state = fermi::use_atom_ref(cx, &STATE);
+
+    cx.use_hook(|| {
+        if let Some(model) = cx.props.model.take() {
+            *state.write_silent() = model;
+        }
+    });
+
+    render!(App {})
+}
+
+/// Whether the app, started with this model, draws exactly the menu that
+/// `prerender.rs` puts into the page - a fresh start, in the default language.
+///
+/// Hydrating only reattaches the pre-rendered nodes, it doesn't change their text
+/// or attributes, so anything else - another language, a game to resume, another
+/// screen - has to be drawn from scratch instead.
+pub fn matches_prerendered_menu(model: &Model) -> bool {
+    model.screen == Screen::Menu
+        && model.settings.language == Language::default()
+        && model.game.status != GameStatus::Ongoing
+        && model.history.is_empty()
+        && model.storage_problems.is_empty()
+        && model.share_error.is_none()
+        && model.sync.is_none()
+}
+
+pub fn App(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let screen = state.read().screen;
+    let language = state.read().settings.language;
+
+    if !(state.read().checked_storage) {
+        state.write().initialize_storage();
+        //state.write()._debug_game_screen();
+    };
+
+    log!("Start render.");
+    render!(
+        div {
+            class: "flex flex-col bg-white h-screen w-screen relative overflow-hidden",
+            lang: language.code(),
+            dir: language.direction().attribute(),
+            crate::frontend::DecorativeSpheres {},
+            crate::frontend::update::UpdatePrompt {},
+            state.read().sync.as_ref().map(|session| session.url()).map(|url| rsx!(
+                crate::frontend::sync::SyncAgent { key: "{url}", url: url.clone() }
+            )),
+            div {
+                class: "z-10 flex flex-col h-screen mx-auto w-full sm:max-w-lg",
+                match screen {
+                    Screen::Menu => rsx!(crate::frontend::menu::MenuScreen {}),
+                    Screen::PlayerSelect => rsx!(crate::frontend::player_select::PlayerSelectScreen {}),
+                    Screen::Templates => rsx!(crate::frontend::templates::TemplateScreen {}),
+                    Screen::Game => rsx!(crate::frontend::game::GameScreen {}),
+                    Screen::EndGame => rsx!(crate::frontend::game_end::EndScreen {}),
+                    Screen::Settings => rsx!(crate::frontend::settings::SettingsScreen {}),
+                    Screen::Credits => rsx!(crate::frontend::credits::CreditsScreen {}),
+                    Screen::History => rsx!(crate::frontend::history::HistoryScreen {}),
+                    Screen::Stats => rsx!(crate::frontend::stats::StatsScreen {}),
+                    Screen::Data => rsx!(crate::frontend::data::DataScreen {}),
+                    Screen::Sync => rsx!(crate::frontend::sync::SyncScreen {}),
+                }
+            }
+        }
+    )
+}
@@ -26,66 +26,6 @@ pub fn OkayIcon(cx: Scope) -> Element {
     )
 }
 
-pub fn EnglishFlagIcon(cx: Scope) -> Element {
-    render!(
-        svg {
-            id: "flag-icons-gb",
-            xmlns: "http://www.w3.org/2000/svg",
-            view_box: "0 0 640 480",
-            height: "100%",
-            width: "100%",
-            path {
-                fill: "#012169",
-                d: "M0 0h640v480H0z",
-            }
-            path {
-                fill: "#FFF",
-                d: "m75 0 244 181L562 0h78v62L400 241l240 178v61h-80L320 301 81 480H0v-60l239-178L0 64V0h75z",
-            }
-            path {
-                d: "m424 281 216 159v40L369 281h55zm-184 20 6 35L54 480H0l240-179zM640 0v3L391 191l2-44L590 0h50zM0 0l239 176h-60L0 42V0z",
-                fill: "#C8102E",
-            }
-            path {
-                fill: "#FFF",
-                d: "M241 0v480h160V0H241zM0 160v160h640V160H0z",
-            }
-            path {
-                d: "M0 193v96h640v-96H0zM273 0v480h96V0h-96z",
-                fill: "#C8102E",
-            }
-        }
-    )
-}
-
-pub fn RomanianFlagIcon(cx: Scope) -> Element {
-    render!(
-        svg {
-            id: "flag-icons-ro",
-            xmlns: "http://www.w3.org/2000/svg",
-            view_box: "0 0 640 480",
-            height: "100%",
-            width: "100%",
-            g {
-                fill_rule: "evenodd",
-                stroke_width: "1pt",
-                path {
-                    d: "M0 0h213.3v480H0z",
-                    fill: "#00319c",
-                }
-                path {
-                    d: "M213.3 0h213.4v480H213.3z",
-                    fill: "#ffde00",
-                }
-                path {
-                    d: "M426.7 0H640v480H426.7z",
-                    fill: "#de2110",
-                }
-            }
-        }
-    )
-}
-
 pub fn AddIcon(cx: Scope) -> Element {
     render!(
         svg {
@@ -508,6 +448,135 @@ pub fn SettingsIcon(cx: Scope) -> Element {
     )
 }
 
+pub fn StatsIcon(cx: Scope) -> Element {
+    render!(
+        svg {
+            role: "img",
+            xmlns: "http://www.w3.org/2000/svg",
+            width: "100%",
+            height: "100%",
+            view_box: "0 0 24 24",
+            stroke: "#000000",
+            stroke_width: "1.5",
+            stroke_linecap: "round",
+            stroke_linejoin: "round",
+            fill: "none",
+            color: "#000000",
+            path {
+                d: "M3 21L21 21",
+            }
+            path {
+                d: "M6 17L6 12",
+            }
+            path {
+                d: "M11 17L11 5",
+            }
+            path {
+                d: "M16 17L16 9",
+            }
+            path {
+                d: "M21 17L21 14",
+            }
+        }
+    )
+}
+
+pub fn ExportIcon(cx: Scope) -> Element {
+    render!(
+        svg {
+            role: "img",
+            xmlns: "http://www.w3.org/2000/svg",
+            width: "100%",
+            height: "100%",
+            view_box: "0 0 24 24",
+            stroke: "#000000",
+            stroke_width: "1.5",
+            stroke_linecap: "round",
+            stroke_linejoin: "round",
+            fill: "none",
+            color: "#000000",
+            path {
+                d: "M12 3L12 15",
+            }
+            path {
+                d: "M8 11L12 15L16 11",
+            }
+            path {
+                d: "M4 17L4 20C4 20.5523 4.44772 21 5 21L19 21C19.5523 21 20 20.5523 20 20L20 17",
+            }
+        }
+    )
+}
+
+pub fn ShareIcon(cx: Scope) -> Element {
+    render!(
+        svg {
+            role: "img",
+            xmlns: "http://www.w3.org/2000/svg",
+            width: "100%",
+            height: "100%",
+            view_box: "0 0 24 24",
+            stroke: "#000000",
+            stroke_width: "1.5",
+            stroke_linecap: "round",
+            stroke_linejoin: "round",
+            fill: "none",
+            color: "#000000",
+            circle {
+                cx: "18",
+                cy: "5",
+                r: "2.5",
+            }
+            circle {
+                cx: "6",
+                cy: "12",
+                r: "2.5",
+            }
+            circle {
+                cx: "18",
+                cy: "19",
+                r: "2.5",
+            }
+            path {
+                d: "M8.2 10.8L15.8 6.2",
+            }
+            path {
+                d: "M8.2 13.2L15.8 17.8",
+            }
+        }
+    )
+}
+
+pub fn SyncIcon(cx: Scope) -> Element {
+    render!(
+        svg {
+            role: "img",
+            xmlns: "http://www.w3.org/2000/svg",
+            width: "100%",
+            height: "100%",
+            view_box: "0 0 24 24",
+            stroke: "#ffffff",
+            stroke_width: "1.5",
+            stroke_linecap: "round",
+            stroke_linejoin: "round",
+            fill: "none",
+            color: "#ffffff",
+            path {
+                d: "M7 8C8.2 6.2 10 5 12 5C15.3 5 18 7.7 18 11",
+            }
+            path {
+                d: "M16 9L18 11L20 9",
+            }
+            path {
+                d: "M17 16C15.8 17.8 14 19 12 19C8.7 19 6 16.3 6 13",
+            }
+            path {
+                d: "M8 15L6 13L4 15",
+            }
+        }
+    )
+}
+
 pub fn TrophyIcon(cx: Scope) -> Element {
     render!(
         svg {
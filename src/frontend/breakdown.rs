@@ -0,0 +1,188 @@
+//! The game round by round - a row per round and a column per player, with
+//! what each round added and the total so far.
+
+use crate::backend::breakdown::{round_rows, RoundPoints, SumBreakdown};
+use crate::prelude::*;
+use dioxus::prelude::*;
+use fluent::fluent_args;
+
+pub fn RoundBreakdown(cx: Scope) -> Element {
+    log!("Rendering round breakdown.");
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let explained = use_state(cx, || None::<usize>);
+
+    let players = state.read().game.players.clone();
+    // Pair every cell with its player's colour up front, so each row owns what it renders.
+    let rows = round_rows(&state.read().game)
+        .into_iter()
+        .map(|row| {
+            let cells = row
+                .points
+                .into_iter()
+                .zip(players.iter().map(|player| player.color_index))
+                .collect::<Vec<_>>();
+            (row.round, cells)
+        })
+        .collect::<Vec<_>>();
+    let columns = format!("2rem repeat({}, minmax(0, 1fr))", players.len());
+    let explained_player = explained
+        .get()
+        .and_then(|id| players.iter().find(|player| player.id == id))
+        .cloned();
+
+    render!(
+        div {
+            class: "z-10 flex flex-col gap-4 px-4 pb-24 overflow-auto",
+            div {
+                class: "grid gap-x-2 gap-y-2 items-center",
+                grid_template_columns: "{columns}",
+                span {}
+                players.iter().map(|player| {
+                    let player_id = player.id;
+                    let background = BG_COLORS[player.color_index];
+                    rsx!(
+                        button {
+                            class: "h-8 min-w-0 px-1 truncate rounded-full text-white font-semibold {background}",
+                            title: "{player.name}",
+                            onclick: move |_| {
+                                if *explained.get() == Some(player_id) {
+                                    explained.set(None);
+                                } else {
+                                    explained.set(Some(player_id));
+                                }
+                            },
+                            "{player.name}"
+                        }
+                    )
+                })
+                rows.into_iter().map(|(round, cells)| rsx!(
+                    span {
+                        class: "text-sm text-center text-slate-500",
+                        "{round + 1}"
+                    }
+                    cells.into_iter().map(|(points, color_index)| rsx!(
+                        RoundCell {
+                            points: points,
+                            color_index: color_index,
+                        }
+                    ))
+                ))
+                span {}
+                players.iter().map(|player| {
+                    let border = BORDER_COLORS[player.color_index];
+                    rsx!(
+                        p {
+                            class: "rounded border-b-[7px] {border} h-10 text-center text-2xl font-bold",
+                            "{player.sum}"
+                        }
+                    )
+                })
+            }
+            match explained_player {
+                Some(player) => rsx!(SumExplanation { player: player }),
+                None => rsx!(
+                    p {
+                        class: "text-sm text-center text-slate-500",
+                        get_text(cx, "breakdown_hint")
+                    }
+                ),
+            }
+        }
+    )
+}
+
+#[component]
+fn RoundCell(cx: Scope, points: RoundPoints, color_index: usize) -> Element<'a> {
+    let border = BORDER_COLORS[*color_index];
+    let score = points
+        .score
+        .map_or_else(|| String::from("-"), |score| score.to_string());
+
+    render!(
+        div {
+            class: "flex flex-col items-center min-w-0 rounded border-b-4 {border} pb-1",
+            span {
+                class: "text-lg leading-6",
+                "{score}"
+            }
+            (points.bonus != 0).then(|| rsx!(
+                span {
+                    class: "flex flex-row items-center gap-1 text-xs",
+                    div {
+                        class: "h-3 w-3",
+                        assets::BonusIcon {}
+                    }
+                    "{points.bonus:+}"
+                }
+            )),
+            (points.doubled != 0).then(|| rsx!(
+                span {
+                    class: "text-xs font-bold text-green-600",
+                    "x2 {points.doubled:+}"
+                }
+            ))
+            span {
+                class: "text-xs text-slate-500",
+                "= {points.running_total}"
+            }
+        }
+    )
+}
+
+/// How a player's total adds up, from their scores, bonuses and doubled rounds.
+#[component]
+fn SumExplanation(cx: Scope, player: Player) -> Element<'a> {
+    let breakdown = SumBreakdown::of(player);
+    let scores = if breakdown.scores.is_empty() {
+        String::from("0")
+    } else {
+        breakdown
+            .scores
+            .iter()
+            .map(|score| score.to_string())
+            .collect::<Vec<_>>()
+            .join(" + ")
+    };
+
+    render!(
+        div {
+            class: "flex flex-col gap-1 p-4 rounded-3xl bg-slate-200",
+            span {
+                class: "font-semibold truncate",
+                "{player.name}"
+            }
+            p {
+                class: "text-sm break-words",
+                get_text_with(
+                    cx,
+                    "breakdown_scores",
+                    fluent_args!["scores" => scores, "total" => breakdown.score_total()],
+                )
+            }
+            (breakdown.bonuses != 0).then(|| rsx!(
+                p {
+                    class: "text-sm",
+                    get_text_with(
+                        cx,
+                        "breakdown_bonuses",
+                        fluent_args!["points" => format!("{:+}", breakdown.bonuses)],
+                    )
+                }
+            )),
+            (breakdown.doubled != 0).then(|| rsx!(
+                p {
+                    class: "text-sm",
+                    get_text_with(
+                        cx,
+                        "breakdown_doubled",
+                        fluent_args!["points" => format!("{:+}", breakdown.doubled)],
+                    )
+                }
+            ))
+            p {
+                class: "font-semibold",
+                get_text_with(cx, "breakdown_sum", fluent_args!["sum" => breakdown.sum])
+            }
+        }
+    )
+}
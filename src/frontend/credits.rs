@@ -8,10 +8,10 @@ pub fn CreditsScreen(cx: Scope) -> Element {
 
     render!(
         button {
-            class: "absolute top-4 left-4",
+            class: "absolute top-4 start-4",
             onclick: move |_| state.write().go_to_screen(Screen::Settings),
             div {
-                class: "h-12 scale-x-[-1]",
+                class: "h-12 scale-x-[-1] rtl:scale-x-100",
                 assets::BackIcon {}
             }
         },
@@ -68,9 +68,6 @@ pub fn CreditsScreen(cx: Scope) -> Element {
                         p {
                             "Charm Icons",
                         }
-                        p {
-                            "Lipis's flag-icons"
-                        }
                     }
                     p {
                         class: "w-full text-center col-span-1",
@@ -0,0 +1,407 @@
+use crate::backend::backup::{Backup, BackupPreview, RestoreMode};
+use crate::backend::clock;
+use crate::backend::qr::QrCode;
+use crate::prelude::*;
+use dioxus::prelude::*;
+use fluent::fluent_args;
+
+pub fn DataScreen(cx: Scope) -> Element {
+    log!("Rendering data screen.");
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let has_game = state.read().game.status != GameStatus::NotStarted;
+    let has_history = !state.read().history.is_empty();
+
+    render!(
+        TopBar {},
+        div {
+            class: "flex flex-col grow gap-6 px-8 mt-16 mb-8 overflow-auto",
+            span {
+                class: "font-semibold text-lg border-b-2 border-violet-500 w-max mx-auto",
+                get_text(cx, "data_prompt")
+            }
+            has_game.then(|| rsx!(ShareSection {})),
+            has_game.then(|| rsx!(
+                ExportRow {
+                    label: get_text(cx, "current_game"),
+                    name: "rummy-nights-game",
+                    json: Model::export_current_game,
+                    csv: Model::current_game_csv,
+                }
+            )),
+            has_history.then(|| rsx!(
+                ExportRow {
+                    label: get_text(cx, "history"),
+                    name: "rummy-nights-history",
+                    json: Model::export_history,
+                    csv: Model::history_csv,
+                }
+            )),
+            ImportButton {}
+            BackupSection {}
+        }
+    )
+}
+
+/// A link and QR code that open the current game on another device.
+fn ShareSection(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let shown = use_state(cx, || false);
+    let copied = use_state(cx, || false);
+    let link = state.read().share_link();
+
+    render!(
+        div {
+            class: "flex flex-col gap-2 p-3 rounded-3xl bg-slate-200",
+            div {
+                class: "flex flex-row gap-2 items-center justify-between",
+                span {
+                    class: "font-semibold text-lg px-2",
+                    get_text(cx, "share_game")
+                }
+                button {
+                    class: "flex flex-row gap-1 items-center px-3 h-10 rounded-full bg-white font-semibold",
+                    onclick: move |_| shown.set(!shown.get()),
+                    div {
+                        class: "h-6",
+                        assets::ShareIcon {}
+                    }
+                    if *shown.get() { get_text(cx, "hide_code") } else { get_text(cx, "show_code") }
+                }
+            }
+            shown.then(|| rsx!(
+                p {
+                    class: "text-sm text-slate-500 px-2",
+                    get_text(cx, "share_hint")
+                }
+                match QrCode::encode(&link) {
+                    Some(qr) => rsx!(QrCodeImage { qr: qr }),
+                    None => rsx!(
+                        p {
+                            class: "text-sm text-center text-red-500",
+                            get_text(cx, "share_too_long")
+                        }
+                    ),
+                }
+                button {
+                    class: "h-10 rounded-full bg-white font-semibold",
+                    onclick: move |_| {
+                        let link = serde_json::to_string(&state.read().share_link()).unwrap_or_default();
+                        let _ = use_eval(cx)(&format!("navigator.clipboard.writeText({link});"));
+                        copied.set(true);
+                    },
+                    if *copied.get() { get_text(cx, "link_copied") } else { get_text(cx, "copy_link") }
+                }
+            ))
+        }
+    )
+}
+
+/// Draws a QR code as an SVG, with the blank border scanners need around it.
+#[component]
+fn QrCodeImage(cx: Scope, qr: QrCode) -> Element<'a> {
+    const QUIET_ZONE: usize = 4;
+    let view = qr.size + QUIET_ZONE * 2;
+
+    let mut path = String::new();
+    for y in 0..qr.size {
+        for x in 0..qr.size {
+            if qr.module(x, y) {
+                path.push_str(&format!("M{} {}h1v1h-1z", x + QUIET_ZONE, y + QUIET_ZONE));
+            }
+        }
+    }
+
+    render!(
+        svg {
+            class: "w-full max-w-xs mx-auto rounded-xl",
+            role: "img",
+            xmlns: "http://www.w3.org/2000/svg",
+            view_box: "0 0 {view} {view}",
+            shape_rendering: "crispEdges",
+            rect {
+                width: "100%",
+                height: "100%",
+                fill: "#ffffff",
+            }
+            path {
+                d: "{path}",
+                fill: "#000000",
+            }
+        }
+    )
+}
+
+/// Exports are built when they're downloaded, as they can be as long as the history.
+#[component]
+fn ExportRow<'a>(
+    cx: Scope,
+    label: &'a str,
+    name: &'a str,
+    json: fn(&Model) -> String,
+    csv: fn(&Model) -> String,
+) -> Element<'a> {
+    let state = fermi::use_atom_ref(cx, &STATE);
+
+    render!(
+        div {
+            class: "flex flex-row gap-2 items-center justify-between p-3 rounded-3xl bg-slate-200",
+            span {
+                class: "font-semibold text-lg px-2",
+                "{label}"
+            }
+            div {
+                class: "flex flex-row gap-2",
+                button {
+                    class: "flex flex-row gap-1 items-center px-3 h-10 rounded-full bg-white font-semibold",
+                    onclick: move |_| {
+                        let json = json(&state.read());
+                        download(cx, &format!("{name}.json"), "application/json", &json);
+                    },
+                    div {
+                        class: "h-6",
+                        assets::ExportIcon {}
+                    }
+                    get_text(cx, "export_json")
+                }
+                button {
+                    class: "flex flex-row gap-1 items-center px-3 h-10 rounded-full bg-white font-semibold",
+                    onclick: move |_| {
+                        let csv = csv(&state.read());
+                        download(cx, &format!("{name}.csv"), "text/csv", &csv);
+                    },
+                    div {
+                        class: "h-6",
+                        assets::ExportIcon {}
+                    }
+                    get_text(cx, "export_csv")
+                }
+            }
+        }
+    )
+}
+
+fn ImportButton(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let result = use_state(cx, || None::<Result<usize, String>>);
+
+    let message = match result.get() {
+        Some(Ok(count)) => get_text_with(cx, "import_done", fluent_args!["count" => *count]),
+        Some(Err(reason)) => get_text_with(
+            cx,
+            "import_failed",
+            fluent_args!["reason" => reason.as_str()],
+        ),
+        None => get_text(cx, "import_hint").to_string(),
+    };
+    let message_color = match result.get() {
+        Some(Err(_)) => "text-red-500",
+        _ => "text-slate-500",
+    };
+
+    render!(
+        div {
+            class: "flex flex-col gap-2",
+            label {
+                class: "flex flex-row gap-2 items-center justify-center h-12 rounded-full bg-slate-200 font-semibold text-lg cursor-pointer",
+                div {
+                    class: "h-8 rotate-180",
+                    assets::ExportIcon {}
+                }
+                get_text(cx, "import_games")
+                input {
+                    class: "hidden",
+                    r#type: "file",
+                    accept: ".json,application/json",
+                    onchange: move |evt| {
+                        let state = state.clone();
+                        let result = result.clone();
+                        cx.spawn(async move {
+                            let Some(files) = &evt.files else {
+                                return;
+                            };
+                            for file in files.files() {
+                                if let Some(text) = files.read_file_to_string(&file).await {
+                                    let imported = state.write().import(&text);
+                                    result.set(Some(imported.map_err(|err| err.to_string())));
+                                }
+                            }
+                        });
+                    },
+                }
+            }
+            p {
+                class: "text-sm text-center {message_color}",
+                "{message}"
+            }
+        }
+    )
+}
+
+fn BackupSection(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let pending = use_state(cx, || None::<Backup>);
+    let error = use_state(cx, || None::<String>);
+
+    render!(
+        div {
+            class: "flex flex-col gap-2 p-3 rounded-3xl bg-slate-200",
+            span {
+                class: "font-semibold text-lg px-2",
+                get_text(cx, "backup")
+            }
+            p {
+                class: "text-sm text-slate-500 px-2",
+                get_text(cx, "backup_hint")
+            }
+            div {
+                class: "flex flex-row gap-2",
+                button {
+                    class: "flex flex-row grow gap-1 items-center justify-center h-10 rounded-full bg-white font-semibold",
+                    onclick: move |_| {
+                        let backup = state.read().create_backup();
+                        download(cx, "rummy-nights-backup.json", "application/json", &backup);
+                    },
+                    div {
+                        class: "h-6",
+                        assets::ExportIcon {}
+                    }
+                    get_text(cx, "download_backup")
+                }
+                label {
+                    class: "flex flex-row grow gap-1 items-center justify-center h-10 rounded-full bg-white font-semibold cursor-pointer",
+                    div {
+                        class: "h-6 rotate-180",
+                        assets::ExportIcon {}
+                    }
+                    get_text(cx, "restore_backup")
+                    input {
+                        class: "hidden",
+                        r#type: "file",
+                        accept: ".json,application/json",
+                        onchange: move |evt| {
+                            let pending = pending.clone();
+                            let error = error.clone();
+                            cx.spawn(async move {
+                                let Some(files) = &evt.files else {
+                                    return;
+                                };
+                                for file in files.files() {
+                                    if let Some(text) = files.read_file_to_string(&file).await {
+                                        match Backup::from_json(&text) {
+                                            Ok(backup) => {
+                                                pending.set(Some(backup));
+                                                error.set(None);
+                                            }
+                                            Err(err) => {
+                                                pending.set(None);
+                                                error.set(Some(err.to_string()));
+                                            }
+                                        }
+                                    }
+                                }
+                            });
+                        },
+                    }
+                }
+            }
+            error.as_ref().map(|reason| {
+                let message =
+                    get_text_with(cx, "import_failed", fluent_args!["reason" => reason.as_str()]);
+                rsx!(
+                    p {
+                        class: "text-sm text-center text-red-500",
+                        "{message}"
+                    }
+                )
+            }),
+            pending.as_ref().map(|backup| rsx!(
+                BackupPreviewPanel {
+                    preview: backup.preview(),
+                    on_restore: move |mode| {
+                        if let Some(backup) = pending.get().clone() {
+                            state.write().restore_backup(backup, mode);
+                        }
+                        pending.set(None);
+                    },
+                    on_cancel: move |_| pending.set(None),
+                }
+            ))
+        }
+    )
+}
+
+#[component]
+fn BackupPreviewPanel<'a>(
+    cx: Scope,
+    preview: BackupPreview,
+    on_restore: EventHandler<'a, RestoreMode>,
+    on_cancel: EventHandler<'a, ()>,
+) -> Element<'a> {
+    let summary = get_text_with(
+        cx,
+        "backup_preview",
+        fluent_args![
+            "date" => clock::format_date(preview.created_at),
+            "games" => preview.history,
+            "players" => preview.players,
+            "templates" => preview.templates,
+        ],
+    );
+
+    render!(
+        div {
+            class: "flex flex-col gap-2 p-3 rounded-3xl bg-white",
+            p {
+                class: "font-semibold",
+                "{summary}"
+            }
+            preview.has_game.then(|| rsx!(
+                p {
+                    class: "text-sm text-slate-500",
+                    get_text(cx, "backup_has_game")
+                }
+            )),
+            p {
+                class: "text-sm text-slate-500",
+                get_text(cx, "restore_modes")
+            }
+            div {
+                class: "flex flex-row gap-2",
+                button {
+                    class: "grow h-10 rounded-full bg-emerald-300 font-semibold",
+                    onclick: move |_| on_restore.call(RestoreMode::Merge),
+                    get_text(cx, "restore_merge")
+                }
+                button {
+                    class: "grow h-10 rounded-full bg-red-300 font-semibold",
+                    onclick: move |_| on_restore.call(RestoreMode::Replace),
+                    get_text(cx, "restore_replace")
+                }
+                button {
+                    class: "grow h-10 rounded-full bg-slate-200 font-semibold",
+                    onclick: move |_| on_cancel.call(()),
+                    get_text(cx, "cancel")
+                }
+            }
+        }
+    )
+}
+
+fn TopBar(cx: Scope) -> Element {
+    log!("Rendering top bar.");
+    let state = fermi::use_atom_ref(cx, &STATE);
+
+    render!(
+        div {
+            class: "absolute top-0 h-16 grid grid-cols-3 z-10 mx-auto w-full sm:max-w-lg px-8",
+            button {
+                class: "col-start-1 justify-self-start",
+                onclick: move |_| state.write().close_data_screen(),
+                div {
+                    class: "h-10 scale-x-[-1] rtl:scale-x-100",
+                    assets::BackIcon {}
+                }
+            }
+        }
+    )
+}
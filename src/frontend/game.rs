@@ -1,22 +1,47 @@
+use crate::backend::clock;
 use crate::prelude::*;
 use dioxus::prelude::*;
-use std::cmp::Ordering;
+use fluent::fluent_args;
 
 pub fn GameScreen(cx: Scope) -> Element {
     let state = fermi::use_atom_ref(cx, &STATE);
     log!(format!("game status is {:?}", state.read().game.status));
     log!("Rendering game screen.");
 
+    let rules = state.read().game.rules();
+    let show_bonuses = state.read().game.status == GameStatus::Ongoing;
+    let use_tile_bonus = state.read().settings.use_tile_bonus;
+    let by_round = use_state(cx, || false);
+    let view_label = get_text(
+        cx,
+        if *by_round.get() {
+            "totals_view"
+        } else {
+            "rounds_view"
+        },
+    );
+
     render!(
         NavBar {},
         Banner {},
-        PlayerTable {},
-        div {
-            class: "z-20 absolute bottom-4 left-4 flex flex-col gap-2",
-            DoubleGameButton {},
-            (state.read().settings.use_tile_bonus && state.read().game.status == GameStatus::Ongoing)
-            .then(|| rsx!(TileBonusButton {})),
+        LimitCounter {},
+        button {
+            class: "z-10 self-center mb-4 px-4 h-8 rounded-full bg-slate-200 text-sm font-semibold",
+            onclick: move |_| by_round.set(!by_round.get()),
+            "{view_label}"
         }
+        if *by_round.get() {
+            rsx!(breakdown::RoundBreakdown {})
+        } else {
+            rsx!(PlayerTable {})
+        }
+        (!*by_round.get()).then(|| rsx!(div {
+            class: "z-20 absolute bottom-4 start-4 flex flex-col gap-2",
+            (!state.read().read_only && rules.double_games).then(|| rsx!(DoubleGameButton {})),
+            rules.bonuses.iter().enumerate()
+            .filter(|(_, bonus)| show_bonuses && (bonus.value.is_some() || use_tile_bonus))
+            .map(|(kind, bonus)| rsx!(TileBonusButton { kind: kind, label: bonus.label })),
+        }))
 
     )
 }
@@ -26,10 +51,17 @@ fn PlayerTable(cx: Scope) -> Element {
     let state = fermi::use_atom_ref(cx, &STATE);
     let executeJS = use_eval(cx);
 
+    // Tighten the table so more than four players still fit on a phone screen.
+    let table_spacing = if state.read().game.players.len() > 4 {
+        "gap-x-2 px-4 overflow-x-auto"
+    } else {
+        "gap-x-4 px-8"
+    };
+
     render!(
         div {
             //Main table
-            class: "z-10 flex justify-evenly gap-x-4 h-max max-h-[50%] px-8",
+            class: "z-10 flex justify-evenly {table_spacing} h-max max-h-[50%]",
             state.read().game.players.iter().map(|player| {
                 let player_id = player.id;
 
@@ -39,7 +71,7 @@ fn PlayerTable(cx: Scope) -> Element {
 
                 rsx!(
                     div {
-                        class: "flex flex-col gap-2 w-full",
+                        class: "flex flex-col gap-2 w-full min-w-[3rem]",
                         NameButton {
                             name: player.name.clone(),
                             player_id: player_id,
@@ -56,11 +88,7 @@ fn PlayerTable(cx: Scope) -> Element {
                                     id: player_id,
                                     on_score_input: move |evt: FormEvent| {
                                         if state.write().add_score(evt, player_id) {
-                                            let focus_id = match player_id.cmp(&state.read().game.players.len()) {
-                                                Ordering::Greater => 5,
-                                                Ordering::Equal => 1,
-                                                Ordering::Less => player_id + 1,
-                                            };
+                                            let focus_id = state.read().game.next_player_id(player_id);
                                             let focus_score = format!("document.getElementById('{focus_id}').focus();");
                                             let _ = executeJS(&get_score);
                                             let _ = executeJS(&focus_score);
@@ -92,8 +120,8 @@ fn PlayerTable(cx: Scope) -> Element {
     )
 }
 
-#[inline_props]
-fn NameButton(cx: Scope, name: String, player_id: usize, color_index: usize) -> Element {
+#[component]
+fn NameButton(cx: Scope, name: String, player_id: usize, color_index: usize) -> Element<'a> {
     let state = fermi::use_atom_ref(cx, &STATE);
     let is_tile_bonus_active = state.read().game.tile_bonus_button_active;
     let is_double_game_button_active = state.read().game.double_game_button_active;
@@ -118,7 +146,8 @@ fn NameButton(cx: Scope, name: String, player_id: usize, color_index: usize) ->
     render!(
         button {
             // Name - first cell
-            class: "relative rounded-full h-8 {player_background} {player_name_button_style} w-full",
+            class: "relative min-w-0 rounded-full h-8 {player_background} {player_name_button_style} w-full",
+            title: "{name}",
             tabindex: "{tabindex}",
             onclick: move |_| {
                 if is_tile_bonus_active {
@@ -136,15 +165,15 @@ fn NameButton(cx: Scope, name: String, player_id: usize, color_index: usize) ->
                 DealerPin {}
             ))
             p {
-                class: "text-center my-auto {player_text_color} font-semibold",
+                class: "text-center my-auto px-1 truncate {player_text_color} font-semibold",
                 "{name}"
             }
         }
     )
 }
 
-#[inline_props]
-fn ScoreTable(cx: Scope, player: Player) -> Element {
+#[component]
+fn ScoreTable(cx: Scope, player: Player) -> Element<'a> {
     let mut game_count = 0;
     let mut score_id = 0;
 
@@ -174,7 +203,7 @@ fn ScoreTable(cx: Scope, player: Player) -> Element {
     )
 }
 
-#[inline_props]
+#[component]
 fn ScoreItem(
     cx: Scope,
     id: i32,
@@ -183,10 +212,11 @@ fn ScoreItem(
     color_index: usize,
     has_bonus: bool,
     has_double: bool,
-) -> Element {
+) -> Element<'a> {
     let state = fermi::use_atom_ref(cx, &STATE);
     let border = BORDER_COLORS[*color_index];
-    let enable_score_editing = state.read().settings.enable_score_editing;
+    let enable_score_editing =
+        state.read().settings.enable_score_editing && !state.read().read_only;
 
     let bonus_visibility = if *has_bonus { "" } else { "hidden" };
     let double_visibility = if *has_double { "" } else { "hidden" };
@@ -226,19 +256,19 @@ fn ScoreItem(
                 }
             ))
             div {
-                class: "absolute left-0 self-center h-4 {bonus_visibility} rounded-full",
+                class: "absolute start-0 self-center h-4 {bonus_visibility} rounded-full",
                 assets::BonusIcon {}
             }
             div {
-                class: "absolute right-0 self-center {double_visibility} font-bold text-green-600 rounded-full text-sm",
+                class: "absolute end-0 self-center {double_visibility} font-bold text-green-600 rounded-full text-sm",
                 "x2"
             }
         }
     )
 }
 
-#[inline_props]
-fn ScoreTotal(cx: Scope, color_index: usize, sum: i32) -> Element {
+#[component]
+fn ScoreTotal(cx: Scope, color_index: usize, sum: i32) -> Element<'a> {
     let border = BORDER_COLORS[*color_index];
 
     render!(
@@ -253,13 +283,13 @@ fn ScoreTotal(cx: Scope, color_index: usize, sum: i32) -> Element {
     )
 }
 
-#[inline_props]
+#[component]
 fn ScoreInput<'a>(
     cx: Scope,
     id: usize,
     color_index: usize,
     on_score_input: EventHandler<'a, FormEvent>,
-) -> Element {
+) -> Element<'a> {
     let caret = CARET_COLORS[*color_index];
     let border = BORDER_COLORS[*color_index];
 
@@ -304,16 +334,18 @@ fn DoubleGameButton(cx: Scope) -> Element {
                 "x2"
             }
             span {
-                class: "font-semibold text-lg self-center pr-2",
+                class: "min-w-0 truncate font-semibold text-lg self-center pe-2",
                 get_text(cx, "double_game")
             }
         }
     )
 }
 
-fn TileBonusButton(cx: Scope) -> Element {
+#[component]
+fn TileBonusButton(cx: Scope, kind: usize, label: &'static str) -> Element<'a> {
     log!("Rendering tile bonus menu.");
     let state = fermi::use_atom_ref(cx, &STATE);
+    let kind = *kind;
 
     let grayscale = if state.read().game.tile_bonus_granted {
         "grayscale"
@@ -324,8 +356,8 @@ fn TileBonusButton(cx: Scope) -> Element {
     render!(
         button {
             class: "flex flex-row gap-2 h-14 w-full p-2 border border-slate-100 rounded-full {grayscale}",
-            onclick: move |_| state.write().toggle_tile_bonus(),
-            box_shadow: if state.read().game.tile_bonus_button_active {
+            onclick: move |_| state.write().toggle_tile_bonus(kind),
+            box_shadow: if state.read().game.tile_bonus_button_active && state.read().game.active_bonus == kind {
                 "inset 0 2px 4px 0 rgb(0 0 0 / 0.25)"
             } else {
                 "0 1px 3px 0 rgb(0 0 0 / 0.25), 0 1px 2px -1px rgb(0 0 0 / 0.25)"
@@ -335,8 +367,8 @@ fn TileBonusButton(cx: Scope) -> Element {
                 assets::BonusIcon {},
             }
             span {
-                class: "font-semibold text-lg self-center pr-2",
-                get_text(cx, "tile_bonus")
+                class: "min-w-0 truncate font-semibold text-lg self-center pe-2",
+                get_text(cx, label)
             }
         }
     )
@@ -353,6 +385,22 @@ fn NavBar(cx: Scope) -> Element {
     };
 
     log!("Render nav bar.");
+    if state.read().read_only {
+        return render!(
+            div {
+                class: "z-10 h-16 grid grid-cols-3 sm:max-w-lg px-8",
+                button {
+                    class: "col-start-1 justify-self-start",
+                    onclick: move |_| state.write().close_archived_game(),
+                    div {
+                        class: "h-10 scale-x-[-1] rtl:scale-x-100",
+                        assets::BackIcon {}
+                    }
+                }
+            }
+        );
+    }
+
     render!(
         div {
             class: "z-10 h-16 grid grid-cols-3 sm:max-w-lg px-8",
@@ -361,18 +409,30 @@ fn NavBar(cx: Scope) -> Element {
                     class: "col-start-1 justify-self-start",
                     onclick: move |_| state.write().go_to_screen(Screen::PlayerSelect),
                     div {
-                        class: "h-10 scale-x-[-1]",
+                        class: "h-10 scale-x-[-1] rtl:scale-x-100",
                         assets::BackIcon {}
                     }
                 }
             )),
-            button {
-                class: "{button_position}",
-                onclick: move |_| state.write().go_to_screen(Screen::Menu),
-                div {
-                    class: "h-10",
-                    assets::HomeIcon {},
+            UndoRedoButtons {},
+            div {
+                class: "{button_position} flex flex-row gap-2",
+                button {
+                    onclick: move |_| state.write().go_to_screen(Screen::Menu),
+                    div {
+                        class: "h-10",
+                        assets::HomeIcon {},
+                    }
                 }
+                (game_status == GameStatus::Ongoing).then(|| rsx!(
+                    button {
+                        onclick: move |_| state.write().open_data_screen(),
+                        div {
+                            class: "h-8",
+                            assets::ShareIcon {},
+                        }
+                    }
+                ))
             }
             (game_status != GameStatus::Ongoing).then(|| rsx!(
                 button {
@@ -388,16 +448,51 @@ fn NavBar(cx: Scope) -> Element {
     )
 }
 
+fn UndoRedoButtons(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let undo_style = if state.read().game.can_undo() {
+        ""
+    } else {
+        "opacity-30"
+    };
+    let redo_style = if state.read().game.can_redo() {
+        ""
+    } else {
+        "opacity-30"
+    };
+
+    log!("Render undo/redo buttons.");
+    render!(
+        div {
+            class: "col-start-2 justify-self-center flex flex-row gap-4",
+            button {
+                class: "{undo_style}",
+                disabled: !state.read().game.can_undo(),
+                onclick: move |_| state.write().undo(),
+                div {
+                    class: "h-10 scale-x-[-1] rtl:scale-x-100",
+                    assets::RestartIcon {}
+                }
+            }
+            button {
+                class: "{redo_style}",
+                disabled: !state.read().game.can_redo(),
+                onclick: move |_| state.write().redo(),
+                div {
+                    class: "h-10 rtl:scale-x-[-1]",
+                    assets::RestartIcon {}
+                }
+            }
+        }
+    )
+}
+
 fn Banner(cx: Scope) -> Element {
     let state = fermi::use_atom_ref(cx, &STATE);
 
     let (banner_text, banner_color) = if state.read().game.status == GameStatus::Finished {
         (
-            format!(
-                "{} {}",
-                state.read().game.get_winner(),
-                get_text(cx, "banner_win")
-            ),
+            winners_text(cx, state.read().game.get_winners()),
             String::from("border-green-600"),
         )
     } else if state.read().game.tile_bonus_button_active {
@@ -412,7 +507,11 @@ fn Banner(cx: Scope) -> Element {
         )
     } else if state.read().game.warn_incorrect_score {
         (
-            get_text(cx, "banner_wrong_score").to_string(),
+            get_text_with(
+                cx,
+                "banner_wrong_score",
+                fluent_args!["step" => state.read().game.rules().score_step],
+            ),
             String::from("border-red-500"),
         )
     } else {
@@ -431,11 +530,105 @@ fn Banner(cx: Scope) -> Element {
     )
 }
 
+/// Ticks at the start of every minute, until told to stop.
+const MINUTE_TICKER: &str = r#"const MINUTE = 60 * 1000;
+const tick = () => dioxus.send(true);
+let interval;
+const timeout = setTimeout(() => {
+    tick();
+    interval = setInterval(tick, MINUTE);
+}, MINUTE - (Date.now() % MINUTE));
+await dioxus.recv();
+clearTimeout(timeout);
+clearInterval(interval);"#;
+
+/// Rounds played out of the limit, and the time left until the deadline.
+fn LimitCounter(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let create_eval = use_eval(cx);
+    // Nothing else re-renders the counter between scores, so a timed game
+    // needs its own clock to count down the minutes.
+    let ticker = cx.use_hook(|| {
+        state
+            .read()
+            .game
+            .deadline
+            .and_then(|_| create_eval(MINUTE_TICKER).ok())
+    });
+
+    use_future(cx, (), |_| {
+        let ticker = ticker.clone();
+        let update = cx.schedule_update();
+        async move {
+            let Some(ticker) = ticker else {
+                return;
+            };
+            while ticker.recv().await.is_ok() {
+                update();
+            }
+        }
+    });
+
+    use_on_destroy(cx, {
+        let ticker = ticker.clone();
+        move || {
+            if let Some(ticker) = ticker {
+                let _ = ticker.send(serde_json::Value::Bool(true));
+            }
+        }
+    });
+
+    let game = &state.read().game;
+
+    if game.status != GameStatus::Ongoing {
+        return None;
+    }
+
+    let rounds = game.round_limit.map(|limit| {
+        get_text_with(
+            cx,
+            "round_counter",
+            fluent_args!["round" => (game.round + 1).min(limit), "limit" => limit],
+        )
+    });
+    let time = game.deadline.map(|deadline| {
+        let minutes_left = (deadline - clock::now()).div_euclid(60) + 1;
+        if minutes_left <= 0 {
+            get_text(cx, "time_up").to_string()
+        } else {
+            get_text_with(
+                cx,
+                "time_left",
+                fluent_args![
+                    "hours" => minutes_left / 60,
+                    "minutes" => format!("{:02}", minutes_left % 60),
+                ],
+            )
+        }
+    });
+    let text = [rounds, time]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+    if text.is_empty() {
+        return None;
+    }
+
+    render!(
+        span {
+            class: "-mt-6 mb-4 w-max mx-auto text-sm font-semibold text-slate-500",
+            "{text}"
+        }
+    )
+}
+
 fn DealerPin(cx: Scope) -> Element {
     log!("Render dealer pin.");
     render!(
         div {
-            class: "h-7 absolute -top-4 -right-4 scale-x-[-1]",
+            class: "h-7 absolute -top-4 -end-4 scale-x-[-1] rtl:scale-x-100",
             assets::DealerIcon {}
         }
     )
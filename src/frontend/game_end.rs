@@ -8,6 +8,11 @@ pub fn EndScreen(cx: Scope) -> Element {
         state.write().game.sort_players();
     }
     let sorted_players = state.read().game.sorted_players.clone();
+    let winner_label = if state.read().game.get_winners().len() > 1 {
+        "winners_label"
+    } else {
+        "winner_label"
+    };
 
     render!(
         NavBar {
@@ -21,7 +26,7 @@ pub fn EndScreen(cx: Scope) -> Element {
             }
             p {
                 class: "text-center font-bold text-4xl",
-                get_text(cx,"winner_label")
+                get_text(cx, winner_label)
             }
             sorted_players.iter().map(|player| {
                 rsx!(
@@ -34,8 +39,8 @@ pub fn EndScreen(cx: Scope) -> Element {
     )
 }
 
-#[inline_props]
-fn PlayerItem(cx: Scope, player: Player) -> Element {
+#[component]
+fn PlayerItem(cx: Scope, player: Player) -> Element<'a> {
     log!("Rendering player: ");
 
     let background = BG_COLORS[player.color_index];
@@ -65,9 +70,9 @@ fn PlayerItem(cx: Scope, player: Player) -> Element {
                 }
             }
             div {
-                class: "h-12 basis-1/3 {background} rounded-full",
+                class: "h-12 basis-1/3 min-w-0 px-2 {background} rounded-full",
                 p {
-                    class: "text-center relative top-[50%] -translate-y-1/2 text-white font-semibold",
+                    class: "text-center truncate relative top-[50%] -translate-y-1/2 text-white font-semibold",
                     "{player.name}"
                 }
             }
@@ -85,7 +90,7 @@ fn NavBar(cx: Scope) -> Element {
                 class: "col-start-1 justify-self-start",
                 onclick: move |_| state.write().go_to_screen(Screen::Game),
                 div {
-                    class: "h-10 scale-x-[-1]",
+                    class: "h-10 scale-x-[-1] rtl:scale-x-100",
                     assets::BackIcon {}
                 }
             }
@@ -0,0 +1,132 @@
+use crate::prelude::*;
+use dioxus::prelude::*;
+use fermi::use_atom_ref;
+
+pub fn HistoryScreen(cx: Scope) -> Element {
+    log!("Rendering history screen.");
+    render!(TopBar {}, HistoryList {})
+}
+
+fn HistoryList(cx: Scope) -> Element {
+    log!("Rendering history list.");
+
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let history = state.read().history.clone();
+
+    render!(
+        div {
+            class: "flex flex-col grow gap-4 px-8 mt-16 mb-8 overflow-auto",
+            span {
+                class: "font-semibold text-lg border-b-2 border-violet-500 w-max mx-auto mb-4",
+                get_text(cx, "history_prompt")
+            }
+            (history.is_empty()).then(|| rsx!(
+                div {
+                    class: "flex flex-col grow justify-center",
+                    p {
+                        class: "font-semibold italic text-slate-400 text-lg mx-auto",
+                        get_text(cx, "no_history_yet")
+                    }
+                }
+            )),
+            history.iter().rev().map(|entry| {
+                rsx!(
+                    HistoryItem {
+                        entry: entry.clone(),
+                    }
+                )
+            })
+        }
+    )
+}
+
+#[component]
+fn HistoryItem(cx: Scope, entry: ArchivedGame) -> Element<'a> {
+    log!("Rendering archived game.");
+    let state = use_atom_ref(cx, &STATE);
+
+    let id = entry.id;
+    let date = entry.date();
+    let winners = winners_text(cx, entry.winners());
+
+    render!(
+        div {
+            class: "flex flex-col gap-2 p-3 rounded-3xl bg-slate-200",
+            div {
+                class: "flex flex-row justify-between items-center px-2",
+                span {
+                    class: "font-semibold",
+                    "{date}"
+                }
+                span {
+                    class: "font-semibold",
+                    "{winners}"
+                }
+            }
+            div {
+                class: "flex flex-row gap-2 items-center",
+                div {
+                    class: "flex flex-row flex-wrap grow gap-2",
+                    entry.game.players.iter().map(|player| {
+                        let background_color = BG_COLORS[player.color_index];
+                        rsx!(
+                            span {
+                                class: "px-3 rounded-full text-white font-semibold {background_color}",
+                                "{player.name}: {player.sum}"
+                            }
+                        )
+                    })
+                }
+                button {
+                    onclick: move |_| state.write().view_archived_game(id),
+                    div {
+                        class: "h-10",
+                        assets::PlayIcon {}
+                    }
+                }
+                button {
+                    onclick: move |_| state.write().delete_archived_game(id),
+                    div {
+                        class: "h-10",
+                        assets::RemoveIcon {}
+                    }
+                }
+            }
+        }
+    )
+}
+
+fn TopBar(cx: Scope) -> Element {
+    log!("Rendering top bar.");
+    let state = fermi::use_atom_ref(cx, &STATE);
+
+    render!(
+        div {
+            class: "absolute top-0 h-16 grid grid-cols-3 z-10 mx-auto w-full sm:max-w-lg px-8",
+            button {
+                class: "col-start-1 justify-self-start",
+                onclick: move |_| state.write().go_to_screen(Screen::Menu),
+                div {
+                    class: "h-10 scale-x-[-1] rtl:scale-x-100",
+                    assets::BackIcon {}
+                }
+            }
+            button {
+                class: "col-start-2 justify-self-center",
+                onclick: move |_| state.write().open_data_screen(),
+                div {
+                    class: "h-10",
+                    assets::ExportIcon {}
+                }
+            }
+            button {
+                class: "col-start-3 justify-self-end",
+                onclick: move |_| state.write().go_to_screen(Screen::Stats),
+                div {
+                    class: "h-10",
+                    assets::StatsIcon {}
+                }
+            }
+        }
+    )
+}
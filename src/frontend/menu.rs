@@ -8,13 +8,15 @@ pub fn MenuScreen(cx: Scope) -> Element {
 
     render!(
         button {
-            class: "absolute right-4 top-4",
+            class: "absolute end-4 top-4",
             onclick: move |_| state.write().go_to_screen(Screen::Settings),
             div {
                 class: "h-12",
                 assets::SettingsIcon {}
             }
         }
+        (!state.read().storage_problems.is_empty()).then(|| rsx!(StorageWarning {})),
+        state.read().share_error.is_some().then(|| rsx!(ShareWarning {})),
         div {
             class : "flex flex-col grow gap-16 justify-center px-8",
             img {
@@ -35,24 +37,93 @@ pub fn MenuScreen(cx: Scope) -> Element {
                         label: "resume_game",
                         icon: render!(assets::ResumeIcon {})
                     }
+                )),
+                MenuButton {
+                    on_click: move |_| state.write().go_to_screen(Screen::Sync),
+                    label: "play_together",
+                    icon: render!(assets::SyncIcon {})
+                }
+                (!state.read().history.is_empty()).then(|| rsx!(
+                    MenuButton {
+                        on_click: move |_| state.write().go_to_screen(Screen::History),
+                        label: "history",
+                        icon: render!(assets::TrophyIcon {})
+                    }
                 ))
             }
         }
         p {
-            class: "text-white font-semibold text-lg text-center w-max max-w-1/2 px-2 absolute bottom-2 left-2 rounded-full",
+            class: "text-white font-semibold text-lg text-center w-max max-w-1/2 px-2 absolute bottom-2 start-2 rounded-full",
             background: "linear-gradient(270deg, #B465DA 0%, #CF6CC9 28.04%, #EE609C 67.6%, #EE609C 100%)",
             VersionNumber {}
         }
     )
 }
 
-#[inline_props]
+/// Tells the user some saved data couldn't be loaded, and where it was kept.
+fn StorageWarning(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let problems = state.read().storage_problems.clone();
+
+    render!(
+        div {
+            class: "absolute top-20 inset-x-4 z-20 flex flex-col gap-1 p-3 rounded-3xl bg-white border-2 border-red-500",
+            span {
+                class: "font-semibold",
+                get_text(cx, "storage_problem")
+            }
+            problems.iter().map(|problem| rsx!(
+                span {
+                    class: "text-sm text-slate-500 break-words",
+                    "{problem}"
+                }
+            )),
+            button {
+                class: "self-end font-semibold text-red-500",
+                onclick: move |_| state.write().dismiss_storage_problems(),
+                get_text(cx, "dismiss")
+            }
+        }
+    )
+}
+
+/// Tells the user the shared game the app was opened with couldn't be loaded.
+fn ShareWarning(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let reason = state
+        .read()
+        .share_error
+        .as_ref()
+        .map(|err| err.to_string())
+        .unwrap_or_default();
+
+    render!(
+        div {
+            class: "absolute top-20 inset-x-4 z-20 flex flex-col gap-1 p-3 rounded-3xl bg-white border-2 border-red-500",
+            span {
+                class: "font-semibold",
+                get_text(cx, "share_problem")
+            }
+            span {
+                class: "text-sm text-slate-500 break-words",
+                "{reason}"
+            }
+            button {
+                class: "self-end font-semibold text-red-500",
+                onclick: move |_| state.write().dismiss_share_error(),
+                get_text(cx, "dismiss")
+            }
+        }
+    )
+}
+
+#[component]
 fn MenuButton<'a>(
     cx: Scope,
     on_click: EventHandler<'a, MouseEvent>,
     label: &'a str,
     icon: Element<'a>,
-) -> Element {
+) -> Element<'a> {
     log!(format!("Rendering main menu button: {label}"));
 
     render!(
@@ -60,7 +131,7 @@ fn MenuButton<'a>(
             class: "grid grid-cols-6 items-center",
             onclick: |evt| on_click.call(evt),
             p {
-                class: "w-max font-semibold text-center text-2xl col-span-2 col-start-2 justify-self-end",
+                class: "min-w-0 font-semibold text-end text-2xl col-span-3 break-words hyphens-auto",
                 get_text(cx, label)
 
             }
@@ -1,17 +1,27 @@
 //! The front-end part of the app, rendering the individual app screens.
 
+pub mod app;
 pub mod assets;
+pub mod breakdown;
 pub mod credits;
+pub mod data;
 pub mod game;
 pub mod game_end;
+pub mod history;
 pub mod menu;
 pub mod player_select;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod prerender;
 pub mod settings;
+pub mod stats;
+pub mod sync;
 pub mod templates;
+pub mod update;
 
+use crate::backend::i18n;
 use crate::prelude::*;
 use dioxus::prelude::*;
-use phf::phf_map;
+use fluent::{fluent_args, FluentArgs};
 
 pub fn TopLeftSphere(cx: Scope) -> Element {
     render!(div {
@@ -74,7 +84,7 @@ pub fn DecorativeSpheres(cx: Scope) -> Element {
                     BottomRightSphere {}
                     BottomLeftSphere {}
                 ),
-                Screen::Templates => rsx!(
+                Screen::Templates | Screen::History | Screen::Stats | Screen::Data | Screen::Sync => rsx!(
                     TopRightSphere {}
                     BottomLeftSphere {}
                 ),
@@ -92,7 +102,7 @@ pub fn DecorativeSpheres(cx: Scope) -> Element {
     )
 }
 
-pub static BG_COLORS: [&str; 7] = [
+pub static BG_COLORS: [&str; PLAYER_COLORS] = [
     "bg-red-600",
     "bg-orange-500",
     "bg-yellow-400",
@@ -102,7 +112,7 @@ pub static BG_COLORS: [&str; 7] = [
     "bg-pink-500",
 ];
 
-pub static BORDER_COLORS: [&str; 7] = [
+pub static BORDER_COLORS: [&str; PLAYER_COLORS] = [
     "border-red-600",
     "border-orange-500",
     "border-yellow-400",
@@ -112,7 +122,7 @@ pub static BORDER_COLORS: [&str; 7] = [
     "border-pink-500",
 ];
 
-pub static CARET_COLORS: [&str; 7] = [
+pub static CARET_COLORS: [&str; PLAYER_COLORS] = [
     "caret-red-600",
     "caret-orange-500",
     "caret-yellow-400",
@@ -122,83 +132,42 @@ pub static CARET_COLORS: [&str; 7] = [
     "caret-pink-500",
 ];
 
-pub static ENGLISH: phf::Map<&'static str, &'static str> = phf_map! {
-    "start_game" => "New game",
-    "resume_game" => "Resume game",
-    "tile_bonus" => "Tile bonus",
-    "restart" => "Restart app",
-    "clear_data" => "Clear data",
-    "language" => "Language",
-    "score_editing" => "Allow score editing",
-    "dealer_tracking" => "Dealer tracking",
-    "max_score" => "Maximum score:",
-    "end_at_max_score" => "End game at maximum score",
-    "tile_bonus_value" => "Tile bonus value:",
-    "programmer" => "Programming:",
-    "design" => "UI/UX Design:",
-    "icons" => "Icons:",
-    "tech" => "Tech:",
-    "love" => "Made with ❤️ in Romania.",
-    "start_game_button" => "Start game",
-    "insert_player" => "Insert player name",
-    "add_players" => "Add up to 4 players",
-    "banner_win" => "won!",
-    "banner_bonus" => "Who gets the bonus?",
-    "banner_play" => "Good luck and have fun!",
-    "banner_double" => "Double a player's score, or everyone's?",
-    "winner_label" => "THE WINNER IS",
-    "no_templates_yet" => "No templates saved yet - add some!",
-    "template_add" => "Save current players",
-    "name_template" => "Name this template",
-    "template_not_enough" => "Add some players first!",
-    "template_too_many" => "Only 5 templates are allowed!",
-    "template_prompt" => "Add up to 5 templates",
-    "score_checking" => "Check scores",
-    "banner_wrong_score" => "Scores should be multiples of 5!",
-    "double_game" => "Double game",
-    "everyone" => "Everyone"
-};
-
-pub static ROMANIAN: phf::Map<&'static str, &'static str> = phf_map! {
-    "start_game" => "Joc nou",
-    "resume_game" => "Reluați jocul",
-    "tile_bonus" => "Atu",
-    "restart" => "Reporniți",
-    "clear_data" => "Ștergeți datele",
-    "language" => "Limbă",
-    "score_editing" => "Permiteți editarea scorurilor",
-    "dealer_tracking" => "Urmărire dealer",
-    "max_score" => "Scorul maxim:",
-    "end_at_max_score" => "Limită de scor",
-    "tile_bonus_value" => "Valoarea atuului:",
-    "programmer" => "Programator:",
-    "design" => "Design UI/UX:",
-    "icons" => "Pictograme:",
-    "tech" => "Tehnologii:",
-    "love" => "Creat cu ❤️ în România.",
-    "start_game_button" => "Începe jocul",
-    "insert_player" => "Introdu un nume",
-    "add_players" => "Adaugă până la 4 jucători",
-    "banner_win" => "a câștigat!",
-    "banner_bonus" => "Cine primește atuuul?",
-    "banner_play" => "Cel mai bun să câștige!",
-    "banner_double" => "Dublezi scorul unui singur jucător sau al tuturor?",
-    "winner_label" => "CÂȘTIGĂTORUL ESTE",
-    "no_templates_yet" => "Niciun șablon salvat - adaugă câteva!",
-    "template_add" => "Salvați jucătorii actuali",
-    "name_template" => "Numește acest șablon",
-    "template_not_enough" => "Adaugă mai întâi niște jucători!",
-    "template_too_many" => "Poți avea doar 5 șabloane!",
-    "template_prompt" => "Adaugă până la 5 șabloane",
-    "score_checking" => "Verifică scorurile adăugate",
-    "banner_wrong_score" => "Scorurile trebuie să fie multipli de 5!",
-    "double_game" => "Joc dublu",
-    "everyone" => "Tuturor"
-};
-
-pub fn get_text<'a>(cx: &ScopeState, text_key: &'a str) -> &'a str {
-    match fermi::use_atom_ref(cx, &STATE).read().settings.language {
-        2 => ROMANIAN.get(text_key).cloned().unwrap(),
-        _ => ENGLISH.get(text_key).cloned().unwrap(),
-    }
+fn language(cx: &ScopeState) -> Language {
+    fermi::use_atom_ref(cx, &STATE).read().settings.language
+}
+
+/// The text of `key` in the language picked in the settings, kept for as long
+/// as the component's render.
+pub fn get_text<'a>(cx: &'a ScopeState, key: &str) -> &'a str {
+    cx.bump()
+        .alloc_str(&i18n::translate(language(cx), key, None))
+}
+
+/// The text of a message that takes arguments, like `import_done`'s `count`.
+pub fn get_text_with(cx: &ScopeState, key: &str, args: FluentArgs) -> String {
+    i18n::translate(language(cx), key, Some(&args))
+}
+
+/// "Antonio won!", or "Antonio & Vlad share the win!" when there are co-winners.
+pub fn winners_text(cx: &ScopeState, winners: &[String]) -> String {
+    get_text_with(
+        cx,
+        "winners",
+        fluent_args!["names" => winners.join(" & "), "count" => winners.len()],
+    )
+}
+
+/// Let the browser save `contents` as a file.
+pub fn download(cx: &ScopeState, file_name: &str, mime: &str, contents: &str) {
+    let contents = serde_json::to_string(contents).unwrap_or_default();
+    let script = format!(
+        "const link = document.createElement('a');
+        link.href = URL.createObjectURL(new Blob([{contents}], {{ type: '{mime}' }}));
+        link.download = '{file_name}';
+        link.click();
+        // Browsers start the download after the click returns, so the file has
+        // to stay around until then.
+        setTimeout(() => URL.revokeObjectURL(link.href), 1000);"
+    );
+    let _ = use_eval(cx)(&script);
 }
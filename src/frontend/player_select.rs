@@ -1,10 +1,16 @@
 use crate::prelude::*;
 use dioxus::prelude::*;
 use fermi::use_atom_ref;
+use fluent::fluent_args;
 
 pub fn PlayerSelectScreen(cx: Scope) -> Element {
     log!("Rendering player select.");
     let state = fermi::use_atom_ref(cx, &STATE);
+    let add_players = get_text_with(
+        cx,
+        "add_players",
+        fluent_args!["max" => state.read().game.max_players],
+    );
 
     render!(
         TopBar {}
@@ -12,9 +18,12 @@ pub fn PlayerSelectScreen(cx: Scope) -> Element {
             class: "flex flex-col grow pb-8",
             div {
                 class: "flex flex-col grow",
+                (state.read().game.status == GameStatus::NotStarted).then(|| rsx!(
+                    VariantSelect {}
+                )),
                 span {
                     class: "font-semibold text-lg border-b-2 border-emerald-300 w-max mx-auto mb-4",
-                    get_text(cx, "add_players")
+                    "{add_players}"
                 }
                 PlayerSelectTable {}
             },
@@ -25,8 +34,8 @@ pub fn PlayerSelectScreen(cx: Scope) -> Element {
     )
 }
 
-#[inline_props]
-fn PlayerItem(cx: Scope, player: Player) -> Element {
+#[component]
+fn PlayerItem(cx: Scope, player: Player) -> Element<'a> {
     let state = use_atom_ref(cx, &STATE);
 
     let show_player_edit = use_state(cx, || false);
@@ -135,6 +144,59 @@ fn PlayerItem(cx: Scope, player: Player) -> Element {
     )
 }
 
+fn VariantSelect(cx: Scope) -> Element {
+    log!("Rendering variant select.");
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let current = state.read().game.variant;
+    let current_scoring = state.read().game.scoring;
+
+    render!(
+        div {
+            class: "flex flex-col px-8 mb-6 gap-2",
+            span {
+                class: "font-semibold text-lg border-b-2 border-emerald-300 w-max mx-auto",
+                get_text(cx, "choose_variant")
+            }
+            div {
+                class: "flex flex-wrap justify-center gap-2",
+                Variant::ALL.iter().map(|variant| {
+                    let variant = *variant;
+                    let chosen = if variant == current {
+                        "bg-emerald-300"
+                    } else {
+                        "bg-slate-200"
+                    };
+                    rsx!(
+                        button {
+                            class: "px-3 h-8 rounded-full font-semibold {chosen}",
+                            onclick: move |_| state.write().set_variant(variant),
+                            get_text(cx, variant.rules().label)
+                        }
+                    )
+                })
+            }
+            div {
+                class: "flex flex-wrap justify-center gap-2",
+                ScoringMode::ALL.iter().map(|scoring| {
+                    let scoring = *scoring;
+                    let chosen = if scoring == current_scoring {
+                        "bg-emerald-300"
+                    } else {
+                        "bg-slate-200"
+                    };
+                    rsx!(
+                        button {
+                            class: "px-3 h-8 rounded-full text-sm font-semibold {chosen}",
+                            onclick: move |_| state.write().set_scoring(scoring),
+                            get_text(cx, scoring.label())
+                        }
+                    )
+                })
+            }
+        }
+    )
+}
+
 fn PlayerSelectTable(cx: Scope) -> Element {
     log!("Rendering player list.");
     let state = fermi::use_atom_ref(cx, &STATE);
@@ -149,7 +211,7 @@ fn PlayerSelectTable(cx: Scope) -> Element {
                     }
                 )
             }),
-            (state.read().game.players.len() < 4).then(|| rsx!(
+            (state.read().game.players.len() < state.read().game.max_players).then(|| rsx!(
                 PlayerInput {}
             ))
         }
@@ -159,10 +221,27 @@ fn PlayerSelectTable(cx: Scope) -> Element {
 fn PlayerInput(cx: Scope) -> Element {
     let state = fermi::use_atom_ref(cx, &STATE);
     let hide_color_bar = use_state(cx, || true);
-    let color_index = use_state(cx, || 0);
-    let selected_color = BG_COLORS[**color_index];
+    let color_index = use_state(cx, || None::<usize>);
+    let selected_color = BG_COLORS[color_index.unwrap_or(0)];
     let mut color_id = 0;
 
+    // Suggest the known players who aren't in this game yet.
+    let suggestions: Vec<String> = state
+        .read()
+        .roster
+        .entries
+        .iter()
+        .filter(|entry| {
+            !state
+                .read()
+                .game
+                .players
+                .iter()
+                .any(|player| player.uid.as_ref() == Some(&entry.id))
+        })
+        .map(|entry| entry.name.clone())
+        .collect();
+
     log!("Rendering player input.");
 
     render!(
@@ -175,6 +254,7 @@ fn PlayerInput(cx: Scope) -> Element {
 
                     if !name.is_empty() {
                         state.write().add_player(name, **color_index);
+                        color_index.set(None);
                     }
                     //Execute some JS on the spot - weird ergonomics but it works
                     let _ = use_eval(cx)("document.getElementById('name_input').reset();");
@@ -183,6 +263,16 @@ fn PlayerInput(cx: Scope) -> Element {
                     name: "player-name",
                     class: "rounded-full w-3/5 h-8 ring-1 ring-grey text-center self-center",
                     placeholder: get_text(cx, "insert_player"),
+                    list: "roster_names",
+                    autocomplete: "off",
+                }
+                datalist {
+                    id: "roster_names",
+                    suggestions.iter().map(|name| rsx!(
+                        option {
+                            value: "{name}",
+                        }
+                    ))
                 }
                 button {
                     r#type: "submit",
@@ -206,7 +296,7 @@ fn PlayerInput(cx: Scope) -> Element {
                             button {
                                 id: "{color_id}",
                                 class: "h-6 w-6 rounded-full {color} place-self-center",
-                                onclick: move |_| color_index.set(color_id-1),
+                                onclick: move |_| color_index.set(Some(color_id-1)),
                             }
                         )
                     })
@@ -247,7 +337,7 @@ fn TopBar(cx: Scope) -> Element {
                 class: "col-start-1 justify-self-start",
                 onclick: move |_| state.write().clear_and_go_to_menu(),
                 div {
-                    class: "h-10 scale-x-[-1]",
+                    class: "h-10 scale-x-[-1] rtl:scale-x-100",
                     assets::BackIcon {}
                 }
             }
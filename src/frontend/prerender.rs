@@ -0,0 +1,299 @@
+//! Static HTML of the first screens, so something shows while the app loads.
+//!
+//! The web build is pre-rendered once it's built (see `src/bin/prerender.rs`):
+//! the menu goes into `index.html`, where the app hydrates it when it starts
+//! if it would draw the same menu, and the credits become a page of their own
+//! that works without the app.
+
+use crate::backend::storage::MemoryStorage;
+use crate::frontend::app::{LoadedRoot, LoadedRootProps};
+use crate::prelude::*;
+use dioxus::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Stands in for the version number, which changes with every build.
+pub const VERSION_PLACEHOLDER: &str = "{version}";
+
+/// The empty root element the app is mounted into.
+const ROOT_ELEMENT: &str = r#"<div id="main"></div>"#;
+
+/// Where the service worker reads its version from (see `public/sw.js`).
+const VERSION_SCRIPT: &str = "version.js";
+
+/// A screen the way the app shows it to someone without any saved data.
+fn fresh_model(screen: Screen) -> Model {
+    let mut model = Model::with_storage(Rc::new(MemoryStorage::new()));
+    model.checked_storage = true;
+    model.screen = screen;
+    model
+}
+
+fn build(model: Model) -> VirtualDom {
+    let mut dom = VirtualDom::new_with_props(LoadedRoot, LoadedRootProps::new(model));
+    let _ = dom.rebuild();
+    dom
+}
+
+/// The screen as HTML the app can hydrate once it loads.
+pub fn prerender_screen(screen: Screen) -> String {
+    dioxus_ssr::pre_render(&build(fresh_model(screen)))
+}
+
+/// The screen as plain HTML, for pages shown without the app.
+pub fn render_screen(screen: Screen) -> String {
+    dioxus_ssr::render(&build(fresh_model(screen)))
+}
+
+/// Pre-render a built web app in `dist`: the menu goes into `index.html`, the
+/// credits into `credits.html`, and the cache name of the service worker into
+/// `version.js`.
+pub fn prerender_dist(dist: &Path) -> Result<(), String> {
+    let index_path = dist.join("index.html");
+    let index = fs::read_to_string(&index_path).map_err(|err| err.to_string())?;
+    if !index.contains(ROOT_ELEMENT) {
+        return Err(String::from(
+            "index.html has no empty root element - already pre-rendered?",
+        ));
+    }
+
+    let menu = fill_root(&index, &prerender_screen(Screen::Menu));
+    let credits = without_app(&fill_root(&index, &render_screen(Screen::Credits)));
+
+    fs::write(&index_path, menu).map_err(|err| err.to_string())?;
+    fs::write(dist.join("credits.html"), credits).map_err(|err| err.to_string())?;
+
+    // The build version alone stays the same between releases that don't bump
+    // the package version, so the hash tells their files apart.
+    let version = format!("{}-{:016x}", env!("BUILD_VERSION"), content_hash(dist)?);
+    let script = format!("const BUILD_VERSION = {version:?};\n");
+    fs::write(dist.join(VERSION_SCRIPT), script).map_err(|err| err.to_string())
+}
+
+/// A hash of every file in the build but the version script, with their paths.
+///
+/// It's FNV-1a, which is simple enough to write out and gives the same result on
+/// every machine and toolchain - unlike the hashers in the standard library.
+fn content_hash(dist: &Path) -> Result<u64, String> {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut files = Vec::new();
+    collect_files(dist, &mut files)?;
+    files.sort();
+
+    let mut hash = OFFSET;
+    for path in files {
+        let relative = path.strip_prefix(dist).unwrap_or(&path);
+        if relative == Path::new(VERSION_SCRIPT) {
+            continue;
+        }
+        let contents = fs::read(&path).map_err(|err| err.to_string())?;
+        let name = relative.to_string_lossy();
+        for byte in name.bytes().chain([0]).chain(contents) {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+        }
+    }
+    Ok(hash)
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|err| err.to_string())? {
+        let path = entry.map_err(|err| err.to_string())?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn fill_root(page: &str, html: &str) -> String {
+    page.replacen(ROOT_ELEMENT, &format!(r#"<div id="main">{html}</div>"#), 1)
+}
+
+/// The page without the script that starts the app, so it stays as rendered.
+fn without_app(page: &str) -> String {
+    let Some(start) = page.find(r#"<script type="module">"#) else {
+        return page.to_string();
+    };
+    match page[start..].find("</script>") {
+        Some(end) => format!(
+            "{}{}",
+            &page[..start],
+            &page[start + end + "</script>".len()..]
+        ),
+        None => page.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::app::matches_prerendered_menu;
+    use dioxus::html::prelude::{EvalError, EvalProvider, Evaluator};
+
+    /// Screens that run scripts get none under test, as in a browser without them.
+    struct NoScripts;
+
+    impl EvalProvider for NoScripts {
+        fn new_evaluator(&self, _: String) -> Result<Rc<dyn Evaluator>, EvalError> {
+            Err(EvalError::InvalidJs(String::from("no scripts under test")))
+        }
+    }
+
+    /// Any screen of the app, in the state the model is in.
+    fn render_model(model: Model) -> String {
+        let mut dom = VirtualDom::new_with_props(LoadedRoot, LoadedRootProps::new(model));
+        dom.base_scope()
+            .provide_context(Rc::new(NoScripts) as Rc<dyn EvalProvider>);
+        let _ = dom.rebuild();
+        dioxus_ssr::render(&dom)
+    }
+
+    /// Whether an element with all of `classes` holds `text`.
+    fn has_element(html: &str, classes: &[&str], text: &str) -> bool {
+        html.match_indices(" class=\"").any(|(start, found)| {
+            let rest = &html[start + found.len()..];
+            let Some((class, rest)) = rest.split_once('"') else {
+                return false;
+            };
+            let words: Vec<&str> = class.split_whitespace().collect();
+            let content = rest
+                .split_once('>')
+                .map_or("", |(_, content)| content.split('<').next().unwrap_or(""));
+            classes.iter().all(|class| words.contains(class)) && content.contains(text)
+        })
+    }
+
+    /// Compare with the HTML kept next to this file. Run the tests with
+    /// `UPDATE_SNAPSHOTS=1` to accept changes to a screen.
+    fn assert_snapshot(name: &str, html: &str) {
+        let html = html.replace(env!("BUILD_VERSION"), VERSION_PLACEHOLDER);
+        let path = format!(
+            "{}/src/frontend/snapshots/{name}.html",
+            env!("CARGO_MANIFEST_DIR")
+        );
+
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, &html).unwrap();
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(html == expected, "{name} no longer matches {path}:\n{html}");
+    }
+
+    #[test]
+    fn menu_matches_its_snapshot() {
+        assert_snapshot("menu", &render_screen(Screen::Menu));
+    }
+
+    #[test]
+    fn credits_match_their_snapshot() {
+        assert_snapshot("credits", &render_screen(Screen::Credits));
+    }
+
+    #[test]
+    fn only_the_prerendered_menu_is_hydrated() {
+        let menu = prerender_screen(Screen::Menu);
+        assert!(matches_prerendered_menu(&fresh_model(Screen::Menu)));
+
+        let mut romanian = fresh_model(Screen::Menu);
+        romanian.settings.language = Language::Romanian;
+        let mut resumable = fresh_model(Screen::Menu);
+        resumable.game.status = GameStatus::Ongoing;
+        let mut with_history = fresh_model(Screen::Menu);
+        with_history.history = vec![ArchivedGame::new(1, Game::new())];
+        let settings = fresh_model(Screen::Settings);
+
+        for model in [romanian, resumable, with_history, settings] {
+            assert!(!matches_prerendered_menu(&model));
+            assert_ne!(dioxus_ssr::pre_render(&build(model)), menu);
+        }
+    }
+
+    #[test]
+    fn screens_follow_the_direction_of_their_language() {
+        let mut hebrew = fresh_model(Screen::Menu);
+        hebrew.settings.language = Language::Hebrew;
+        let html = render_model(hebrew);
+        assert!(html.contains(r#"lang="he" dir="rtl""#), "{html}");
+        assert!(html.contains("משחק חדש"));
+
+        let html = render_model(fresh_model(Screen::Settings));
+        assert!(html.contains(r#"lang="en" dir="ltr""#), "{html}");
+    }
+
+    #[test]
+    fn long_labels_stay_inside_their_rows() {
+        let name = "Maximilian-Alexander von Habsburg-Lothringen";
+        let mut playing = fresh_model(Screen::Game);
+        playing.game.add_player(name.to_string(), 0);
+        playing.game.add_player(String::from("Vlad"), 1);
+        // A game without limits draws no counter, which can't be rendered on its own.
+        playing.game.round_limit = Some(10);
+        playing.game.start_game();
+        let html = render_model(playing);
+        assert!(html.contains("Round 1 of 10"), "{html}");
+        assert!(html.contains(&format!(r#"title="{name}""#)), "{html}");
+        assert!(has_element(&html, &["truncate", "px-1"], name), "{html}");
+
+        let html = render_model(fresh_model(Screen::Menu));
+        assert!(
+            has_element(&html, &["min-w-0", "break-words"], "New game"),
+            "{html}"
+        );
+
+        let html = render_model(fresh_model(Screen::Settings));
+        assert!(
+            has_element(&html, &["min-w-0", "break-words"], "Allow score editing"),
+            "{html}"
+        );
+    }
+
+    #[test]
+    fn prerendered_html_can_be_hydrated() {
+        let html = prerender_screen(Screen::Menu);
+
+        // Dynamic text is marked so the app can find it again.
+        assert!(html.contains("<!--#-->"));
+        assert!(html.contains("intro_logo.gif"));
+    }
+
+    const PAGE: &str =
+        r#"<body><div id="main"></div><script type="module">init();</script></body>"#;
+
+    #[test]
+    fn fills_the_root_element() {
+        assert_eq!(
+            fill_root(PAGE, "<p>Menu</p>"),
+            r#"<body><div id="main"><p>Menu</p></div><script type="module">init();</script></body>"#
+        );
+    }
+
+    #[test]
+    fn the_version_follows_the_built_files() {
+        let dist = std::env::temp_dir().join(format!("rummy-nights-dist-{}", std::process::id()));
+        fs::create_dir_all(dist.join("assets")).unwrap();
+        fs::write(dist.join("index.html"), PAGE).unwrap();
+        fs::write(dist.join("assets/app.wasm"), [0, 1, 2]).unwrap();
+
+        let first = content_hash(&dist).unwrap();
+        fs::write(dist.join(VERSION_SCRIPT), "const BUILD_VERSION = \"old\";").unwrap();
+        assert_eq!(content_hash(&dist).unwrap(), first);
+
+        fs::write(dist.join("assets/app.wasm"), [0, 1, 3]).unwrap();
+        let changed = content_hash(&dist).unwrap();
+        fs::remove_dir_all(&dist).unwrap();
+        assert_ne!(changed, first);
+    }
+
+    #[test]
+    fn static_pages_do_not_start_the_app() {
+        assert_eq!(
+            without_app(&fill_root(PAGE, "<p>Credits</p>")),
+            r#"<body><div id="main"><p>Credits</p></div></body>"#
+        );
+    }
+}
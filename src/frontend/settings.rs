@@ -1,13 +1,12 @@
 use crate::prelude::*;
 use dioxus::events::FormEvent;
 use dioxus::prelude::*;
-use gloo_console::log;
-use gloo_storage::{LocalStorage, SessionStorage, Storage};
 
 pub fn SettingsScreen(cx: Scope) -> Element {
     log!("Rendering settings menu.");
     let state = fermi::use_atom_ref(cx, &STATE);
     let settings = state.read().settings;
+    let confirm_clear = use_state(cx, || false);
 
     render!(
         section {
@@ -17,18 +16,18 @@ pub fn SettingsScreen(cx: Scope) -> Element {
                 button {
                     class: "",
                     onclick: move |_| {
-                        state.write().settings.save();
+                        state.read().save_settings();
                         state.write().go_to_screen(Screen::Menu);
                     },
                     div {
-                        class: "h-12 scale-x-[-1]",
+                        class: "h-12 scale-x-[-1] rtl:scale-x-100",
                         assets::BackIcon {}
                     }
                 },
                 button {
                     class: "",
                     onclick: move |_| {
-                        state.write().settings.save();
+                        state.read().save_settings();
                         state.write().go_to_screen(Screen::Credits);
                     },
                     div {
@@ -38,7 +37,7 @@ pub fn SettingsScreen(cx: Scope) -> Element {
                 },
             }
             div {
-                class: "flex flex-col grow justify-evenly px-8 max-h-[70%]",
+                class: "flex flex-col grow justify-evenly gap-2 px-8 max-h-[70%] overflow-y-auto",
                 SwitchSetting {
                     label: get_text(cx, "score_editing"),
                     setting: state.read().settings.enable_score_editing,
@@ -54,6 +53,15 @@ pub fn SettingsScreen(cx: Scope) -> Element {
                     setting: state.read().settings.enable_dealer_tracking,
                     on_switch: move |enabled| state.write().enable_dealer_tracking(enabled),
                 }
+                ValueSetting {
+                    label: get_text(cx, "max_players"),
+                    setting: state.read().settings.max_players as i32,
+                    on_submit: move |value: i32| {
+                        if let Ok(players @ MIN_PLAYERS..=PLAYER_COLORS) = usize::try_from(value) {
+                            state.write().set_max_players(players);
+                        }
+                    },
+                }
                 div {
                     class: "flex flex-col grow gap-4 max-h-32",
                     SwitchSetting {
@@ -65,12 +73,12 @@ pub fn SettingsScreen(cx: Scope) -> Element {
                         ValueSetting {
                             label: get_text(cx, "tile_bonus_value"),
                             setting: state.read().settings.tile_bonus_value,
-                            on_submit: move |value| state.write().settings.set_tile_bonus(value),
+                            on_submit: move |value| state.write().set_tile_bonus(value),
                         },
                     ))
                 },
                 div {
-                    class: "flex flex-col grow gap-4 max-h-32",
+                    class: "flex flex-col grow gap-4 max-h-48",
                     SwitchSetting {
                         label: get_text(cx, "end_at_max_score"),
                         setting: state.read().settings.end_game_at_score,
@@ -80,7 +88,42 @@ pub fn SettingsScreen(cx: Scope) -> Element {
                         ValueSetting {
                             label: get_text(cx, "max_score"),
                             setting: state.read().settings.max_score,
-                            on_submit: move |value| state.write().settings.set_max_score(value),
+                            on_submit: move |value| state.write().set_max_score(value),
+                        },
+                        TieRuleSetting {},
+                    ))
+                },
+                div {
+                    class: "flex flex-col grow gap-4 max-h-32",
+                    SwitchSetting {
+                        label: get_text(cx, "end_after_rounds"),
+                        setting: state.read().settings.end_after_rounds,
+                        on_switch: move |enabled| state.write().enable_round_limit(enabled),
+                    }
+                    settings.end_after_rounds.then(|| rsx!(
+                        ValueSetting {
+                            label: get_text(cx, "round_limit"),
+                            setting: state.read().settings.round_limit as i32,
+                            on_submit: move |value: i32| {
+                                if let Ok(rounds @ 1..) = usize::try_from(value) {
+                                    state.write().set_round_limit(rounds);
+                                }
+                            },
+                        },
+                    ))
+                },
+                div {
+                    class: "flex flex-col grow gap-4 max-h-32",
+                    SwitchSetting {
+                        label: get_text(cx, "end_at_time"),
+                        setting: state.read().settings.end_at_time,
+                        on_switch: move |enabled| state.write().enable_end_time(enabled),
+                    }
+                    settings.end_at_time.then(|| rsx!(
+                        TimeSetting {
+                            label: get_text(cx, "end_time"),
+                            minutes: state.read().settings.end_time,
+                            on_change: move |minutes| state.write().set_end_time(minutes),
                         },
                     ))
                 },
@@ -91,8 +134,8 @@ pub fn SettingsScreen(cx: Scope) -> Element {
                 button {
                     class: "flex flex-row gap-2 items-center w-full place-self-center justify-center",
                     onclick: move |_| {
-                        SessionStorage::clear();
-                        let _ = use_eval(cx)("location.reload()");
+                        state.read().storage.clear(StorageArea::Session);
+                        state.write().restart();
                     },
                     div {
                         class: "h-8",
@@ -106,74 +149,161 @@ pub fn SettingsScreen(cx: Scope) -> Element {
                 button {
                     class: "flex flex-row gap-2 items-center w-full place-self-center justify-center",
                     onclick: move |_| {
-                        LocalStorage::clear();
-                        SessionStorage::clear();
-                        let _ = use_eval(cx)("location.reload()");
+                        state.read().save_settings();
+                        state.write().open_data_screen();
                     },
                     div {
                         class: "h-8",
-                        assets::BinIcon {}
+                        assets::SaveIcon {}
                     }
                     span {
                         class: "font-semibold text-lg leading-8 h-8",
-                        get_text(cx, "clear_data")
+                        get_text(cx, "backup_and_restore")
                     }
                 }
+                (!confirm_clear).then(|| rsx!(
+                    button {
+                        class: "flex flex-row gap-2 items-center w-full place-self-center justify-center",
+                        onclick: move |_| confirm_clear.set(true),
+                        div {
+                            class: "h-8",
+                            assets::BinIcon {}
+                        }
+                        span {
+                            class: "font-semibold text-lg leading-8 h-8",
+                            get_text(cx, "clear_data")
+                        }
+                    }
+                )),
+                confirm_clear.then(|| rsx!(ClearDataConfirm {
+                    on_cancel: move |_| confirm_clear.set(false),
+                }))
             }
         }
     )
 }
 
-fn LanguageSelect(cx: Scope) -> Element {
+/// "Clear data" can't be undone, so offer a backup before going through with it.
+#[component]
+fn ClearDataConfirm<'a>(cx: Scope, on_cancel: EventHandler<'a, ()>) -> Element<'a> {
     let state = fermi::use_atom_ref(cx, &STATE);
-    let mut ro_enabled = "";
-    let mut en_enabled = "";
 
-    match state.read().settings.language {
-        2 => {
-            ro_enabled = "outline";
+    render!(
+        div {
+            class: "flex flex-col gap-2 mx-8 p-3 rounded-3xl border-2 border-red-500",
+            p {
+                class: "font-semibold text-center",
+                get_text(cx, "clear_data_confirm")
+            }
+            div {
+                class: "flex flex-row gap-2",
+                button {
+                    class: "grow h-10 rounded-full bg-emerald-300 font-semibold",
+                    onclick: move |_| {
+                        let backup = state.read().create_backup();
+                        download(cx, "rummy-nights-backup.json", "application/json", &backup);
+                    },
+                    get_text(cx, "download_backup")
+                }
+                button {
+                    class: "grow h-10 rounded-full bg-red-300 font-semibold",
+                    onclick: move |_| {
+                        state.read().clear_storage();
+                        state.write().restart();
+                    },
+                    get_text(cx, "clear_anyway")
+                }
+                button {
+                    class: "grow h-10 rounded-full bg-slate-200 font-semibold",
+                    onclick: move |_| on_cancel.call(()),
+                    get_text(cx, "cancel")
+                }
+            }
         }
-        _ => {
-            en_enabled = "outline";
+    )
+}
+
+fn TieRuleSetting(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let current = state.read().settings.tie_rule;
+
+    render!(
+        div {
+            class: "flex flex-row flex-wrap gap-2 items-center justify-between",
+            span {
+                class: "font-semibold text-lg",
+                get_text(cx, "tie_rule")
+            }
+            div {
+                class: "flex flex-row flex-wrap gap-1",
+                TieRule::ALL.iter().map(|tie_rule| {
+                    let tie_rule = *tie_rule;
+                    let chosen = if tie_rule == current {
+                        "bg-[#ee609c] text-white"
+                    } else {
+                        "bg-slate-200"
+                    };
+                    rsx!(
+                        button {
+                            class: "px-2 h-7 rounded-full text-sm font-semibold {chosen}",
+                            onclick: move |_| state.write().set_tie_rule(tie_rule),
+                            get_text(cx, tie_rule.label())
+                        }
+                    )
+                })
+            }
         }
-    }
+    )
+}
+
+fn LanguageSelect(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let current = state.read().settings.language;
 
     render!(
         div {
-            class: "grid grid-cols-6 gap-4 h-12 py-4 items-center",
+            class: "flex flex-row flex-wrap gap-2 items-center justify-between",
             span {
-                class: "col-span-4 justify-self-start font-semibold text-lg",
+                class: "font-semibold text-lg",
                 get_text(cx, "language")
-            },
-            button {
-                class: "h-8 w-max {ro_enabled} outline-2 outline-offset-4 outline-[#ee609c]",
-                onclick: move |_| state.write().set_language(2),
-                assets::RomanianFlagIcon {},
-            },
-            button {
-                class: "h-8 w-max {en_enabled} outline-2 outline-offset-4 outline-[#ee609c]",
-                onclick: move |_| state.write().set_language(1),
-                assets::EnglishFlagIcon {},
             }
-
+            div {
+                class: "flex flex-row flex-wrap gap-1",
+                Language::ALL.iter().map(|language| {
+                    let language = *language;
+                    let chosen = if language == current {
+                        "bg-[#ee609c] text-white"
+                    } else {
+                        "bg-slate-200"
+                    };
+                    rsx!(
+                        button {
+                            class: "px-2 h-7 rounded-full text-sm font-semibold {chosen}",
+                            lang: language.code(),
+                            onclick: move |_| state.write().set_language(language),
+                            language.name()
+                        }
+                    )
+                })
+            }
         }
     )
 }
 
-#[inline_props]
+#[component]
 fn SwitchSetting<'a>(
     cx: Scope,
     label: &'a str,
     setting: bool,
     on_switch: EventHandler<'a, bool>,
-) -> Element {
+) -> Element<'a> {
     let enabled = use_state(cx, || *setting);
 
     render!(
         div {
             class: "grid grid-cols-6 gap-4 items-center grow max-h-16",
             span {
-                class: "col-span-5 justify-self-start font-semibold text-lg",
+                class: "col-span-5 min-w-0 justify-self-start font-semibold text-lg break-words hyphens-auto",
                 "{label}"
             }
             label {
@@ -189,20 +319,52 @@ fn SwitchSetting<'a>(
                     }
                 }
                 div {
-                    class: "w-11 h-6 bg-gray-200 rounded-full peer peer-focus:outline-none peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-5 after:w-5 after:transition-all peer-checked:bg-[#ee609c]"
+                    class: "w-11 h-6 bg-gray-200 rounded-full peer peer-focus:outline-none peer-checked:after:translate-x-full rtl:peer-checked:after:-translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:start-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-5 after:w-5 after:transition-all peer-checked:bg-[#ee609c]"
                 }
             }
         }
     )
 }
 
-#[inline_props]
+#[component]
+fn TimeSetting<'a>(
+    cx: Scope,
+    label: &'a str,
+    minutes: u16,
+    on_change: EventHandler<'a, u16>,
+) -> Element<'a> {
+    let time = format!("{:02}:{:02}", minutes / 60, minutes % 60);
+
+    render!(
+        div {
+            class: "grid grid-cols-2 gap-4 h-12 pb-2",
+            span {
+                class: "col-span-1 justify-self-end font-semibold text-lg",
+                "{label}"
+            }
+            input {
+                class: "text-lg font-light bg-transparent h-10 w-3/4 text-center rounded focus:border-b-[8px] border-b-4 border-[#ee609c]",
+                r#type: "time",
+                value: "{time}",
+                onchange: move |evt: FormEvent| {
+                    if let Some((hours, minutes)) = evt.value.split_once(':') {
+                        if let (Ok(hours), Ok(minutes)) = (hours.parse::<u16>(), minutes.parse::<u16>()) {
+                            on_change.call(hours * 60 + minutes);
+                        }
+                    }
+                },
+            }
+        }
+    )
+}
+
+#[component]
 fn ValueSetting<'a>(
     cx: Scope,
     label: &'a str,
     setting: i32,
     on_submit: EventHandler<'a, i32>,
-) -> Element {
+) -> Element<'a> {
     let max_score = use_state(cx, || *setting);
     let changed = use_state(cx, || false);
 
@@ -0,0 +1,112 @@
+use crate::prelude::*;
+use dioxus::prelude::*;
+
+pub fn StatsScreen(cx: Scope) -> Element {
+    log!("Rendering stats screen.");
+    render!(TopBar {}, StatsList {})
+}
+
+fn StatsList(cx: Scope) -> Element {
+    log!("Rendering stats list.");
+
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let stats = state.read().player_stats();
+
+    render!(
+        div {
+            class: "flex flex-col grow gap-4 px-8 mt-16 mb-8 overflow-auto",
+            span {
+                class: "font-semibold text-lg border-b-2 border-violet-500 w-max mx-auto mb-4",
+                get_text(cx, "stats_prompt")
+            }
+            (stats.is_empty()).then(|| rsx!(
+                div {
+                    class: "flex flex-col grow justify-center",
+                    p {
+                        class: "font-semibold italic text-slate-400 text-lg mx-auto",
+                        get_text(cx, "no_history_yet")
+                    }
+                }
+            )),
+            stats.into_iter().map(|player_stats| {
+                rsx!(
+                    StatsItem {
+                        stats: player_stats,
+                    }
+                )
+            })
+        }
+    )
+}
+
+#[component]
+fn StatsItem(cx: Scope, stats: PlayerStats) -> Element<'a> {
+    log!("Rendering player stats.");
+
+    let background_color = BG_COLORS[stats.color_index];
+    let win_rate = format!("{:.0}%", stats.win_rate());
+    let average_round = format!("{:.1}", stats.average_round());
+    let best_round = stats
+        .best_round
+        .map_or(String::from("-"), |best| best.to_string());
+    let worst_round = stats
+        .worst_round
+        .map_or(String::from("-"), |worst| worst.to_string());
+
+    render!(
+        div {
+            class: "flex flex-col gap-2 p-3 rounded-3xl bg-slate-200",
+            div {
+                class: "flex justify-center h-8 w-3/5 self-center rounded-full {background_color}",
+                p {
+                    class: "flex self-center text-white font-semibold",
+                    "{stats.name}"
+                }
+            }
+            div {
+                class: "grid grid-cols-2 gap-x-4 px-2",
+                StatsRow { label: get_text(cx, "stats_games"), value: stats.games_played.to_string() }
+                StatsRow { label: get_text(cx, "stats_wins"), value: stats.wins.to_string() }
+                StatsRow { label: get_text(cx, "stats_win_rate"), value: win_rate }
+                StatsRow { label: get_text(cx, "stats_average_round"), value: average_round }
+                StatsRow { label: get_text(cx, "stats_best_round"), value: best_round }
+                StatsRow { label: get_text(cx, "stats_worst_round"), value: worst_round }
+                StatsRow { label: get_text(cx, "stats_tile_bonus"), value: stats.tile_bonuses.to_string() }
+                StatsRow { label: get_text(cx, "stats_doubled"), value: stats.times_doubled.to_string() }
+            }
+        }
+    )
+}
+
+#[component]
+fn StatsRow<'a>(cx: Scope, label: &'a str, value: String) -> Element<'a> {
+    render!(
+        span {
+            class: "font-semibold",
+            "{label}"
+        }
+        span {
+            class: "text-end",
+            "{value}"
+        }
+    )
+}
+
+fn TopBar(cx: Scope) -> Element {
+    log!("Rendering top bar.");
+    let state = fermi::use_atom_ref(cx, &STATE);
+
+    render!(
+        div {
+            class: "absolute top-0 h-16 grid grid-cols-3 z-10 mx-auto w-full sm:max-w-lg px-8",
+            button {
+                class: "col-start-1 justify-self-start",
+                onclick: move |_| state.write().go_to_screen(Screen::History),
+                div {
+                    class: "h-10 scale-x-[-1] rtl:scale-x-100",
+                    assets::BackIcon {}
+                }
+            }
+        }
+    )
+}
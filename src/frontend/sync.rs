@@ -0,0 +1,226 @@
+use crate::backend::sync::{socket_script, SyncRole, DEFAULT_RELAY};
+use crate::prelude::*;
+use dioxus::prelude::*;
+use serde_json::Value;
+
+pub fn SyncScreen(cx: Scope) -> Element {
+    log!("Rendering sync screen.");
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let in_session = state.read().sync.is_some();
+
+    render!(
+        TopBar {},
+        div {
+            class: "flex flex-col grow gap-6 px-8 mt-16 mb-8 overflow-auto",
+            span {
+                class: "font-semibold text-lg border-b-2 border-violet-500 w-max mx-auto",
+                get_text(cx, "sync_prompt")
+            }
+            p {
+                class: "text-sm text-slate-500",
+                get_text(cx, "sync_hint")
+            }
+            if in_session {
+                rsx!(SessionStatus {})
+            } else {
+                rsx!(SessionForm {})
+            }
+        }
+    )
+}
+
+/// Pick a relay, then host the current game or join someone else's.
+fn SessionForm(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let relay = use_state(cx, || String::from(DEFAULT_RELAY));
+    let code = use_state(cx, String::new);
+    let can_host = state.read().game.status == GameStatus::Ongoing;
+
+    render!(
+        div {
+            class: "flex flex-col gap-2 p-3 rounded-3xl bg-slate-200",
+            span {
+                class: "font-semibold px-2",
+                get_text(cx, "relay_address")
+            }
+            input {
+                class: "h-10 rounded-full px-4 bg-white",
+                value: "{relay}",
+                oninput: move |evt: FormEvent| relay.set(evt.value.clone()),
+            }
+        }
+        div {
+            class: "flex flex-col gap-2 p-3 rounded-3xl bg-slate-200",
+            button {
+                class: "h-10 rounded-full bg-emerald-300 font-semibold disabled:opacity-50",
+                disabled: !can_host,
+                onclick: move |_| state.write().host_session(relay.get()),
+                get_text(cx, "host_game")
+            }
+            (!can_host).then(|| rsx!(
+                p {
+                    class: "text-sm text-center text-slate-500",
+                    get_text(cx, "host_needs_game")
+                }
+            ))
+        }
+        div {
+            class: "flex flex-row gap-2 p-3 rounded-3xl bg-slate-200",
+            input {
+                class: "grow min-w-0 h-10 rounded-full px-4 bg-white uppercase tracking-widest",
+                placeholder: get_text(cx, "session_code"),
+                value: "{code}",
+                oninput: move |evt: FormEvent| code.set(evt.value.clone()),
+            }
+            button {
+                class: "px-4 h-10 rounded-full bg-white font-semibold disabled:opacity-50",
+                disabled: code.trim().is_empty(),
+                onclick: move |_| state.write().join_session(relay.get(), code.get()),
+                get_text(cx, "join_game")
+            }
+        }
+    )
+}
+
+fn SessionStatus(cx: Scope) -> Element {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let session = state.read().sync.clone()?;
+
+    let (status, status_color) = if session.connected {
+        (get_text(cx, "sync_connected"), "text-emerald-600")
+    } else {
+        (get_text(cx, "sync_disconnected"), "text-red-500")
+    };
+    let can_open = session.role == SyncRole::Host || session.received_game;
+
+    render!(
+        div {
+            class: "flex flex-col gap-2 p-3 rounded-3xl bg-slate-200",
+            match session.role {
+                SyncRole::Host => rsx!(
+                    span {
+                        class: "font-semibold px-2",
+                        get_text(cx, "hosting")
+                    }
+                    span {
+                        class: "font-bold text-4xl text-center tracking-widest",
+                        "{session.code}"
+                    }
+                ),
+                SyncRole::Guest => rsx!(
+                    span {
+                        class: "font-semibold px-2",
+                        get_text(cx, "joined")
+                    }
+                    span {
+                        class: "font-bold text-2xl text-center tracking-widest",
+                        "{session.code}"
+                    }
+                    (!session.received_game).then(|| rsx!(
+                        p {
+                            class: "text-sm text-center text-slate-500",
+                            get_text(cx, "waiting_for_host")
+                        }
+                    ))
+                ),
+            }
+            p {
+                class: "text-sm text-center {status_color}",
+                "{status}"
+            }
+            div {
+                class: "flex flex-row gap-2",
+                can_open.then(|| rsx!(
+                    button {
+                        class: "grow h-10 rounded-full bg-emerald-300 font-semibold",
+                        onclick: move |_| state.write().go_to_screen(Screen::Game),
+                        get_text(cx, "open_game")
+                    }
+                )),
+                button {
+                    class: "grow h-10 rounded-full bg-red-300 font-semibold",
+                    onclick: move |_| state.write().leave_session(),
+                    get_text(cx, "leave_session")
+                }
+            }
+        }
+    )
+}
+
+/// Keeps the connection to the relay for as long as there is a session,
+/// passing messages between it and the model. It doesn't render anything.
+#[component]
+pub fn SyncAgent(cx: Scope, url: String) -> Element<'a> {
+    let state = fermi::use_atom_ref(cx, &STATE);
+    let create_eval = use_eval(cx);
+    let connection = cx.use_hook(|| create_eval(&socket_script(url)).ok());
+
+    use_future(cx, (), |_| {
+        let connection = connection.clone();
+        let state = state.clone();
+        async move {
+            let Some(connection) = connection else {
+                return;
+            };
+            while let Ok(message) = connection.recv().await {
+                match message["kind"].as_str() {
+                    Some("open") => state.write().set_sync_connected(true),
+                    Some("closed") => state.write().set_sync_connected(false),
+                    Some("message") => {
+                        if let Some(text) = message["text"].as_str() {
+                            state.write().receive_sync(text);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+    });
+
+    use_on_destroy(cx, {
+        let connection = connection.clone();
+        move || {
+            if let Some(connection) = connection {
+                let _ = connection.send(serde_json::json!({ "close": true }));
+            }
+        }
+    });
+
+    // Send new messages once the render is done, since taking them changes the state.
+    use_effect(cx, (&state.read().sync_queued(),), |_| {
+        let connection = connection.clone();
+        let state = state.clone();
+        async move {
+            if !state.read().has_sync_messages() {
+                return;
+            }
+            let messages = state.write().take_sync_messages();
+            if let Some(connection) = connection {
+                for message in messages {
+                    let _ = connection.send(Value::String(message));
+                }
+            }
+        }
+    });
+
+    None
+}
+
+fn TopBar(cx: Scope) -> Element {
+    log!("Rendering top bar.");
+    let state = fermi::use_atom_ref(cx, &STATE);
+
+    render!(
+        div {
+            class: "absolute top-0 h-16 grid grid-cols-3 z-10 mx-auto w-full sm:max-w-lg px-8",
+            button {
+                class: "col-start-1 justify-self-start",
+                onclick: move |_| state.write().go_to_screen(Screen::Menu),
+                div {
+                    class: "h-10 scale-x-[-1] rtl:scale-x-100",
+                    assets::BackIcon {}
+                }
+            }
+        }
+    )
+}
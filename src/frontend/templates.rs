@@ -39,7 +39,7 @@ fn TemplateList(cx: Scope) -> Element {
             })
         }
         div {
-            class: "z-20 absolute bottom-4 right-4",
+            class: "z-20 absolute bottom-4 end-4",
             if templates.len() < 5 {
                 rsx!(
                     (state.read().game.players.len() >= 2).then(|| rsx!(
@@ -70,8 +70,8 @@ fn TemplateList(cx: Scope) -> Element {
     )
 }
 
-#[inline_props]
-fn TemplateItem(cx: Scope, template: GameTemplate) -> Element {
+#[component]
+fn TemplateItem(cx: Scope, template: GameTemplate) -> Element<'a> {
     log!("Rendering template.");
     let state = use_atom_ref(cx, &STATE);
 
@@ -179,7 +179,7 @@ fn AddTemplateButton(cx: Scope) -> Element {
             class: "flex flex-row gap-2 h-14 w-max",
             onclick: move |_| state.write().add_template(),
             span {
-                class: "font-semibold text-lg self-center pl-2",
+                class: "font-semibold text-lg self-center ps-2",
                 get_text(cx, "template_add")
             }
             div {
@@ -202,7 +202,7 @@ fn TopBar(cx: Scope) -> Element {
                 class: "col-start-1 justify-self-start",
                 onclick: move |_| state.write().go_to_screen(Screen::PlayerSelect),
                 div {
-                    class: "h-10 scale-x-[-1]",
+                    class: "h-10 scale-x-[-1] rtl:scale-x-100",
                     assets::BackIcon {}
                 }
             }
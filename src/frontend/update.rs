@@ -0,0 +1,80 @@
+//! Lets people know when a new version of the app has been downloaded.
+//!
+//! The service worker (`public/sw.js`) installs a new build next to the running
+//! one and keeps serving the old files until it is told to take over, so a game
+//! in progress is never interrupted by an update.
+
+use crate::prelude::*;
+use dioxus::prelude::*;
+
+/// Reports a new service worker that is waiting to take over, and hands over
+/// to it when the app asks.
+const WORKER_SCRIPT: &str = r#"if ("serviceWorker" in navigator) {
+    const registration = await navigator.serviceWorker.ready;
+    const announce = (worker) => {
+        if (worker && navigator.serviceWorker.controller) {
+            dioxus.send("available");
+        }
+    };
+    const watch = (worker) => worker && worker.addEventListener("statechange", () => {
+        if (worker.state === "installed") {
+            announce(worker);
+        }
+    });
+    announce(registration.waiting);
+    watch(registration.installing);
+    registration.addEventListener("updatefound", () => watch(registration.installing));
+    setInterval(() => registration.update(), 60 * 60 * 1000);
+
+    await dioxus.recv();
+    navigator.serviceWorker.addEventListener("controllerchange", () => location.reload());
+    if (registration.waiting) {
+        registration.waiting.postMessage({ type: "SKIP_WAITING" });
+    } else {
+        location.reload();
+    }
+}"#;
+
+pub fn UpdatePrompt(cx: Scope) -> Element {
+    let available = use_state(cx, || false);
+    // Only the web app has a service worker - the desktop app and the
+    // pre-rendered pages have nothing to update.
+    let create_eval = cfg!(target_arch = "wasm32").then(|| use_eval(cx));
+    let worker =
+        cx.use_hook(|| create_eval.and_then(|create_eval| create_eval(WORKER_SCRIPT).ok()));
+
+    use_future(cx, (), |_| {
+        let worker = worker.clone();
+        let available = available.clone();
+        async move {
+            let Some(worker) = worker else {
+                return;
+            };
+            while let Ok(message) = worker.recv().await {
+                if message == "available" {
+                    log!("A new version is ready.");
+                    available.set(true);
+                }
+            }
+        }
+    });
+
+    render!(available.get().then(|| rsx!(
+        div {
+            class: "absolute bottom-4 inset-x-4 z-20 mx-auto sm:max-w-md flex flex-row items-center gap-4 p-3 ps-6 rounded-full bg-slate-800 text-white shadow-lg",
+            span {
+                class: "grow text-sm",
+                get_text(cx, "update_available")
+            }
+            button {
+                class: "px-4 h-10 rounded-full bg-emerald-300 text-black font-semibold",
+                onclick: move |_| {
+                    if let Some(worker) = worker {
+                        let _ = worker.send("reload".into());
+                    }
+                },
+                get_text(cx, "reload")
+            }
+        }
+    )))
+}
@@ -1,9 +1,27 @@
 #![allow(non_snake_case)]
 pub mod backend;
+#[cfg(feature = "app")]
 pub mod frontend;
 
+/// Logs to the browser console.
+///
+/// Outside of the browser (e.g. native tests) the arguments are evaluated
+/// and discarded, since there is no console to write to.
+#[macro_export]
+macro_rules! log {
+    ($($arg:expr),* $(,)?) => {{
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        ::gloo_console::log!($($arg),*);
+        #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+        {
+            $(let _ = &$arg;)*
+        }
+    }};
+}
+
 pub mod prelude {
     pub use crate::backend::prelude::*;
+    #[cfg(feature = "app")]
     pub use crate::frontend::*;
-    pub use gloo_console::log;
+    pub use crate::log;
 }
@@ -1,42 +1,46 @@
 #![allow(non_snake_case)]
-use dioxus::prelude::*;
+use rummy_nights::frontend::app::{matches_prerendered_menu, LoadedRoot, LoadedRootProps};
 use rummy_nights::prelude::*;
 
-pub fn App(cx: Scope) -> Element {
-    let state = fermi::use_atom_ref(cx, &STATE);
-    let screen = state.read().screen;
+pub fn main() {
+    log!("Initializing app.");
+
+    let mut model = Model::new();
+    model.initialize_storage();
 
-    if !(state.read().checked_storage) {
-        state.write().initialize_storage();
-        //state.write()._debug_game_screen();
-    };
+    // The pre-rendered menu (see `src/bin/prerender.rs`) is taken over if the app
+    // would draw the same thing, otherwise it only showed while the app loaded.
+    let prerendered = is_prerendered();
+    let hydrate = prerendered && matches_prerendered_menu(&model);
+    if prerendered && !hydrate {
+        clear_root();
+    }
 
-    log!("Start render.");
-    render!(
-        div {
-            class: "flex flex-col bg-white h-screen w-screen relative overflow-hidden",
-            rummy_nights::frontend::DecorativeSpheres {},
-            div {
-                class: "z-10 flex flex-col h-screen mx-auto w-full sm:max-w-lg",
-                match screen {
-                    Screen::Menu => rsx!(rummy_nights::frontend::menu::MenuScreen {}),
-                    Screen::PlayerSelect => rsx!(rummy_nights::frontend::player_select::PlayerSelectScreen {}),
-                    Screen::Templates => rsx!(rummy_nights::frontend::templates::TemplateScreen {}),
-                    Screen::Game => rsx!(rummy_nights::frontend::game::GameScreen {}),
-                    Screen::EndGame => rsx!(rummy_nights::frontend::game_end::EndScreen {}),
-                    Screen::Settings => rsx!(rummy_nights::frontend::settings::SettingsScreen {}),
-                    Screen::Credits => rsx!(rummy_nights::frontend::credits::CreditsScreen {}),
-                }
-            }
-        }
-    )
+    dioxus_web::launch_with_props(
+        LoadedRoot,
+        LoadedRootProps::new(model),
+        dioxus_web::Config::new().hydrate(hydrate),
+    );
 }
 
-pub fn main() {
-    log!("Initializing app.");
+/// Whether the menu was pre-rendered into the page.
+fn is_prerendered() -> bool {
+    #[cfg(target_arch = "wasm32")]
+    {
+        gloo_utils::document()
+            .get_element_by_id("main")
+            .is_some_and(|root| root.has_child_nodes())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        false
+    }
+}
 
-    dioxus_web::launch(|cx| {
-        fermi::use_init_atom_root(cx);
-        render!(App {})
-    });
+/// Remove the pre-rendered menu, so the app is drawn into an empty root.
+fn clear_root() {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(root) = gloo_utils::document().get_element_by_id("main") {
+        root.set_inner_html("");
+    }
 }